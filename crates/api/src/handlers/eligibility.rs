@@ -0,0 +1,96 @@
+//! Scoring eligibility diagnostics handlers
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use pm_domain::{EligibilityCheck, IneligibleReason};
+use pm_storage::{
+    eligibility::{self, EligibilityError},
+    markets,
+};
+use serde::Serialize;
+
+use super::market::market_error;
+use crate::state::AppState;
+
+/// Counts of scoring candidates by the outcome of their latest check
+#[derive(Debug, Serialize)]
+pub struct EligibilitySummaryResponse {
+    pub candidates: i64,
+    pub eligible: i64,
+    pub ineligible: Vec<ReasonCountItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReasonCountItem {
+    pub reason: IneligibleReason,
+    pub markets: i64,
+}
+
+/// A market's latest eligibility check endpoint
+///
+/// Accepts a namespaced market key or a bare venue market id, like the
+/// market details endpoint
+pub async fn market_eligibility_handler(
+    State(state): State<Arc<AppState>>,
+    Path(market_ref): Path<String>,
+) -> Result<Json<EligibilityCheck>, (StatusCode, String)> {
+    let market_id = markets::resolve_market_id(&state.pool, &market_ref)
+        .await
+        .map_err(|e| market_error(e, &market_ref))?;
+
+    let check = eligibility::get_check(&state.pool, &market_id)
+        .await
+        .map_err(|e| match e {
+            EligibilityError::NotFound(_) => (
+                StatusCode::NOT_FOUND,
+                "Market was not a candidate in recent scoring cycles".to_string(),
+            ),
+            _ => {
+                tracing::error!(error = %e, market_id, "Failed to fetch eligibility check");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to fetch eligibility check".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(check))
+}
+
+/// Eligibility counts by reason endpoint
+pub async fn eligibility_summary_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<EligibilitySummaryResponse>, (StatusCode, String)> {
+    let counts = eligibility::count_by_reason(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to count eligibility checks");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to count eligibility checks".to_string(),
+            )
+        })?;
+
+    let mut response = EligibilitySummaryResponse {
+        candidates: 0,
+        eligible: 0,
+        ineligible: Vec::new(),
+    };
+    for count in counts {
+        response.candidates += count.markets;
+        match count.reason {
+            Some(reason) => response.ineligible.push(ReasonCountItem {
+                reason,
+                markets: count.markets,
+            }),
+            None => response.eligible = count.markets,
+        }
+    }
+
+    Ok(Json(response))
+}
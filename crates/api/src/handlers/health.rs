@@ -3,17 +3,28 @@
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, Json};
+use pm_storage::leader::{self, LeaderRole};
 use serde_json::{json, Value};
 
 use crate::state::AppState;
 
 /// Health check endpoint
 ///
-/// Returns service status and database connectivity
+/// Returns service status, database connectivity, and which replica leads
+/// each of ingest and scoring (`null` while none holds the lock)
 pub async fn health_handler(State(state): State<Arc<AppState>>) -> (StatusCode, Json<Value>) {
     // Check database connectivity
     let db_healthy = sqlx::query("SELECT 1").fetch_one(&state.pool).await.is_ok();
 
+    let leaders = leader::list_leaders(&state.pool).await.unwrap_or_default();
+    let leaders: serde_json::Map<String, Value> = LeaderRole::ALL
+        .iter()
+        .map(|role| {
+            let holder = leaders.iter().find(|l| l.role == *role);
+            (role.to_string(), json!(holder))
+        })
+        .collect();
+
     let status = if db_healthy { "healthy" } else { "unhealthy" };
     let status_code = if db_healthy {
         StatusCode::OK
@@ -26,6 +37,7 @@ pub async fn health_handler(State(state): State<Arc<AppState>>) -> (StatusCode,
         Json(json!({
             "status": status,
             "database": db_healthy,
+            "leaders": leaders,
         })),
     )
 }
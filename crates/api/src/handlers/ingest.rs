@@ -0,0 +1,62 @@
+//! Ingest health handlers
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use pm_domain::SchemaDrift;
+use pm_storage::schema_drift;
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppState;
+
+/// Query parameters for the schema drift report
+#[derive(Debug, Default, Deserialize)]
+pub struct SchemaDriftQuery {
+    /// Only drift the latest venue responses still show
+    pub active: Option<bool>,
+}
+
+/// Schema drift report
+#[derive(Debug, Serialize)]
+pub struct SchemaDriftResponse {
+    /// Whether any endpoint is in degraded mode
+    pub degraded: bool,
+    pub degraded_endpoints: Vec<String>,
+    pub drift: Vec<SchemaDrift>,
+}
+
+/// Venue response schema drift endpoint
+pub async fn schema_drift_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SchemaDriftQuery>,
+) -> Result<Json<SchemaDriftResponse>, (StatusCode, String)> {
+    let drift = schema_drift::list_drift(&state.pool, query.active.unwrap_or(false))
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to fetch schema drift");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch schema drift".to_string(),
+            )
+        })?;
+
+    state.metrics.set_schema_drift(&drift);
+
+    let mut degraded_endpoints: Vec<String> = drift
+        .iter()
+        .filter(|d| d.degrades())
+        .map(|d| d.endpoint.clone())
+        .collect();
+    degraded_endpoints.sort();
+    degraded_endpoints.dedup();
+
+    Ok(Json(SchemaDriftResponse {
+        degraded: !degraded_endpoints.is_empty(),
+        degraded_endpoints,
+        drift,
+    }))
+}
@@ -7,7 +7,11 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use pm_storage::{markets, quotes, recs, rules, scores};
+use pm_domain::{MarketKey, MarketMetadata, PriceSource, RetractionReason};
+use pm_storage::{
+    markets::{self, MarketError},
+    quotes, recs, rules, scores,
+};
 use serde::Serialize;
 use serde_json::Value;
 
@@ -27,6 +31,7 @@ pub struct MarketDetailsResponse {
 pub struct MarketInfo {
     pub market_id: String,
     pub venue: String,
+    pub venue_market_id: String,
     pub title: String,
     pub slug: Option<String>,
     pub category: Option<String>,
@@ -35,6 +40,7 @@ pub struct MarketInfo {
     pub close_time: Option<String>,
     pub resolved_time: Option<String>,
     pub url: Option<String>,
+    pub metadata: MarketMetadata,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,6 +54,8 @@ pub struct QuoteInfo {
     pub spread_no: Option<f64>,
     pub mid_yes: Option<f64>,
     pub mid_no: Option<f64>,
+    pub yes_source: PriceSource,
+    pub no_source: PriceSource,
 }
 
 #[derive(Debug, Serialize)]
@@ -86,33 +94,67 @@ pub struct RecommendationInfo {
     pub risk_score: f64,
     pub risk_flags: Vec<Value>,
     pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retracted_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retraction_reason: Option<RetractionReason>,
 }
 
 /// Get market details endpoint
 ///
-/// Returns comprehensive information about a specific market
+/// Accepts a namespaced market key (`polymarket:0xabc`) or, for backward
+/// compatibility, a bare venue market id
 pub async fn market_handler(
     State(state): State<Arc<AppState>>,
-    Path(market_id): Path<String>,
+    Path(market_ref): Path<String>,
+) -> Result<Json<MarketDetailsResponse>, (StatusCode, String)> {
+    let market_id = markets::resolve_market_id(&state.pool, &market_ref)
+        .await
+        .map_err(|e| market_error(e, &market_ref))?;
+
+    market_details(&state, &market_id).await.map(Json)
+}
+
+/// Get market details by venue and venue market id endpoint
+pub async fn venue_market_handler(
+    State(state): State<Arc<AppState>>,
+    Path((venue, venue_market_id)): Path<(String, String)>,
 ) -> Result<Json<MarketDetailsResponse>, (StatusCode, String)> {
+    let market_id = MarketKey::new(venue, venue_market_id).to_string();
+
+    market_details(&state, &market_id).await.map(Json)
+}
+
+/// Map a market lookup error to an HTTP response
+pub(crate) fn market_error(e: MarketError, market_id: &str) -> (StatusCode, String) {
+    match e {
+        MarketError::NotFound(_) => (StatusCode::NOT_FOUND, "Market not found".to_string()),
+        MarketError::Ambiguous(_) => (
+            StatusCode::CONFLICT,
+            "Market id matches several venues; use /v1/market/{venue}/{id}".to_string(),
+        ),
+        _ => {
+            tracing::error!(error = %e, market_id, "Failed to fetch market");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch market".to_string(),
+            )
+        }
+    }
+}
+
+/// Load market details by namespaced market key
+async fn market_details(
+    state: &AppState,
+    market_id: &str,
+) -> Result<MarketDetailsResponse, (StatusCode, String)> {
     // Fetch market
-    let market = markets::get_market(&state.pool, &market_id)
+    let market = markets::get_market(&state.pool, market_id)
         .await
-        .map_err(|e| match e {
-            pm_storage::markets::MarketError::NotFound(_) => {
-                (StatusCode::NOT_FOUND, "Market not found".to_string())
-            }
-            _ => {
-                tracing::error!(error = %e, market_id, "Failed to fetch market");
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Failed to fetch market".to_string(),
-                )
-            }
-        })?;
+        .map_err(|e| market_error(e, market_id))?;
 
     // Fetch quote (optional)
-    let quote = quotes::get_quote_latest(&state.pool, &market_id)
+    let quote = quotes::get_quote_latest(&state.pool, market_id)
         .await
         .ok()
         .map(|q| QuoteInfo {
@@ -125,10 +167,12 @@ pub async fn market_handler(
             spread_no: q.spread_no,
             mid_yes: q.mid_yes,
             mid_no: q.mid_no,
+            yes_source: q.yes_source,
+            no_source: q.no_source,
         });
 
     // Fetch rule (optional)
-    let rule = rules::get_rule(&state.pool, &market_id)
+    let rule = rules::get_rule(&state.pool, market_id)
         .await
         .ok()
         .map(|r| RuleInfo {
@@ -146,7 +190,7 @@ pub async fn market_handler(
         });
 
     // Fetch score (optional)
-    let score = scores::get_score(&state.pool, &market_id)
+    let score = scores::get_score(&state.pool, market_id)
         .await
         .ok()
         .map(|s| ScoreInfo {
@@ -165,7 +209,7 @@ pub async fn market_handler(
 
     // Fetch recommendation (optional)
     let recommendation =
-        recs::get_rec(&state.pool, &market_id)
+        recs::get_rec(&state.pool, market_id)
             .await
             .ok()
             .map(|r| RecommendationInfo {
@@ -181,26 +225,30 @@ pub async fn market_handler(
                     .map(|f| serde_json::to_value(f).unwrap_or(serde_json::Value::Null))
                     .collect(),
                 notes: r.notes,
+                retracted_at: r.retraction.as_ref().map(|x| x.at.to_rfc3339()),
+                retraction_reason: r.retraction.map(|x| x.reason),
             });
 
     let market_info = MarketInfo {
         market_id: market.market_id,
         venue: market.venue,
+        venue_market_id: market.venue_market_id,
         title: market.title,
         slug: market.slug,
         category: market.category,
-        status: format!("{:?}", market.status),
+        status: market.status.as_str().to_string(),
         open_time: market.open_time.map(|t| t.to_rfc3339()),
         close_time: market.close_time.map(|t| t.to_rfc3339()),
         resolved_time: market.resolved_time.map(|t| t.to_rfc3339()),
         url: market.url,
+        metadata: market.metadata,
     };
 
-    Ok(Json(MarketDetailsResponse {
+    Ok(MarketDetailsResponse {
         market: market_info,
         quote,
         rule,
         score,
         recommendation,
-    }))
+    })
 }
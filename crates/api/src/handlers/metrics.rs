@@ -3,6 +3,7 @@
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode};
+use pm_storage::{leader, schema_drift};
 
 use crate::state::AppState;
 
@@ -12,6 +13,17 @@ use crate::state::AppState;
 pub async fn metrics_handler(
     State(state): State<Arc<AppState>>,
 ) -> Result<String, (StatusCode, String)> {
+    // Ingest records schema drift in the database; refresh its gauges
+    match schema_drift::list_drift(&state.pool, true).await {
+        Ok(drift) => state.metrics.set_schema_drift(&drift),
+        Err(e) => tracing::warn!(error = %e, "Failed to refresh schema drift metrics"),
+    }
+
+    match leader::list_leaders(&state.pool).await {
+        Ok(leaders) => state.metrics.set_leaders(&leaders),
+        Err(e) => tracing::warn!(error = %e, "Failed to refresh leader metrics"),
+    }
+
     state.metrics.render().map_err(|e| {
         tracing::error!(error = %e, "Failed to render metrics");
         (
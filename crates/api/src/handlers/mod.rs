@@ -1,11 +1,15 @@
 //! HTTP request handlers
 
+pub mod eligibility;
 pub mod health;
+pub mod ingest;
 pub mod market;
 pub mod metrics;
 pub mod opportunities;
 
+pub use eligibility::{eligibility_summary_handler, market_eligibility_handler};
 pub use health::health_handler;
-pub use market::market_handler;
+pub use ingest::schema_drift_handler;
+pub use market::{market_handler, venue_market_handler};
 pub use metrics::metrics_handler;
 pub use opportunities::opportunities_handler;
@@ -7,7 +7,8 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use pm_storage::recs;
+use pm_domain::RetractionReason;
+use pm_storage::recs::{self, RecFilter};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -28,6 +29,27 @@ pub struct OpportunitiesQuery {
     /// Filter for markets with risk flags
     pub has_flags: Option<bool>,
 
+    /// Market category
+    pub category: Option<String>,
+
+    /// Market tag
+    pub tag: Option<String>,
+
+    /// Parent event id
+    pub event_id: Option<String>,
+
+    /// Minimum 24h traded volume (USD)
+    pub min_volume_24h: Option<f64>,
+
+    /// Minimum displayed liquidity (USD)
+    pub min_liquidity: Option<f64>,
+
+    /// Filter for neg-risk markets
+    pub neg_risk: Option<bool>,
+
+    /// Also list retracted recommendations (hidden by default)
+    pub include_retracted: Option<bool>,
+
     /// Page size (limited by config)
     pub limit: Option<usize>,
 
@@ -56,6 +78,10 @@ pub struct OpportunityItem {
     pub risk_score: f64,
     pub risk_flags: Vec<Value>,
     pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retracted_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retraction_reason: Option<RetractionReason>,
 }
 
 /// List opportunities endpoint
@@ -72,35 +98,33 @@ pub async fn opportunities_handler(
         .min(state.config.max_page_size);
     let offset = params.offset.unwrap_or(0);
 
+    let filter = RecFilter {
+        min_score: params.min_score,
+        max_t_remaining_sec: params.max_t_remaining_sec,
+        max_risk_score: params.max_risk_score,
+        has_flags: params.has_flags,
+        category: params.category,
+        tag: params.tag,
+        event_id: params.event_id,
+        min_volume_24h: params.min_volume_24h,
+        min_liquidity: params.min_liquidity,
+        neg_risk: params.neg_risk,
+        include_retracted: params.include_retracted.unwrap_or(false),
+    };
+
     // Fetch recommendations with filters
-    let recs = recs::list_recs(
-        &state.pool,
-        params.min_score,
-        params.max_t_remaining_sec,
-        params.max_risk_score,
-        params.has_flags,
-        limit,
-        offset,
-    )
-    .await
-    .map_err(|e| {
-        tracing::error!(error = %e, "Failed to fetch recommendations");
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to fetch opportunities".to_string(),
-        )
-    })?;
+    let recs = recs::list_recs(&state.pool, &filter, limit, offset)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to fetch recommendations");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch opportunities".to_string(),
+            )
+        })?;
 
     // Count total matching recommendations
-    let total = recs::count_recs(
-        &state.pool,
-        params.min_score,
-        params.max_t_remaining_sec,
-        params.max_risk_score,
-        params.has_flags,
-    )
-    .await
-    .map_err(|e| {
+    let total = recs::count_recs(&state.pool, &filter).await.map_err(|e| {
         tracing::error!(error = %e, "Failed to count recommendations");
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -125,6 +149,8 @@ pub async fn opportunities_handler(
                 .map(|f| serde_json::to_value(f).unwrap_or(serde_json::Value::Null))
                 .collect(),
             notes: rec.notes,
+            retracted_at: rec.retraction.as_ref().map(|r| r.at.to_rfc3339()),
+            retraction_reason: rec.retraction.map(|r| r.reason),
         })
         .collect();
 
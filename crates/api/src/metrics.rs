@@ -2,9 +2,11 @@
 
 use std::sync::Arc;
 
+use pm_domain::SchemaDrift;
+use pm_storage::leader::{LeaderInfo, LeaderRole};
 use prometheus::{
-    opts, register_int_counter_vec, register_int_gauge, Encoder, IntCounterVec, IntGauge, Registry,
-    TextEncoder,
+    opts, register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Encoder,
+    IntCounterVec, IntGauge, IntGaugeVec, Registry, TextEncoder,
 };
 
 /// Metrics collector for API service
@@ -19,6 +21,15 @@ pub struct Metrics {
     /// Current number of recommendations available
     pub recommendations_gauge: IntGauge,
 
+    /// Venue response fields currently drifting, by endpoint and kind
+    pub schema_drift_gauge: IntGaugeVec,
+
+    /// Number of venue endpoints in degraded mode
+    pub degraded_endpoints_gauge: IntGauge,
+
+    /// Whether some replica holds each role's leader lock
+    pub leader_elected_gauge: IntGaugeVec,
+
     /// Prometheus registry
     registry: Arc<Registry>,
 }
@@ -49,10 +60,37 @@ impl Metrics {
         ))?;
         registry.register(Box::new(recommendations_gauge.clone()))?;
 
+        let schema_drift_gauge = register_int_gauge_vec!(
+            opts!(
+                "pm_ingest_schema_drift_fields",
+                "Venue response fields currently drifting from the baseline schema"
+            ),
+            &["endpoint", "kind"]
+        )?;
+        registry.register(Box::new(schema_drift_gauge.clone()))?;
+
+        let degraded_endpoints_gauge = register_int_gauge!(opts!(
+            "pm_ingest_degraded_endpoints",
+            "Number of venue endpoints in degraded mode due to schema drift"
+        ))?;
+        registry.register(Box::new(degraded_endpoints_gauge.clone()))?;
+
+        let leader_elected_gauge = register_int_gauge_vec!(
+            opts!(
+                "pm_leader_elected",
+                "Whether a replica currently holds the leader lock, by role"
+            ),
+            &["role"]
+        )?;
+        registry.register(Box::new(leader_elected_gauge.clone()))?;
+
         Ok(Self {
             api_requests_total,
             active_markets_gauge,
             recommendations_gauge,
+            schema_drift_gauge,
+            degraded_endpoints_gauge,
+            leader_elected_gauge,
             registry: Arc::new(registry),
         })
     }
@@ -83,6 +121,35 @@ impl Metrics {
     pub fn set_recommendations(&self, count: i64) {
         self.recommendations_gauge.set(count);
     }
+
+    /// Update schema drift gauges from the active drift
+    pub fn set_schema_drift(&self, drift: &[SchemaDrift]) {
+        self.schema_drift_gauge.reset();
+        for d in drift.iter().filter(|d| d.active) {
+            self.schema_drift_gauge
+                .with_label_values(&[d.endpoint.as_str(), d.kind.as_str()])
+                .inc();
+        }
+
+        let mut degraded: Vec<&str> = drift
+            .iter()
+            .filter(|d| d.degrades())
+            .map(|d| d.endpoint.as_str())
+            .collect();
+        degraded.sort_unstable();
+        degraded.dedup();
+        self.degraded_endpoints_gauge.set(degraded.len() as i64);
+    }
+
+    /// Update leader gauges from the current lock holders
+    pub fn set_leaders(&self, leaders: &[LeaderInfo]) {
+        for role in LeaderRole::ALL {
+            let elected = leaders.iter().any(|l| l.role == role);
+            self.leader_elected_gauge
+                .with_label_values(&[role.as_str()])
+                .set(i64::from(elected));
+        }
+    }
 }
 
 impl Default for Metrics {
@@ -6,7 +6,10 @@ use tower_http::trace::TraceLayer;
 
 use crate::{
     config::ApiConfig,
-    handlers::{health_handler, market_handler, metrics_handler, opportunities_handler},
+    handlers::{
+        eligibility_summary_handler, health_handler, market_eligibility_handler, market_handler,
+        metrics_handler, opportunities_handler, schema_drift_handler, venue_market_handler,
+    },
     metrics::Metrics,
     state::AppState,
 };
@@ -30,7 +33,17 @@ impl ApiServer {
             .route("/metrics", get(metrics_handler))
             // API v1 routes
             .route("/v1/opportunities", get(opportunities_handler))
-            .route("/v1/market/:market_id", get(market_handler))
+            .route("/v1/market/{market_id}", get(market_handler))
+            .route(
+                "/v1/market/{venue}/{venue_market_id}",
+                get(venue_market_handler),
+            )
+            .route(
+                "/v1/market/{market_id}/eligibility",
+                get(market_eligibility_handler),
+            )
+            .route("/v1/ingest/schema-drift", get(schema_drift_handler))
+            .route("/v1/scoring/eligibility", get(eligibility_summary_handler))
             // Add trace layer for request logging
             .layer(TraceLayer::new_for_http())
             .with_state(state);
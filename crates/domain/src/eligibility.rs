@@ -0,0 +1,147 @@
+//! Scoring eligibility checks
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Eligibility gate a market failed, leaving it unscored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IneligibleReason {
+    /// Market has no close time
+    MissingCloseTime,
+    /// Time remaining is outside the scoring window
+    OutsideWindow,
+    /// 24h volume below the configured floor
+    LowVolume,
+    /// Displayed liquidity below the configured floor
+    LowLiquidity,
+    /// No latest quote
+    MissingQuote,
+    /// Latest quote has no NO bid
+    MissingNoBid,
+    /// Latest quote has no NO ask
+    MissingNoAsk,
+    /// Latest quote is older than the staleness limit
+    StaleQuote,
+    /// No rule snapshot
+    MissingRule,
+}
+
+/// Error returned when parsing an unknown ineligibility reason string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown ineligibility reason: {0}")]
+pub struct ParseIneligibleReasonError(pub String);
+
+impl IneligibleReason {
+    /// Canonical snake_case name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IneligibleReason::MissingCloseTime => "missing_close_time",
+            IneligibleReason::OutsideWindow => "outside_window",
+            IneligibleReason::LowVolume => "low_volume",
+            IneligibleReason::LowLiquidity => "low_liquidity",
+            IneligibleReason::MissingQuote => "missing_quote",
+            IneligibleReason::MissingNoBid => "missing_no_bid",
+            IneligibleReason::MissingNoAsk => "missing_no_ask",
+            IneligibleReason::StaleQuote => "stale_quote",
+            IneligibleReason::MissingRule => "missing_rule",
+        }
+    }
+}
+
+impl fmt::Display for IneligibleReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IneligibleReason {
+    type Err = ParseIneligibleReasonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "missing_close_time" => Ok(IneligibleReason::MissingCloseTime),
+            "outside_window" => Ok(IneligibleReason::OutsideWindow),
+            "low_volume" => Ok(IneligibleReason::LowVolume),
+            "low_liquidity" => Ok(IneligibleReason::LowLiquidity),
+            "missing_quote" => Ok(IneligibleReason::MissingQuote),
+            "missing_no_bid" => Ok(IneligibleReason::MissingNoBid),
+            "missing_no_ask" => Ok(IneligibleReason::MissingNoAsk),
+            "stale_quote" => Ok(IneligibleReason::StaleQuote),
+            "missing_rule" => Ok(IneligibleReason::MissingRule),
+            _ => Err(ParseIneligibleReasonError(s.to_string())),
+        }
+    }
+}
+
+/// Outcome of the eligibility gates for one market in a scoring cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EligibilityCheck {
+    pub market_id: String,
+    pub checked_at: DateTime<Utc>,
+    /// First gate the market failed; `None` if it was eligible
+    pub reason: Option<IneligibleReason>,
+    /// Values behind the failure, e.g. the time remaining
+    pub detail: Option<String>,
+}
+
+impl EligibilityCheck {
+    /// Market passed every gate
+    pub fn eligible(market_id: &str, checked_at: DateTime<Utc>) -> Self {
+        Self {
+            market_id: market_id.to_string(),
+            checked_at,
+            reason: None,
+            detail: None,
+        }
+    }
+
+    /// Market failed the `reason` gate
+    pub fn failed(
+        market_id: &str,
+        checked_at: DateTime<Utc>,
+        reason: IneligibleReason,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            market_id: market_id.to_string(),
+            checked_at,
+            reason: Some(reason),
+            detail: Some(detail.into()),
+        }
+    }
+
+    pub fn is_eligible(&self) -> bool {
+        self.reason.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ineligible_reason_round_trip() {
+        for reason in [
+            IneligibleReason::MissingCloseTime,
+            IneligibleReason::OutsideWindow,
+            IneligibleReason::LowVolume,
+            IneligibleReason::LowLiquidity,
+            IneligibleReason::MissingQuote,
+            IneligibleReason::MissingNoBid,
+            IneligibleReason::MissingNoAsk,
+            IneligibleReason::StaleQuote,
+            IneligibleReason::MissingRule,
+        ] {
+            assert_eq!(reason.as_str().parse::<IneligibleReason>(), Ok(reason));
+            assert_eq!(
+                serde_json::to_value(reason).unwrap(),
+                serde_json::json!(reason.as_str())
+            );
+        }
+        assert!("unknown".parse::<IneligibleReason>().is_err());
+        assert!("Low_Volume".parse::<IneligibleReason>().is_err());
+    }
+}
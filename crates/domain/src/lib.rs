@@ -2,12 +2,25 @@
 //!
 //! This crate defines the shared types used across all services.
 
+pub mod clock;
+pub mod eligibility;
 pub mod market;
+pub mod payload;
 pub mod quote;
 pub mod risk;
 pub mod score;
+pub mod trade;
 
-pub use market::{Market, MarketStatus, Outcome};
-pub use quote::Quote;
+pub use clock::{Clock, SharedClock, SimulatedClock, SystemClock};
+pub use eligibility::{EligibilityCheck, IneligibleReason, ParseIneligibleReasonError};
+pub use market::{
+    Market, MarketKey, MarketMetadata, MarketStatus, MarketStatusChange, Outcome,
+    ParseMarketKeyError, ParseMarketStatusError, TransitionCheck,
+};
+pub use payload::{DriftKind, ParseDriftKindError, RawPayload, SchemaDrift};
+pub use quote::{
+    PriceSource, QuarantinedQuote, Quote, QuoteCheck, QuoteIssue, QuoteLimits, QuoteValidity,
+};
 pub use risk::{RiskFlag, RuleSnapshot};
-pub use score::{Recommendation, Score};
+pub use score::{ParseRetractionReasonError, Recommendation, Retraction, RetractionReason, Score};
+pub use trade::{ParseTradeSideError, Trade, TradeSide, TradeStats};
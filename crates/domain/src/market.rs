@@ -1,5 +1,7 @@
 //! Market-related domain types
 
+use std::{fmt, str::FromStr};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -13,11 +15,131 @@ pub enum MarketStatus {
     Halted,
 }
 
+/// Outcome of validating a proposed status change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionCheck {
+    /// Proposed status equals the current one
+    Unchanged,
+    /// Legal lifecycle step, apply it
+    Allowed,
+    /// Unusual but possible (e.g. a closed market reopening); keep the
+    /// current status until an operator confirms
+    NeedsReview,
+    /// Impossible step (e.g. leaving `Resolved`); never apply
+    Rejected,
+}
+
+/// Error returned when parsing an unknown status string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown market status: {0}")]
+pub struct ParseMarketStatusError(pub String);
+
+impl MarketStatus {
+    /// Canonical lowercase name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketStatus::Active => "active",
+            MarketStatus::Closed => "closed",
+            MarketStatus::Resolved => "resolved",
+            MarketStatus::Halted => "halted",
+        }
+    }
+
+    /// Whether no further transitions are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, MarketStatus::Resolved)
+    }
+
+    /// Validate moving from this status to `next`
+    ///
+    /// Lifecycle: `Active <-> Halted`, both may move to `Closed` or
+    /// `Resolved`, `Closed -> Resolved`. `Resolved` is terminal. A closed
+    /// market reporting active or halted again needs review.
+    pub fn check_transition(self, next: MarketStatus) -> TransitionCheck {
+        use MarketStatus::*;
+
+        match (self, next) {
+            (from, to) if from == to => TransitionCheck::Unchanged,
+            (Resolved, _) => TransitionCheck::Rejected,
+            (Active | Halted, _) => TransitionCheck::Allowed,
+            (Closed, Resolved) => TransitionCheck::Allowed,
+            (Closed, _) => TransitionCheck::NeedsReview,
+        }
+    }
+}
+
+impl fmt::Display for MarketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarketStatus {
+    type Err = ParseMarketStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(MarketStatus::Active),
+            "closed" => Ok(MarketStatus::Closed),
+            "resolved" => Ok(MarketStatus::Resolved),
+            "halted" => Ok(MarketStatus::Halted),
+            _ => Err(ParseMarketStatusError(s.to_string())),
+        }
+    }
+}
+
+/// Venue-namespaced market identity
+///
+/// Rendered as `<venue>:<venue_market_id>`, which is the form stored in
+/// `markets.market_id` and referenced by every child table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MarketKey {
+    pub venue: String,
+    pub venue_market_id: String,
+}
+
+/// Error returned when parsing a string that is not a namespaced market key
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid market key (expected <venue>:<id>): {0}")]
+pub struct ParseMarketKeyError(pub String);
+
+impl MarketKey {
+    /// Create a key from its parts
+    pub fn new(venue: impl Into<String>, venue_market_id: impl Into<String>) -> Self {
+        Self {
+            venue: venue.into(),
+            venue_market_id: venue_market_id.into(),
+        }
+    }
+}
+
+impl fmt::Display for MarketKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.venue, self.venue_market_id)
+    }
+}
+
+impl FromStr for MarketKey {
+    type Err = ParseMarketKeyError;
+
+    /// Split on the first `:`; venue names never contain one, venue ids may
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((venue, id)) if !venue.is_empty() && !id.is_empty() => Ok(Self::new(venue, id)),
+            _ => Err(ParseMarketKeyError(s.to_string())),
+        }
+    }
+}
+
 /// A prediction market
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Market {
+    /// Namespaced key (see [`MarketKey`])
     pub market_id: String,
     pub venue: String,
+    /// Identifier used by the venue's own API
+    pub venue_market_id: String,
     pub title: String,
     pub slug: Option<String>,
     pub category: Option<String>,
@@ -26,6 +148,31 @@ pub struct Market {
     pub close_time: Option<DateTime<Utc>>,
     pub resolved_time: Option<DateTime<Utc>>,
     pub url: Option<String>,
+    #[serde(default)]
+    pub metadata: MarketMetadata,
+    /// Outcomes listed alongside the market by discovery, stored in their
+    /// own table; empty when read back from storage
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<Outcome>,
+}
+
+/// Venue-provided grouping, activity and display metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketMetadata {
+    /// Parent event the market belongs to
+    pub event_id: Option<String>,
+    pub event_title: Option<String>,
+    pub tags: Vec<String>,
+    /// Traded volume over the last 24 hours (USD)
+    pub volume_24h: Option<f64>,
+    /// Lifetime traded volume (USD)
+    pub volume_total: Option<f64>,
+    /// Displayed order book liquidity (USD)
+    pub liquidity: Option<f64>,
+    /// Part of a negative-risk event (mutually exclusive outcomes)
+    pub neg_risk: bool,
+    pub image_url: Option<String>,
+    pub icon_url: Option<String>,
 }
 
 /// Binary outcome (YES/NO)
@@ -35,3 +182,78 @@ pub struct Outcome {
     pub outcome: String,
     pub token_id: Option<String>,
 }
+
+/// A recorded change of a market's status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketStatusChange {
+    pub market_id: String,
+    pub from_status: Option<MarketStatus>,
+    pub to_status: MarketStatus,
+    /// Component that observed the change (e.g. `discovery`, `reconciliation`)
+    pub source: String,
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [MarketStatus; 4] = [
+        MarketStatus::Active,
+        MarketStatus::Closed,
+        MarketStatus::Resolved,
+        MarketStatus::Halted,
+    ];
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in ALL {
+            assert_eq!(status.as_str().parse::<MarketStatus>(), Ok(status));
+        }
+        assert!("delisted".parse::<MarketStatus>().is_err());
+    }
+
+    #[test]
+    fn test_market_key_round_trip() {
+        let key = MarketKey::new("polymarket", "0xabc");
+        assert_eq!(key.to_string(), "polymarket:0xabc");
+        assert_eq!("polymarket:0xabc".parse::<MarketKey>(), Ok(key));
+
+        let nested: MarketKey = "venue:a:b".parse().unwrap();
+        assert_eq!(nested.venue_market_id, "a:b");
+
+        assert!("0xabc".parse::<MarketKey>().is_err());
+        assert!(":0xabc".parse::<MarketKey>().is_err());
+    }
+
+    #[test]
+    fn test_resolved_is_terminal() {
+        for next in ALL {
+            let expected = if next == MarketStatus::Resolved {
+                TransitionCheck::Unchanged
+            } else {
+                TransitionCheck::Rejected
+            };
+            assert_eq!(MarketStatus::Resolved.check_transition(next), expected);
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_transitions() {
+        use MarketStatus::*;
+
+        assert_eq!(Active.check_transition(Halted), TransitionCheck::Allowed);
+        assert_eq!(Halted.check_transition(Active), TransitionCheck::Allowed);
+        assert_eq!(Active.check_transition(Closed), TransitionCheck::Allowed);
+        assert_eq!(Closed.check_transition(Resolved), TransitionCheck::Allowed);
+        assert_eq!(
+            Closed.check_transition(Active),
+            TransitionCheck::NeedsReview
+        );
+        assert_eq!(
+            Closed.check_transition(Halted),
+            TransitionCheck::NeedsReview
+        );
+    }
+}
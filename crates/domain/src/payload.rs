@@ -0,0 +1,99 @@
+//! Raw venue payload and schema drift types
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Raw response body fetched from a venue endpoint, kept so it can be
+/// re-parsed after a parser fix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawPayload {
+    pub venue: String,
+    /// Market the payload belongs to; `None` for discovery pages
+    pub market_id: Option<String>,
+    /// Endpoint class, e.g. `gamma_markets`, `gamma_market`, `clob_book`
+    pub endpoint: String,
+    pub request_url: String,
+    /// Outcome side for order book payloads
+    pub outcome: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+    pub http_status: u16,
+    pub body: Vec<u8>,
+}
+
+/// How a venue response field differs from the recorded baseline schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// Field not in the baseline
+    Unexpected,
+    /// Baseline field that is always present was absent
+    Missing,
+    /// Field arrived with a JSON type the baseline does not allow
+    TypeChanged,
+}
+
+/// Error returned when parsing an unknown drift kind string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown drift kind: {0}")]
+pub struct ParseDriftKindError(pub String);
+
+impl DriftKind {
+    /// Canonical snake_case name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftKind::Unexpected => "unexpected",
+            DriftKind::Missing => "missing",
+            DriftKind::TypeChanged => "type_changed",
+        }
+    }
+}
+
+impl fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DriftKind {
+    type Err = ParseDriftKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unexpected" => Ok(DriftKind::Unexpected),
+            "missing" => Ok(DriftKind::Missing),
+            "type_changed" => Ok(DriftKind::TypeChanged),
+            _ => Err(ParseDriftKindError(s.to_string())),
+        }
+    }
+}
+
+/// Drift of one field of a venue endpoint's responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaDrift {
+    pub venue: String,
+    pub endpoint: String,
+    /// Dotted field path; `[]` marks array elements (e.g. `events[].id`)
+    pub field: String,
+    pub kind: DriftKind,
+    /// JSON types the baseline allows
+    pub expected: Option<String>,
+    /// JSON type last observed
+    pub observed: Option<String>,
+    /// Whether ingest relies on this field
+    pub critical: bool,
+    /// Whether the latest response from the endpoint still shows it
+    pub active: bool,
+    pub occurrences: i64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+impl SchemaDrift {
+    /// Whether this drift puts its endpoint in degraded mode: a critical
+    /// field the latest response lost or retyped
+    pub fn degrades(&self) -> bool {
+        self.critical && self.active && self.kind != DriftKind::Unexpected
+    }
+}
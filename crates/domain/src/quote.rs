@@ -3,6 +3,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Provenance of one side (YES or NO) of a quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    /// Read directly from the outcome token's order book
+    Observed,
+    /// Synthesized as the complement (`1 - p`) of the other side
+    Derived,
+}
+
 /// Top-of-book quote snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Quote {
@@ -16,5 +26,255 @@ pub struct Quote {
     pub spread_no: Option<f64>,
     pub mid_yes: Option<f64>,
     pub mid_no: Option<f64>,
+    pub yes_source: PriceSource,
+    pub no_source: PriceSource,
     pub quote_source: String,
 }
+
+/// Validation outcome for a fetched quote, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteValidity {
+    /// Safe to persist and score
+    Valid,
+    /// Persisted, but recorded for review
+    Suspicious,
+    /// Quarantined; the last good quote stays in place
+    Invalid,
+}
+
+impl QuoteValidity {
+    /// Canonical lowercase name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuoteValidity::Valid => "valid",
+            QuoteValidity::Suspicious => "suspicious",
+            QuoteValidity::Invalid => "invalid",
+        }
+    }
+}
+
+/// Reason a quote failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteIssue {
+    /// No bid or ask on either side
+    Empty,
+    /// A price is not a probability in `[0, 1]`
+    PriceOutOfRange,
+    /// A side's bid is above its ask
+    CrossedBook,
+    /// YES and NO asks sum above the suspicious limit
+    AskSumHigh,
+    /// YES and NO asks sum above the invalid limit
+    AskSumExtreme,
+    /// YES and NO bids sum above 1 by more than the tolerance
+    BidSumHigh,
+    /// A side's spread is wider than the limit
+    WideSpread,
+}
+
+impl QuoteIssue {
+    /// Canonical snake_case name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuoteIssue::Empty => "empty",
+            QuoteIssue::PriceOutOfRange => "price_out_of_range",
+            QuoteIssue::CrossedBook => "crossed_book",
+            QuoteIssue::AskSumHigh => "ask_sum_high",
+            QuoteIssue::AskSumExtreme => "ask_sum_extreme",
+            QuoteIssue::BidSumHigh => "bid_sum_high",
+            QuoteIssue::WideSpread => "wide_spread",
+        }
+    }
+
+    /// How bad a quote with this issue is
+    pub fn severity(&self) -> QuoteValidity {
+        match self {
+            QuoteIssue::Empty
+            | QuoteIssue::PriceOutOfRange
+            | QuoteIssue::CrossedBook
+            | QuoteIssue::AskSumExtreme => QuoteValidity::Invalid,
+            QuoteIssue::AskSumHigh | QuoteIssue::BidSumHigh | QuoteIssue::WideSpread => {
+                QuoteValidity::Suspicious
+            }
+        }
+    }
+}
+
+/// Sanity limits for quote validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteLimits {
+    /// YES + NO asks above this are suspicious
+    pub suspicious_ask_sum: f64,
+
+    /// YES + NO asks above this are invalid
+    pub invalid_ask_sum: f64,
+
+    /// YES + NO bids may exceed 1 by this much before being suspicious
+    pub bid_sum_tolerance: f64,
+
+    /// Spreads wider than this on either side are suspicious
+    pub max_spread: f64,
+}
+
+impl Default for QuoteLimits {
+    fn default() -> Self {
+        Self {
+            suspicious_ask_sum: 1.05,
+            invalid_ask_sum: 1.25,
+            bid_sum_tolerance: 0.02,
+            max_spread: 0.25,
+        }
+    }
+}
+
+/// Result of validating a quote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteCheck {
+    pub validity: QuoteValidity,
+    pub issues: Vec<QuoteIssue>,
+}
+
+impl Quote {
+    /// Check the quote against sanity limits
+    pub fn validate(&self, limits: &QuoteLimits) -> QuoteCheck {
+        let mut issues = Vec::new();
+        let prices = [self.yes_bid, self.yes_ask, self.no_bid, self.no_ask];
+
+        if prices.iter().all(Option::is_none) {
+            issues.push(QuoteIssue::Empty);
+        }
+
+        if prices
+            .iter()
+            .flatten()
+            .any(|p| !p.is_finite() || !(0.0..=1.0).contains(p))
+        {
+            issues.push(QuoteIssue::PriceOutOfRange);
+        }
+
+        let sides = [(self.yes_bid, self.yes_ask), (self.no_bid, self.no_ask)];
+        if sides
+            .iter()
+            .any(|side| matches!(side, (Some(bid), Some(ask)) if bid > ask))
+        {
+            issues.push(QuoteIssue::CrossedBook);
+        }
+
+        if sides
+            .iter()
+            .any(|side| matches!(side, (Some(bid), Some(ask)) if ask - bid > limits.max_spread))
+        {
+            issues.push(QuoteIssue::WideSpread);
+        }
+
+        if let (Some(yes), Some(no)) = (self.yes_ask, self.no_ask) {
+            if yes + no > limits.invalid_ask_sum {
+                issues.push(QuoteIssue::AskSumExtreme);
+            } else if yes + no > limits.suspicious_ask_sum {
+                issues.push(QuoteIssue::AskSumHigh);
+            }
+        }
+
+        if let (Some(yes), Some(no)) = (self.yes_bid, self.no_bid) {
+            if yes + no > 1.0 + limits.bid_sum_tolerance {
+                issues.push(QuoteIssue::BidSumHigh);
+            }
+        }
+
+        let validity = issues
+            .iter()
+            .map(QuoteIssue::severity)
+            .max()
+            .unwrap_or(QuoteValidity::Valid);
+
+        QuoteCheck { validity, issues }
+    }
+}
+
+/// Quote that failed validation, kept for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedQuote {
+    pub quote: Quote,
+    pub validity: QuoteValidity,
+    pub issues: Vec<QuoteIssue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(yes: (f64, f64), no: (f64, f64)) -> Quote {
+        Quote {
+            market_id: "polymarket:0xabc".to_string(),
+            as_of: Utc::now(),
+            yes_bid: Some(yes.0),
+            yes_ask: Some(yes.1),
+            no_bid: Some(no.0),
+            no_ask: Some(no.1),
+            spread_yes: Some(yes.1 - yes.0),
+            spread_no: Some(no.1 - no.0),
+            mid_yes: Some((yes.0 + yes.1) / 2.0),
+            mid_no: Some((no.0 + no.1) / 2.0),
+            yes_source: PriceSource::Observed,
+            no_source: PriceSource::Observed,
+            quote_source: "polymarket".to_string(),
+        }
+    }
+
+    fn check(quote: &Quote) -> QuoteCheck {
+        quote.validate(&QuoteLimits::default())
+    }
+
+    #[test]
+    fn test_consistent_book_is_valid() {
+        let result = check(&quote((0.03, 0.05), (0.95, 0.97)));
+        assert_eq!(result.validity, QuoteValidity::Valid);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        let crossed = check(&quote((0.06, 0.05), (0.95, 0.97)));
+        assert_eq!(crossed.validity, QuoteValidity::Invalid);
+        assert_eq!(crossed.issues, vec![QuoteIssue::CrossedBook]);
+
+        let out_of_range = check(&quote((0.03, 0.05), (0.95, 1.2)));
+        assert_eq!(out_of_range.validity, QuoteValidity::Invalid);
+        assert!(out_of_range.issues.contains(&QuoteIssue::PriceOutOfRange));
+
+        let mut empty = quote((0.0, 0.0), (0.0, 0.0));
+        empty.yes_bid = None;
+        empty.yes_ask = None;
+        empty.no_bid = None;
+        empty.no_ask = None;
+        assert_eq!(check(&empty).issues, vec![QuoteIssue::Empty]);
+
+        let mut nan = quote((0.03, 0.05), (0.95, 0.97));
+        nan.no_bid = Some(f64::NAN);
+        assert_eq!(check(&nan).validity, QuoteValidity::Invalid);
+    }
+
+    #[test]
+    fn test_ask_sum_thresholds() {
+        let high = check(&quote((0.10, 0.12), (0.90, 0.96)));
+        assert_eq!(high.validity, QuoteValidity::Suspicious);
+        assert_eq!(high.issues, vec![QuoteIssue::AskSumHigh]);
+
+        let extreme = check(&quote((0.20, 0.40), (0.80, 0.95)));
+        assert_eq!(extreme.validity, QuoteValidity::Invalid);
+        assert!(extreme.issues.contains(&QuoteIssue::AskSumExtreme));
+    }
+
+    #[test]
+    fn test_suspicious_quotes() {
+        let bids = check(&quote((0.10, 0.11), (0.93, 0.94)));
+        assert_eq!(bids.validity, QuoteValidity::Suspicious);
+        assert_eq!(bids.issues, vec![QuoteIssue::BidSumHigh]);
+
+        let wide = check(&quote((0.01, 0.05), (0.50, 0.95)));
+        assert_eq!(wide.validity, QuoteValidity::Suspicious);
+        assert_eq!(wide.issues, vec![QuoteIssue::WideSpread]);
+    }
+}
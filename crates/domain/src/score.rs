@@ -1,9 +1,11 @@
 //! Score-related domain types
 
+use std::{fmt, str::FromStr};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::risk::RiskFlag;
+use crate::{eligibility::IneligibleReason, risk::RiskFlag};
 
 /// Score snapshot with computed features
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,4 +37,110 @@ pub struct Recommendation {
     pub risk_score: f64,
     pub risk_flags: Vec<RiskFlag>,
     pub notes: Option<String>,
+    /// Set once scoring stops re-issuing the recommendation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retraction: Option<Retraction>,
+}
+
+/// Why a recommendation was retracted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetractionReason {
+    /// Left the eligibility window, e.g. too close to its close time
+    Expired,
+    /// No usable quote within the staleness limit
+    StaleQuote,
+    /// Closed or resolved at the venue
+    Closed,
+    /// Still scored, but too risky to recommend
+    RiskIncrease,
+    /// Failed another eligibility check, e.g. an activity floor
+    Ineligible,
+}
+
+/// Error returned when parsing an unknown retraction reason string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown retraction reason: {0}")]
+pub struct ParseRetractionReasonError(pub String);
+
+impl RetractionReason {
+    /// Canonical snake_case name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetractionReason::Expired => "expired",
+            RetractionReason::StaleQuote => "stale_quote",
+            RetractionReason::Closed => "closed",
+            RetractionReason::RiskIncrease => "risk_increase",
+            RetractionReason::Ineligible => "ineligible",
+        }
+    }
+}
+
+impl From<IneligibleReason> for RetractionReason {
+    /// Retraction for a recommended market that failed `reason`
+    fn from(reason: IneligibleReason) -> Self {
+        match reason {
+            IneligibleReason::OutsideWindow => RetractionReason::Expired,
+            IneligibleReason::MissingQuote
+            | IneligibleReason::MissingNoBid
+            | IneligibleReason::MissingNoAsk
+            | IneligibleReason::StaleQuote => RetractionReason::StaleQuote,
+            IneligibleReason::MissingCloseTime
+            | IneligibleReason::LowVolume
+            | IneligibleReason::LowLiquidity
+            | IneligibleReason::MissingRule => RetractionReason::Ineligible,
+        }
+    }
+}
+
+impl fmt::Display for RetractionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RetractionReason {
+    type Err = ParseRetractionReasonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "expired" => Ok(RetractionReason::Expired),
+            "stale_quote" => Ok(RetractionReason::StaleQuote),
+            "closed" => Ok(RetractionReason::Closed),
+            "risk_increase" => Ok(RetractionReason::RiskIncrease),
+            "ineligible" => Ok(RetractionReason::Ineligible),
+            _ => Err(ParseRetractionReasonError(s.to_string())),
+        }
+    }
+}
+
+/// When and why a recommendation was retracted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Retraction {
+    pub at: DateTime<Utc>,
+    pub reason: RetractionReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retraction_reason_round_trip() {
+        for reason in [
+            RetractionReason::Expired,
+            RetractionReason::StaleQuote,
+            RetractionReason::Closed,
+            RetractionReason::RiskIncrease,
+            RetractionReason::Ineligible,
+        ] {
+            assert_eq!(reason.as_str().parse::<RetractionReason>().unwrap(), reason);
+            assert_eq!(
+                serde_json::to_value(reason).unwrap(),
+                serde_json::json!(reason.as_str())
+            );
+        }
+        assert!("withdrawn".parse::<RetractionReason>().is_err());
+        assert!("Stale_Quote".parse::<RetractionReason>().is_err());
+    }
 }
@@ -0,0 +1,166 @@
+//! Trade print domain types
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Taker side of a fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Error returned when parsing an unknown trade side string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown trade side: {0}")]
+pub struct ParseTradeSideError(pub String);
+
+impl TradeSide {
+    /// Canonical lowercase name, as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        }
+    }
+}
+
+impl fmt::Display for TradeSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TradeSide {
+    type Err = ParseTradeSideError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "buy" => Ok(TradeSide::Buy),
+            "sell" => Ok(TradeSide::Sell),
+            _ => Err(ParseTradeSideError(s.to_string())),
+        }
+    }
+}
+
+/// A single fill on one outcome token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub market_id: String,
+    /// Venue-unique fill id, used to de-duplicate overlapping fetches
+    pub trade_id: String,
+    /// Outcome traded (`YES` or `NO`)
+    pub outcome: String,
+    pub side: TradeSide,
+    pub price: f64,
+    pub size: f64,
+    pub traded_at: DateTime<Utc>,
+}
+
+impl Trade {
+    /// Fill price expressed on the NO side (`1 - p` for YES fills)
+    pub fn no_price(&self) -> f64 {
+        if self.outcome.eq_ignore_ascii_case("YES") {
+            1.0 - self.price
+        } else {
+            self.price
+        }
+    }
+
+    /// Traded notional (price times size)
+    pub fn notional(&self) -> f64 {
+        self.price * self.size
+    }
+}
+
+/// Trade-derived activity features for one market
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TradeStats {
+    /// Price of the most recent fill, on the NO side
+    pub last_no_price: Option<f64>,
+    pub last_trade_at: Option<DateTime<Utc>>,
+    /// Notional traded within the window
+    pub window_notional: f64,
+    /// Fills within the window
+    pub window_count: usize,
+}
+
+impl TradeStats {
+    /// Summarize fills, counting those at or after `now - window`
+    pub fn from_trades(trades: &[Trade], now: DateTime<Utc>, window: Duration) -> Self {
+        let last = trades.iter().max_by_key(|t| t.traded_at);
+        let since = now - window;
+        let recent = trades
+            .iter()
+            .filter(|t| t.traded_at >= since && t.traded_at <= now);
+
+        let (window_notional, window_count) = recent.fold((0.0, 0), |(notional, count), t| {
+            (notional + t.notional(), count + 1)
+        });
+
+        Self {
+            last_no_price: last.map(Trade::no_price),
+            last_trade_at: last.map(|t| t.traded_at),
+            window_notional,
+            window_count,
+        }
+    }
+
+    /// Seconds since the most recent fill
+    pub fn seconds_since_last_trade(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.last_trade_at.map(|t| (now - t).num_seconds().max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(outcome: &str, price: f64, size: f64, age_min: i64, now: DateTime<Utc>) -> Trade {
+        Trade {
+            market_id: "polymarket:0xabc".to_string(),
+            trade_id: format!("{}-{}", outcome, age_min),
+            outcome: outcome.to_string(),
+            side: TradeSide::Buy,
+            price,
+            size,
+            traded_at: now - Duration::minutes(age_min),
+        }
+    }
+
+    #[test]
+    fn test_trade_side_round_trip() {
+        for side in [TradeSide::Buy, TradeSide::Sell] {
+            assert_eq!(side.as_str().parse::<TradeSide>().unwrap(), side);
+        }
+        assert_eq!("BUY".parse::<TradeSide>().unwrap(), TradeSide::Buy);
+        assert!("hold".parse::<TradeSide>().is_err());
+    }
+
+    #[test]
+    fn test_stats_window_and_last_trade() {
+        let now = Utc::now();
+        let trades = vec![
+            trade("NO", 0.96, 100.0, 90, now),
+            trade("NO", 0.95, 200.0, 30, now),
+            trade("YES", 0.03, 1000.0, 5, now),
+        ];
+
+        let stats = TradeStats::from_trades(&trades, now, Duration::hours(1));
+        assert_eq!(stats.window_count, 2);
+        assert!((stats.window_notional - (190.0 + 30.0)).abs() < 1e-9);
+        assert!((stats.last_no_price.unwrap() - 0.97).abs() < 1e-9);
+        assert_eq!(stats.seconds_since_last_trade(now), Some(300));
+    }
+
+    #[test]
+    fn test_stats_without_trades() {
+        let now = Utc::now();
+        let stats = TradeStats::from_trades(&[], now, Duration::hours(1));
+        assert_eq!(stats, TradeStats::default());
+        assert_eq!(stats.seconds_since_last_trade(now), None);
+    }
+}
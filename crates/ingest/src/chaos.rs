@@ -0,0 +1,403 @@
+//! Fault-injection decorator for venue clients
+//!
+//! `ChaosVenueClient` wraps any `VenueClient` and, per method, adds latency
+//! and replaces results with errors, truncated JSON, empty pages or
+//! timeouts at configured rates. It exists to exercise the ingest pipeline's
+//! resilience and is only wired in by debug builds.
+
+use std::{sync::Mutex, time::Duration};
+
+use async_trait::async_trait;
+use pm_domain::{Market, MarketStatus, Outcome, Quote, RuleSnapshot, Trade};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{ClientError, Result, VenueClient};
+
+/// Fault-injection configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChaosConfig {
+    /// Whether to wrap the venue client (ignored by release builds)
+    pub enabled: bool,
+
+    /// RNG seed for reproducible runs
+    pub seed: Option<u64>,
+
+    /// Faults for market discovery
+    #[serde(default)]
+    pub discover_markets: MethodChaos,
+
+    /// Faults for quote polling
+    #[serde(default)]
+    pub get_quotes: MethodChaos,
+
+    /// Faults for rule fetches
+    #[serde(default)]
+    pub get_rules: MethodChaos,
+
+    /// Faults for status lookups
+    #[serde(default)]
+    pub get_market_status: MethodChaos,
+
+    /// Faults for outcome fetches
+    #[serde(default)]
+    pub get_outcomes: MethodChaos,
+
+    /// Faults for trade fetches
+    #[serde(default)]
+    pub get_trades: MethodChaos,
+}
+
+/// Faults injected into a single client method
+///
+/// Rates are probabilities in `[0, 1]`, checked in the order timeout,
+/// error, truncated JSON, empty page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MethodChaos {
+    /// Maximum extra latency per call, drawn uniformly (milliseconds)
+    pub max_latency_ms: u64,
+
+    /// Rate of calls that hang for `timeout_ms` and then fail
+    pub timeout_rate: f64,
+
+    /// How long a timed out call hangs (milliseconds)
+    pub timeout_ms: u64,
+
+    /// Rate of calls that fail outright
+    pub error_rate: f64,
+
+    /// Rate of calls whose response body is cut short
+    pub truncated_json_rate: f64,
+
+    /// Rate of list calls that return an empty page
+    pub empty_rate: f64,
+}
+
+impl ChaosConfig {
+    /// Moderate faults on every method, for local soak runs
+    pub fn flaky() -> Self {
+        let method = MethodChaos {
+            max_latency_ms: 500,
+            timeout_rate: 0.02,
+            timeout_ms: 5000,
+            error_rate: 0.1,
+            truncated_json_rate: 0.05,
+            empty_rate: 0.05,
+        };
+
+        Self {
+            enabled: true,
+            seed: None,
+            discover_markets: method.clone(),
+            get_quotes: method.clone(),
+            get_rules: method.clone(),
+            get_market_status: method.clone(),
+            get_outcomes: method.clone(),
+            get_trades: method,
+        }
+    }
+}
+
+/// Fault chosen for a single call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fault {
+    Timeout,
+    Error,
+    TruncatedJson,
+    Empty,
+}
+
+/// Venue client decorator that injects configured faults
+pub struct ChaosVenueClient<C: VenueClient> {
+    inner: C,
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl<C: VenueClient> ChaosVenueClient<C> {
+    /// Wrap a client
+    pub fn new(inner: C, config: ChaosConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            inner,
+            config,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Draw latency and a fault for one call
+    fn draw(&self, chaos: &MethodChaos) -> (Duration, Option<Fault>) {
+        let mut rng = match self.rng.lock() {
+            Ok(rng) => rng,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let latency = match chaos.max_latency_ms {
+            0 => Duration::ZERO,
+            max => Duration::from_millis(rng.gen_range(0..=max)),
+        };
+
+        let roll: f64 = rng.gen();
+        let mut threshold = 0.0;
+        let mut fault = None;
+        for (rate, candidate) in [
+            (chaos.timeout_rate, Fault::Timeout),
+            (chaos.error_rate, Fault::Error),
+            (chaos.truncated_json_rate, Fault::TruncatedJson),
+            (chaos.empty_rate, Fault::Empty),
+        ] {
+            threshold += rate.clamp(0.0, 1.0);
+            if roll < threshold {
+                fault = Some(candidate);
+                break;
+            }
+        }
+
+        (latency, fault)
+    }
+
+    /// Run a call through the fault injector
+    ///
+    /// `empty` is the empty page for list methods; `None` for methods
+    /// returning a single item, where empty-page faults are skipped.
+    async fn inject<T, F>(
+        &self,
+        method: &str,
+        chaos: &MethodChaos,
+        empty: Option<T>,
+        call: F,
+    ) -> Result<T>
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+        F: std::future::Future<Output = Result<T>>,
+    {
+        let (latency, fault) = self.draw(chaos);
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        match fault {
+            Some(Fault::Timeout) => {
+                tracing::debug!(method, "Injecting timeout");
+                tokio::time::sleep(Duration::from_millis(chaos.timeout_ms)).await;
+                Err(ClientError::Injected(format!("{} timed out", method)))
+            }
+            Some(Fault::Error) => {
+                tracing::debug!(method, "Injecting error");
+                Err(ClientError::Injected(format!("{} failed", method)))
+            }
+            Some(Fault::TruncatedJson) => {
+                tracing::debug!(method, "Injecting truncated JSON");
+                let body = serde_json::to_string(&call.await?)?;
+                let cut = truncation_point(&body);
+                Err(serde_json::from_str::<T>(&body[..cut])
+                    .err()
+                    .map(ClientError::from)
+                    .unwrap_or_else(|| {
+                        ClientError::Injected(format!("{} returned truncated body", method))
+                    }))
+            }
+            Some(Fault::Empty) => match empty {
+                Some(empty) => {
+                    tracing::debug!(method, "Injecting empty page");
+                    Ok(empty)
+                }
+                None => call.await,
+            },
+            None => call.await,
+        }
+    }
+}
+
+/// Cut a JSON body roughly in half, on a character boundary
+fn truncation_point(body: &str) -> usize {
+    let mut cut = body.len() / 2;
+    while !body.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    cut
+}
+
+#[async_trait]
+impl<C: VenueClient> VenueClient for ChaosVenueClient<C> {
+    async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>> {
+        self.inject(
+            "discover_markets",
+            &self.config.discover_markets,
+            Some(Vec::new()),
+            self.inner.discover_markets(limit, offset),
+        )
+        .await
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        self.inject(
+            "get_quotes",
+            &self.config.get_quotes,
+            Some(Vec::new()),
+            self.inner.get_quotes(outcomes),
+        )
+        .await
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        self.inject(
+            "get_rules",
+            &self.config.get_rules,
+            None,
+            self.inner.get_rules(market_id),
+        )
+        .await
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        self.inject(
+            "get_market_status",
+            &self.config.get_market_status,
+            None,
+            self.inner.get_market_status(market_id),
+        )
+        .await
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        self.inject(
+            "get_outcomes",
+            &self.config.get_outcomes,
+            Some(Vec::new()),
+            self.inner.get_outcomes(market_id),
+        )
+        .await
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        self.inject(
+            "get_trades",
+            &self.config.get_trades,
+            Some(Vec::new()),
+            self.inner.get_trades(market_id, limit),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Client that always succeeds with one outcome
+    struct OkClient;
+
+    #[async_trait]
+    impl VenueClient for OkClient {
+        async fn discover_markets(&self, _limit: usize, _offset: usize) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_quotes(&self, _outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+            Err(ClientError::MarketNotFound(market_id.to_string()))
+        }
+
+        async fn get_market_status(&self, _market_id: &str) -> Result<MarketStatus> {
+            Ok(MarketStatus::Active)
+        }
+
+        async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+            Ok(vec![Outcome {
+                market_id: market_id.to_string(),
+                outcome: "YES".to_string(),
+                token_id: Some("1".to_string()),
+            }])
+        }
+
+        async fn get_trades(&self, _market_id: &str, _limit: usize) -> Result<Vec<Trade>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn chaos(method: MethodChaos) -> ChaosConfig {
+        ChaosConfig {
+            enabled: true,
+            seed: Some(7),
+            get_outcomes: method.clone(),
+            get_market_status: method,
+            ..ChaosConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_faults_passes_through() {
+        let client = ChaosVenueClient::new(OkClient, ChaosConfig::default());
+        assert_eq!(client.get_outcomes("m1").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate() {
+        let client = ChaosVenueClient::new(
+            OkClient,
+            chaos(MethodChaos {
+                error_rate: 1.0,
+                ..MethodChaos::default()
+            }),
+        );
+        assert!(matches!(
+            client.get_outcomes("m1").await,
+            Err(ClientError::Injected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_json() {
+        let client = ChaosVenueClient::new(
+            OkClient,
+            chaos(MethodChaos {
+                truncated_json_rate: 1.0,
+                ..MethodChaos::default()
+            }),
+        );
+        assert!(matches!(
+            client.get_outcomes("m1").await,
+            Err(ClientError::Json(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_empty_page_only_for_lists() {
+        let client = ChaosVenueClient::new(
+            OkClient,
+            chaos(MethodChaos {
+                empty_rate: 1.0,
+                ..MethodChaos::default()
+            }),
+        );
+        assert!(client.get_outcomes("m1").await.unwrap().is_empty());
+        assert_eq!(
+            client.get_market_status("m1").await.unwrap(),
+            MarketStatus::Active
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_waits_then_fails() {
+        let client = ChaosVenueClient::new(
+            OkClient,
+            chaos(MethodChaos {
+                timeout_rate: 1.0,
+                timeout_ms: 50,
+                ..MethodChaos::default()
+            }),
+        );
+
+        let started = tokio::time::Instant::now();
+        assert!(client.get_outcomes("m1").await.is_err());
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -1,13 +1,24 @@
 //! Venue client trait and Polymarket implementation
 
+use std::{collections::HashMap, sync::Arc};
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use pm_domain::{Market, MarketStatus, Outcome, Quote, RiskFlag, RuleSnapshot};
+use pm_domain::{
+    Market, MarketKey, MarketMetadata, MarketStatus, Outcome, PriceSource, Quote, RawPayload,
+    RiskFlag, RuleSnapshot, SharedClock, SystemClock, Trade, TradeSide,
+};
+use prometheus::IntCounterVec;
 use reqwest::Client;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use tokio::sync::mpsc::{self, error::TrySendError};
 
-use crate::{config::RetryConfig, retry::retry_with_backoff};
+use crate::{
+    config::{RetryConfig, VenueConfig},
+    retry::retry_with_backoff,
+    schema::SchemaTracker,
+};
 
 /// Error type for venue client operations
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +34,15 @@ pub enum ClientError {
 
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+
+    #[error("Market belongs to another venue: {0}")]
+    ForeignMarket(String),
+
+    #[error("Fixture error: {0}")]
+    Fixture(String),
+
+    #[error("Injected fault: {0}")]
+    Injected(String),
 }
 
 pub type Result<T> = std::result::Result<T, ClientError>;
@@ -33,33 +53,173 @@ pub trait VenueClient: Send + Sync {
     /// Discover active markets (with pagination)
     async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>>;
 
-    /// Get top-of-book quotes for a list of markets
-    async fn get_quotes(&self, market_ids: &[String]) -> Result<Vec<Quote>>;
+    /// Get top-of-book quotes from each outcome token's order book
+    ///
+    /// Outcomes are grouped by market; markets with no known token ids are
+    /// skipped.
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>>;
 
     /// Get rule text and extract risk flags for a market
     async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot>;
 
-    /// Get market outcomes (for binary or multi-outcome markets)
+    /// Get the venue's current lifecycle status for a market
+    ///
+    /// Returns `ClientError::MarketNotFound` if the venue no longer lists it.
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus>;
+
+    /// Get market outcomes with their CLOB token ids (for binary or
+    /// multi-outcome markets)
     async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>>;
+
+    /// Get up to `limit` of the market's most recent fills
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>>;
 }
 
+/// Venue name used to namespace Polymarket market ids
+pub const VENUE: &str = "polymarket";
+
+/// Endpoint class of gamma `/markets` discovery pages
+pub const ENDPOINT_MARKETS: &str = "gamma_markets";
+
+/// Endpoint class of gamma `/markets/{id}` detail bodies
+pub const ENDPOINT_MARKET: &str = "gamma_market";
+
+/// Endpoint class of CLOB `/book` order books
+pub const ENDPOINT_BOOK: &str = "clob_book";
+
+/// Endpoint class of data API `/trades` fill lists
+pub const ENDPOINT_TRADES: &str = "data_trades";
+
 /// Polymarket client implementation
 pub struct PolymarketClient {
     http: Client,
     base_url: String,
+    clob_url: String,
+    data_url: String,
     retry_config: RetryConfig,
+    payload_tx: Option<mpsc::Sender<RawPayload>>,
+    schema: Option<Arc<SchemaTracker>>,
+    clock: SharedClock,
+    retries: Option<IntCounterVec>,
+}
+
+/// What a fetched body is recorded as when payloads are captured
+struct Capture<'a> {
+    endpoint: &'static str,
+    market_id: Option<&'a str>,
+    outcome: Option<&'a str>,
+    fetched_at: DateTime<Utc>,
 }
 
 impl PolymarketClient {
-    /// Create a new Polymarket client
+    /// Create a new Polymarket client against the public endpoints
     pub fn new(retry_config: RetryConfig) -> Self {
+        Self::with_venue(&VenueConfig::default(), retry_config)
+    }
+
+    /// Create a new Polymarket client against configured endpoints
+    pub fn with_venue(venue: &VenueConfig, retry_config: RetryConfig) -> Self {
         Self {
             http: Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()
                 .expect("Failed to build HTTP client"),
-            base_url: "https://gamma-api.polymarket.com".to_string(),
+            base_url: venue.gamma_base_url.trim_end_matches('/').to_string(),
+            clob_url: venue.clob_base_url.trim_end_matches('/').to_string(),
+            data_url: venue.data_base_url.trim_end_matches('/').to_string(),
             retry_config,
+            payload_tx: None,
+            schema: None,
+            clock: SystemClock::shared(),
+            retries: None,
+        }
+    }
+
+    /// Stamp quotes, rules and captured payloads with `clock` instead of
+    /// the wall clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Count retried requests in `counter`, labelled by endpoint class
+    pub fn with_retry_counter(mut self, counter: IntCounterVec) -> Self {
+        self.retries = Some(counter);
+        self
+    }
+
+    /// Check every successfully fetched response body for schema drift
+    pub fn with_schema_tracking(mut self, tracker: Arc<SchemaTracker>) -> Self {
+        self.schema = Some(tracker);
+        self
+    }
+
+    /// Send every successfully fetched response body to `tx` for archiving
+    ///
+    /// Capture never blocks requests: payloads are dropped while the
+    /// channel is full.
+    pub fn with_payload_capture(mut self, tx: mpsc::Sender<RawPayload>) -> Self {
+        self.payload_tx = Some(tx);
+        self
+    }
+
+    /// GET a URL with retries and return its body, capturing it if enabled
+    async fn fetch(
+        &self,
+        url: &str,
+        capture: Capture<'_>,
+    ) -> std::result::Result<Vec<u8>, reqwest::Error> {
+        let mut attempts = 0_u64;
+        let response = retry_with_backoff(&self.retry_config, || {
+            attempts += 1;
+            async { self.http.get(url).send().await?.error_for_status() }
+        })
+        .await;
+
+        if let Some(retries) = self.retries.as_ref().filter(|_| attempts > 1) {
+            retries
+                .with_label_values(&[capture.endpoint])
+                .inc_by(attempts - 1);
+        }
+        let response = response?;
+
+        let http_status = response.status().as_u16();
+        let body = response.bytes().await?.to_vec();
+
+        if let Some(tracker) = &self.schema {
+            tracker.observe(capture.endpoint, &body);
+        }
+
+        if let Some(tx) = &self.payload_tx {
+            let payload = RawPayload {
+                venue: VENUE.to_string(),
+                market_id: capture.market_id.map(str::to_string),
+                endpoint: capture.endpoint.to_string(),
+                request_url: url.to_string(),
+                outcome: capture.outcome.map(str::to_string),
+                fetched_at: capture.fetched_at,
+                http_status,
+                body: body.clone(),
+            };
+
+            match tx.try_send(payload) {
+                Ok(()) | Err(TrySendError::Closed(_)) => {}
+                Err(TrySendError::Full(_)) => {
+                    tracing::debug!(url, "Payload archive queue full, dropping payload");
+                }
+            }
+        }
+
+        Ok(body)
+    }
+
+    /// Capture for a gamma market detail request
+    fn market_capture<'a>(&self, market_id: &'a str) -> Capture<'a> {
+        Capture {
+            endpoint: ENDPOINT_MARKET,
+            market_id: Some(market_id),
+            outcome: None,
+            fetched_at: self.clock.now(),
         }
     }
 
@@ -126,99 +286,50 @@ impl VenueClient for PolymarketClient {
             self.base_url, limit, offset
         );
 
-        let response = retry_with_backoff(&self.retry_config, || async {
-            self.http.get(&url).send().await?.error_for_status()
-        })
-        .await?;
-
-        let markets_json: Vec<PolymarketMarketResponse> = response.json().await?;
-
-        Ok(markets_json
-            .into_iter()
-            .map(|m| {
-                let url = format!("https://polymarket.com/event/{}", m.slug);
-                Market {
-                    market_id: m.condition_id,
-                    venue: "polymarket".to_string(),
-                    title: m.question,
-                    slug: Some(m.slug),
-                    category: m.category,
-                    status: if m.closed {
-                        MarketStatus::Closed
-                    } else {
-                        MarketStatus::Active
-                    },
-                    open_time: m.start_date,
-                    close_time: m.end_date,
-                    resolved_time: None,
-                    url: Some(url),
-                }
-            })
-            .collect())
+        let body = self
+            .fetch(
+                &url,
+                Capture {
+                    endpoint: ENDPOINT_MARKETS,
+                    market_id: None,
+                    outcome: None,
+                    fetched_at: self.clock.now(),
+                },
+            )
+            .await?;
+
+        parse_markets_page(&body)
     }
 
-    async fn get_quotes(&self, market_ids: &[String]) -> Result<Vec<Quote>> {
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
         let mut quotes = Vec::new();
-        let now = Utc::now();
+        let now = self.clock.now();
 
-        for market_id in market_ids {
-            let url = format!("{}/markets/{}/book", self.base_url, market_id);
+        for (market_id, tokens) in group_outcome_tokens(outcomes) {
+            if tokens.yes.is_none() && tokens.no.is_none() {
+                tracing::debug!(market_id, "Skipping quote for market without token ids");
+                continue;
+            }
 
-            let response = retry_with_backoff(&self.retry_config, || async {
-                self.http.get(&url).send().await?.error_for_status()
-            })
-            .await;
-
-            match response {
-                Ok(resp) => {
-                    let book: PolymarketBookResponse = resp.json().await?;
-
-                    // For binary markets, extract YES/NO quotes
-                    let yes_bid = book.bids.first().map(|b| b.price);
-                    let yes_ask = book.asks.first().map(|a| a.price);
-                    let no_bid = yes_ask.map(|p| 1.0 - p);
-                    let no_ask = yes_bid.map(|p| 1.0 - p);
-
-                    let spread_yes = match (yes_bid, yes_ask) {
-                        (Some(bid), Some(ask)) => Some(ask - bid),
-                        _ => None,
-                    };
-
-                    let spread_no = match (no_bid, no_ask) {
-                        (Some(bid), Some(ask)) => Some(ask - bid),
-                        _ => None,
-                    };
-
-                    let mid_yes = match (yes_bid, yes_ask) {
-                        (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
-                        _ => None,
-                    };
-
-                    let mid_no = match (no_bid, no_ask) {
-                        (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
-                        _ => None,
-                    };
-
-                    quotes.push(Quote {
-                        market_id: market_id.clone(),
-                        as_of: now,
-                        yes_bid,
-                        yes_ask,
-                        no_bid,
-                        no_ask,
-                        spread_yes,
-                        spread_no,
-                        mid_yes,
-                        mid_no,
-                        quote_source: "polymarket".to_string(),
-                    });
+            let yes_book = match tokens.yes {
+                Some(token_id) => {
+                    self.fetch_top_of_book(&market_id, token_id, "YES", now)
+                        .await
+                }
+                None => None,
+            };
+            let no_book = match tokens.no {
+                Some(token_id) => {
+                    self.fetch_top_of_book(&market_id, token_id, "NO", now)
+                        .await
                 }
-                Err(e) => {
-                    tracing::warn!(
-                        market_id,
-                        error = %e,
-                        "Failed to fetch quote for market"
-                    );
+                None => None,
+            };
+
+            match build_quote(&market_id, now, yes_book, no_book) {
+                Some(quote) => quotes.push(quote),
+                None => {
+                    tracing::warn!(market_id, "No order book available for either outcome");
                 }
             }
         }
@@ -227,57 +338,463 @@ impl VenueClient for PolymarketClient {
     }
 
     async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
-        let url = format!("{}/markets/{}", self.base_url, market_id);
+        let url = format!("{}/markets/{}", self.base_url, venue_market_id(market_id)?);
+        let capture = self.market_capture(market_id);
+        let as_of = capture.fetched_at;
 
-        let response = retry_with_backoff(&self.retry_config, || async {
-            self.http.get(&url).send().await?.error_for_status()
-        })
-        .await?;
+        let body = self.fetch(&url, capture).await?;
 
-        let market: PolymarketMarketDetailResponse = response.json().await?;
+        parse_rule(market_id, &body, as_of)
+    }
 
-        let rule_text = market
-            .description
-            .unwrap_or_else(|| "No rules provided".to_string());
-        let rule_hash = Self::compute_rule_hash(&rule_text);
-        let risk_flags = Self::extract_risk_flags(&rule_text);
-        let definition_risk_score = Self::calculate_risk_score(&risk_flags);
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        let url = format!("{}/markets/{}", self.base_url, venue_market_id(market_id)?);
 
-        Ok(RuleSnapshot {
-            market_id: market_id.to_string(),
-            as_of: Utc::now(),
-            rule_text,
-            rule_hash,
-            settlement_source: market.resolution_source,
-            settlement_window: None,
-            definition_risk_score,
-            risk_flags,
-        })
+        let body = self
+            .fetch(&url, self.market_capture(market_id))
+            .await
+            .map_err(|e| match e.status() {
+                Some(reqwest::StatusCode::NOT_FOUND) => {
+                    ClientError::MarketNotFound(market_id.to_string())
+                }
+                _ => ClientError::Http(e),
+            })?;
+
+        let market: PolymarketMarketDetailResponse = serde_json::from_slice(&body)?;
+
+        Ok(map_venue_status(&market))
     }
 
     async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
-        let url = format!("{}/markets/{}", self.base_url, market_id);
+        let url = format!("{}/markets/{}", self.base_url, venue_market_id(market_id)?);
+
+        let body = self.fetch(&url, self.market_capture(market_id)).await?;
+
+        let market: PolymarketMarketDetailResponse = serde_json::from_slice(&body)?;
+
+        let outcomes = parse_outcomes(
+            market_id,
+            market.outcomes.as_ref(),
+            market.clob_token_ids.as_ref(),
+        )?;
+
+        // Markets without an outcome list are still recorded as binary, just
+        // without token ids
+        if outcomes.is_empty() {
+            return Ok(["YES", "NO"]
+                .into_iter()
+                .map(|outcome| Outcome {
+                    market_id: market_id.to_string(),
+                    outcome: outcome.to_string(),
+                    token_id: None,
+                })
+                .collect());
+        }
+
+        Ok(outcomes)
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        let url = format!(
+            "{}/trades?market={}&limit={}",
+            self.data_url,
+            venue_market_id(market_id)?,
+            limit
+        );
+        let capture = Capture {
+            endpoint: ENDPOINT_TRADES,
+            market_id: Some(market_id),
+            outcome: None,
+            fetched_at: self.clock.now(),
+        };
 
-        let response = retry_with_backoff(&self.retry_config, || async {
-            self.http.get(&url).send().await?.error_for_status()
+        let body = self.fetch(&url, capture).await?;
+
+        parse_trades(market_id, &body)
+    }
+}
+
+/// Extract the Polymarket condition id from a market key
+///
+/// Bare ids (pre-namespacing) are passed through unchanged.
+fn venue_market_id(market_id: &str) -> Result<String> {
+    match market_id.parse::<MarketKey>() {
+        Ok(key) if key.venue == VENUE => Ok(key.venue_market_id),
+        Ok(_) => Err(ClientError::ForeignMarket(market_id.to_string())),
+        Err(_) => Ok(market_id.to_string()),
+    }
+}
+
+/// Parse a gamma `/markets` discovery page
+pub fn parse_markets_page(body: &[u8]) -> Result<Vec<Market>> {
+    let markets_json: Vec<PolymarketMarketResponse> = serde_json::from_slice(body)?;
+
+    Ok(markets_json
+        .into_iter()
+        .map(|m| {
+            let url = format!("https://polymarket.com/event/{}", m.slug);
+            let metadata = m.metadata();
+            let market_id = MarketKey::new(VENUE, &m.condition_id).to_string();
+            // Book polling needs token ids before the market's first rule fetch
+            let outcomes = parse_outcomes(
+                &market_id,
+                m.outcomes.as_ref(),
+                m.clob_token_ids.as_ref(),
+            )
+            .unwrap_or_else(|e| {
+                tracing::warn!(market_id = %market_id, error = %e, "Ignoring discovered outcomes");
+                Vec::new()
+            });
+            Market {
+                market_id,
+                venue: VENUE.to_string(),
+                venue_market_id: m.condition_id,
+                title: m.question,
+                slug: Some(m.slug),
+                category: m.category,
+                status: if m.closed {
+                    MarketStatus::Closed
+                } else {
+                    MarketStatus::Active
+                },
+                open_time: m.start_date,
+                close_time: m.end_date,
+                resolved_time: None,
+                url: Some(url),
+                metadata,
+                outcomes,
+            }
         })
-        .await?;
+        .collect())
+}
 
-        let _market: PolymarketMarketDetailResponse = response.json().await?;
+/// Parse a gamma market detail body into a rule snapshot taken at `as_of`
+pub fn parse_rule(market_id: &str, body: &[u8], as_of: DateTime<Utc>) -> Result<RuleSnapshot> {
+    let market: PolymarketMarketDetailResponse = serde_json::from_slice(body)?;
 
-        // For binary markets, create YES/NO outcomes
-        Ok(vec![
-            Outcome {
-                market_id: market_id.to_string(),
-                outcome: "YES".to_string(),
-                token_id: None,
-            },
-            Outcome {
+    let rule_text = market
+        .description
+        .unwrap_or_else(|| "No rules provided".to_string());
+    let rule_hash = PolymarketClient::compute_rule_hash(&rule_text);
+    let risk_flags = PolymarketClient::extract_risk_flags(&rule_text);
+    let definition_risk_score = PolymarketClient::calculate_risk_score(&risk_flags);
+
+    Ok(RuleSnapshot {
+        market_id: market_id.to_string(),
+        as_of,
+        rule_text,
+        rule_hash,
+        settlement_source: market.resolution_source,
+        settlement_window: None,
+        definition_risk_score,
+        risk_flags,
+    })
+}
+
+/// Parse a data API `/trades` body into fills for a market
+pub fn parse_trades(market_id: &str, body: &[u8]) -> Result<Vec<Trade>> {
+    let fills: Vec<PolymarketTradeResponse> = serde_json::from_slice(body)?;
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    fills
+        .into_iter()
+        .map(|fill| {
+            let side = fill.side.parse::<TradeSide>().map_err(|e| {
+                ClientError::InvalidResponse(format!("Trade for {}: {}", market_id, e))
+            })?;
+            let traded_at = DateTime::from_timestamp(fill.timestamp, 0).ok_or_else(|| {
+                ClientError::InvalidResponse(format!(
+                    "Trade for {} has invalid timestamp {}",
+                    market_id, fill.timestamp
+                ))
+            })?;
+
+            // A transaction may fill several orders; the asset, side, price
+            // and size tell its fills apart, and identical fills are
+            // numbered in the order the response lists them
+            let key = format!(
+                "{}:{}:{}:{}:{}",
+                fill.transaction_hash, fill.asset, side, fill.price, fill.size
+            );
+            let repeat = seen.entry(key.clone()).or_default();
+            let trade_id = match *repeat {
+                0 => key,
+                n => format!("{}:{}", key, n),
+            };
+            *repeat += 1;
+
+            Ok(Trade {
                 market_id: market_id.to_string(),
-                outcome: "NO".to_string(),
-                token_id: None,
-            },
-        ])
+                trade_id,
+                outcome: fill.outcome.to_uppercase(),
+                side,
+                price: fill.price,
+                size: fill.size,
+                traded_at,
+            })
+        })
+        .collect()
+}
+
+/// Parse a CLOB order book body into its top of book
+pub(crate) fn parse_book(body: &[u8]) -> Result<TopOfBook> {
+    let book: PolymarketBookResponse = serde_json::from_slice(body)?;
+    Ok(TopOfBook::from_book(&book))
+}
+
+/// Map gamma lifecycle flags onto a market status
+fn map_venue_status(market: &PolymarketMarketDetailResponse) -> MarketStatus {
+    let resolved = market
+        .uma_resolution_status
+        .as_deref()
+        .is_some_and(|s| s.eq_ignore_ascii_case("resolved"));
+
+    if resolved {
+        MarketStatus::Resolved
+    } else if market.closed.unwrap_or(false) || market.archived.unwrap_or(false) {
+        MarketStatus::Closed
+    } else if market.active == Some(false) || market.accepting_orders == Some(false) {
+        MarketStatus::Halted
+    } else {
+        MarketStatus::Active
+    }
+}
+
+/// CLOB token ids for the two sides of a binary market
+#[derive(Debug, Default)]
+struct BinaryTokens<'a> {
+    yes: Option<&'a str>,
+    no: Option<&'a str>,
+}
+
+/// Best bid and ask from a single outcome token's order book
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TopOfBook {
+    bid: Option<f64>,
+    ask: Option<f64>,
+}
+
+impl TopOfBook {
+    /// Pick best levels regardless of the order the venue sorts them in
+    fn from_book(book: &PolymarketBookResponse) -> Self {
+        Self {
+            bid: book.bids.iter().map(|l| l.price).reduce(f64::max),
+            ask: book.asks.iter().map(|l| l.price).reduce(f64::min),
+        }
+    }
+
+    /// Book implied for the opposite outcome (`p -> 1 - p`)
+    fn complement(&self) -> Self {
+        Self {
+            bid: self.ask.map(|p| 1.0 - p),
+            ask: self.bid.map(|p| 1.0 - p),
+        }
+    }
+}
+
+impl PolymarketClient {
+    /// Fetch top-of-book for a single CLOB token, logging failures
+    async fn fetch_top_of_book(
+        &self,
+        market_id: &str,
+        token_id: &str,
+        outcome: &str,
+        fetched_at: DateTime<Utc>,
+    ) -> Option<TopOfBook> {
+        let url = format!("{}/book?token_id={}", self.clob_url, token_id);
+        let capture = Capture {
+            endpoint: ENDPOINT_BOOK,
+            market_id: Some(market_id),
+            outcome: Some(outcome),
+            fetched_at,
+        };
+
+        let book = match self.fetch(&url, capture).await {
+            Ok(body) => parse_book(&body),
+            Err(e) => Err(e.into()),
+        };
+
+        match book {
+            Ok(book) => Some(book),
+            Err(e) => {
+                tracing::warn!(
+                    market_id,
+                    token_id,
+                    error = %e,
+                    "Failed to fetch order book for token"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Group stored outcomes into YES/NO token pairs per market, preserving
+/// market order
+fn group_outcome_tokens(outcomes: &[Outcome]) -> Vec<(String, BinaryTokens<'_>)> {
+    let mut grouped: Vec<(String, BinaryTokens<'_>)> = Vec::new();
+
+    for outcome in outcomes {
+        let idx = match grouped.iter().position(|(id, _)| *id == outcome.market_id) {
+            Some(idx) => idx,
+            None => {
+                grouped.push((outcome.market_id.clone(), BinaryTokens::default()));
+                grouped.len() - 1
+            }
+        };
+
+        let token_id = outcome.token_id.as_deref();
+        match outcome.outcome.to_uppercase().as_str() {
+            "YES" => grouped[idx].1.yes = token_id,
+            "NO" => grouped[idx].1.no = token_id,
+            _ => {}
+        }
+    }
+
+    grouped
+}
+
+/// Build a quote from the YES and NO books, deriving a side from the
+/// complement of the other only when its own book is unavailable
+pub(crate) fn build_quote(
+    market_id: &str,
+    as_of: DateTime<Utc>,
+    yes_book: Option<TopOfBook>,
+    no_book: Option<TopOfBook>,
+) -> Option<Quote> {
+    let (yes, yes_source, no, no_source) = match (yes_book, no_book) {
+        (Some(yes), Some(no)) => (yes, PriceSource::Observed, no, PriceSource::Observed),
+        (Some(yes), None) => (
+            yes,
+            PriceSource::Observed,
+            yes.complement(),
+            PriceSource::Derived,
+        ),
+        (None, Some(no)) => (
+            no.complement(),
+            PriceSource::Derived,
+            no,
+            PriceSource::Observed,
+        ),
+        (None, None) => return None,
+    };
+
+    let spread = |b: &TopOfBook| match (b.bid, b.ask) {
+        (Some(bid), Some(ask)) => Some(ask - bid),
+        _ => None,
+    };
+    let mid = |b: &TopOfBook| match (b.bid, b.ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+        _ => None,
+    };
+
+    Some(Quote {
+        market_id: market_id.to_string(),
+        as_of,
+        yes_bid: yes.bid,
+        yes_ask: yes.ask,
+        no_bid: no.bid,
+        no_ask: no.ask,
+        spread_yes: spread(&yes),
+        spread_no: spread(&no),
+        mid_yes: mid(&yes),
+        mid_no: mid(&no),
+        yes_source,
+        no_source,
+        quote_source: VENUE.to_string(),
+    })
+}
+
+/// Pair gamma's outcome names with their CLOB token ids
+///
+/// Gamma lists outcomes as "Yes"/"No" in the same order as `clobTokenIds`.
+/// Returns no outcomes when the market has no outcome list.
+fn parse_outcomes(
+    market_id: &str,
+    outcomes: Option<&serde_json::Value>,
+    clob_token_ids: Option<&serde_json::Value>,
+) -> Result<Vec<Outcome>> {
+    let names = parse_string_list(outcomes)?;
+    let token_ids = parse_string_list(clob_token_ids)?;
+
+    if !token_ids.is_empty() && token_ids.len() != names.len() {
+        return Err(ClientError::InvalidResponse(format!(
+            "Market {} has {} outcomes but {} CLOB token ids",
+            market_id,
+            names.len(),
+            token_ids.len()
+        )));
+    }
+
+    Ok(names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| Outcome {
+            market_id: market_id.to_string(),
+            outcome: name.to_uppercase(),
+            token_id: token_ids.get(i).cloned(),
+        })
+        .collect())
+}
+
+/// Parse a gamma list field, which is usually a JSON-encoded string
+/// (`"[\"Yes\", \"No\"]"`) but may also arrive as a plain array
+fn parse_string_list(value: Option<&serde_json::Value>) -> Result<Vec<String>> {
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(serde_json::Value::String(s)) if s.is_empty() => Ok(Vec::new()),
+        Some(serde_json::Value::String(s)) => Ok(serde_json::from_str(s)?),
+        Some(v) => Ok(serde_json::from_value(v.clone())?),
+    }
+}
+
+/// Deserialize an optional number that gamma may encode as a decimal string
+fn deserialize_opt_decimal<'de, D>(deserializer: D) -> std::result::Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Number(n)) => Ok(n.as_f64()),
+        Some(serde_json::Value::String(s)) if s.is_empty() => Ok(None),
+        Some(serde_json::Value::String(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "expected decimal, got {}",
+            other
+        ))),
+    }
+}
+
+/// Deserialize an id that may be encoded as a string or a number
+fn deserialize_id<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected id, got {}",
+            other
+        ))),
+    }
+}
+
+/// Deserialize a price or size that the CLOB encodes as a decimal string
+fn deserialize_decimal<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Decimal {
+        Number(f64),
+        Text(String),
+    }
+
+    match Decimal::deserialize(deserializer)? {
+        Decimal::Number(n) => Ok(n),
+        Decimal::Text(s) => s.parse().map_err(serde::de::Error::custom),
     }
 }
 
@@ -295,6 +812,76 @@ struct PolymarketMarketResponse {
     start_date: Option<DateTime<Utc>>,
     #[serde(rename = "endDate")]
     end_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    events: Vec<PolymarketEvent>,
+    #[serde(default)]
+    tags: Vec<PolymarketTag>,
+    #[serde(
+        rename = "volume24hr",
+        default,
+        deserialize_with = "deserialize_opt_decimal"
+    )]
+    volume_24h: Option<f64>,
+    #[serde(
+        rename = "volume",
+        default,
+        deserialize_with = "deserialize_opt_decimal"
+    )]
+    volume_total: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_opt_decimal")]
+    liquidity: Option<f64>,
+    #[serde(rename = "negRisk", default)]
+    neg_risk: Option<bool>,
+    image: Option<String>,
+    icon: Option<String>,
+    #[serde(default)]
+    outcomes: Option<serde_json::Value>,
+    #[serde(rename = "clobTokenIds", default)]
+    clob_token_ids: Option<serde_json::Value>,
+}
+
+impl PolymarketMarketResponse {
+    /// Collect event, tag, activity and display metadata
+    fn metadata(&self) -> MarketMetadata {
+        let event = self.events.first();
+
+        // Tags usually live on the parent event; merge both, keeping order
+        let mut tags: Vec<String> = Vec::new();
+        let event_tags = event.map(|e| e.tags.as_slice()).unwrap_or_default();
+        for tag in self.tags.iter().chain(event_tags) {
+            if let Some(label) = tag.label.as_ref().filter(|l| !l.is_empty()) {
+                if !tags.contains(label) {
+                    tags.push(label.clone());
+                }
+            }
+        }
+
+        MarketMetadata {
+            event_id: event.map(|e| e.id.clone()),
+            event_title: event.and_then(|e| e.title.clone()),
+            tags,
+            volume_24h: self.volume_24h,
+            volume_total: self.volume_total,
+            liquidity: self.liquidity,
+            neg_risk: self.neg_risk.unwrap_or(false),
+            image_url: self.image.clone().filter(|s| !s.is_empty()),
+            icon_url: self.icon.clone().filter(|s| !s.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PolymarketEvent {
+    #[serde(deserialize_with = "deserialize_id")]
+    id: String,
+    title: Option<String>,
+    #[serde(default)]
+    tags: Vec<PolymarketTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolymarketTag {
+    label: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -302,6 +889,16 @@ struct PolymarketMarketDetailResponse {
     description: Option<String>,
     #[serde(rename = "resolutionSource")]
     resolution_source: Option<String>,
+    outcomes: Option<serde_json::Value>,
+    #[serde(rename = "clobTokenIds")]
+    clob_token_ids: Option<serde_json::Value>,
+    active: Option<bool>,
+    closed: Option<bool>,
+    archived: Option<bool>,
+    #[serde(rename = "acceptingOrders")]
+    accepting_orders: Option<bool>,
+    #[serde(rename = "umaResolutionStatus")]
+    uma_resolution_status: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -310,9 +907,272 @@ struct PolymarketBookResponse {
     asks: Vec<PolymarketOrderLevel>,
 }
 
+#[derive(Debug, Deserialize)]
+struct PolymarketTradeResponse {
+    side: String,
+    asset: String,
+    #[serde(deserialize_with = "deserialize_decimal")]
+    size: f64,
+    #[serde(deserialize_with = "deserialize_decimal")]
+    price: f64,
+    timestamp: i64,
+    outcome: String,
+    #[serde(rename = "transactionHash")]
+    transaction_hash: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct PolymarketOrderLevel {
+    #[serde(deserialize_with = "deserialize_decimal")]
     price: f64,
     #[allow(dead_code)]
+    #[serde(deserialize_with = "deserialize_decimal")]
     size: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(market_id: &str, outcome: &str, token_id: Option<&str>) -> Outcome {
+        Outcome {
+            market_id: market_id.to_string(),
+            outcome: outcome.to_string(),
+            token_id: token_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_build_quote_uses_observed_no_book() {
+        let yes = TopOfBook {
+            bid: Some(0.03),
+            ask: Some(0.05),
+        };
+        let no = TopOfBook {
+            bid: Some(0.94),
+            ask: Some(0.96),
+        };
+
+        let quote = build_quote("m1", Utc::now(), Some(yes), Some(no)).unwrap();
+        assert_eq!(quote.no_bid, Some(0.94));
+        assert_eq!(quote.no_ask, Some(0.96));
+        assert_eq!(quote.yes_source, PriceSource::Observed);
+        assert_eq!(quote.no_source, PriceSource::Observed);
+    }
+
+    #[test]
+    fn test_build_quote_derives_missing_side() {
+        let yes = TopOfBook {
+            bid: Some(0.25),
+            ask: Some(0.5),
+        };
+
+        let quote = build_quote("m1", Utc::now(), Some(yes), None).unwrap();
+        assert_eq!(quote.no_bid, Some(0.5));
+        assert_eq!(quote.no_ask, Some(0.75));
+        assert_eq!(quote.no_source, PriceSource::Derived);
+
+        assert!(build_quote("m1", Utc::now(), None, None).is_none());
+    }
+
+    #[test]
+    fn test_group_outcome_tokens() {
+        let outcomes = vec![
+            outcome("m1", "YES", Some("1")),
+            outcome("m2", "NO", None),
+            outcome("m1", "NO", Some("2")),
+        ];
+
+        let grouped = group_outcome_tokens(&outcomes);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].0, "m1");
+        assert_eq!(grouped[0].1.yes, Some("1"));
+        assert_eq!(grouped[0].1.no, Some("2"));
+        assert_eq!(grouped[1].1.no, None);
+    }
+
+    #[test]
+    fn test_parse_book_with_string_prices() {
+        let book: PolymarketBookResponse = serde_json::from_str(
+            r#"{"bids":[{"price":"0.90","size":"10"},{"price":"0.92","size":"5"}],
+                "asks":[{"price":"0.97","size":"3"},{"price":"0.95","size":"1"}]}"#,
+        )
+        .unwrap();
+
+        let top = TopOfBook::from_book(&book);
+        assert_eq!(top.bid, Some(0.92));
+        assert_eq!(top.ask, Some(0.95));
+    }
+
+    #[test]
+    fn test_venue_market_id() {
+        assert_eq!(venue_market_id("polymarket:0xabc").unwrap(), "0xabc");
+        assert_eq!(venue_market_id("0xabc").unwrap(), "0xabc");
+        assert!(matches!(
+            venue_market_id("kalshi:ABC-1"),
+            Err(ClientError::ForeignMarket(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_venue_status() {
+        let detail =
+            |json: &str| -> PolymarketMarketDetailResponse { serde_json::from_str(json).unwrap() };
+
+        assert_eq!(
+            map_venue_status(&detail(r#"{"active":true,"closed":false}"#)),
+            MarketStatus::Active
+        );
+        assert_eq!(
+            map_venue_status(&detail(r#"{"active":true,"closed":true}"#)),
+            MarketStatus::Closed
+        );
+        assert_eq!(
+            map_venue_status(&detail(r#"{"active":true,"acceptingOrders":false}"#)),
+            MarketStatus::Halted
+        );
+        assert_eq!(
+            map_venue_status(&detail(
+                r#"{"closed":true,"umaResolutionStatus":"resolved"}"#
+            )),
+            MarketStatus::Resolved
+        );
+    }
+
+    #[test]
+    fn test_market_metadata() {
+        let market: PolymarketMarketResponse = serde_json::from_str(
+            r#"{
+                "conditionId": "0xabc",
+                "question": "Will it rain?",
+                "slug": "will-it-rain",
+                "closed": false,
+                "volume": "12345.67",
+                "volume24hr": 890.5,
+                "liquidity": "4000",
+                "negRisk": true,
+                "icon": "",
+                "events": [{"id": 42, "title": "Weather", "tags": [{"label": "Climate"}]}],
+                "tags": [{"label": "Weather"}, {"label": "Climate"}]
+            }"#,
+        )
+        .unwrap();
+
+        let metadata = market.metadata();
+        assert_eq!(metadata.event_id.as_deref(), Some("42"));
+        assert_eq!(metadata.event_title.as_deref(), Some("Weather"));
+        assert_eq!(metadata.tags, vec!["Weather", "Climate"]);
+        assert_eq!(metadata.volume_total, Some(12345.67));
+        assert_eq!(metadata.volume_24h, Some(890.5));
+        assert_eq!(metadata.liquidity, Some(4000.0));
+        assert!(metadata.neg_risk);
+        assert_eq!(metadata.icon_url, None);
+    }
+
+    #[test]
+    fn test_parse_markets_page() {
+        let markets = parse_markets_page(
+            br#"[{"conditionId":"0xabc","question":"Will it rain?","slug":"will-it-rain",
+                  "closed":true,"endDate":"2026-03-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(markets.len(), 1);
+        assert_eq!(markets[0].market_id, "polymarket:0xabc");
+        assert_eq!(markets[0].status, MarketStatus::Closed);
+        assert!(markets[0].close_time.is_some());
+        assert!(markets[0].outcomes.is_empty());
+
+        assert!(matches!(
+            parse_markets_page(b"[{\"conditionId\":"),
+            Err(ClientError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_markets_page_outcomes() {
+        let markets = parse_markets_page(
+            br#"[{"conditionId":"0xabc","question":"Will it rain?","slug":"will-it-rain",
+                  "closed":false,"outcomes":"[\"Yes\", \"No\"]",
+                  "clobTokenIds":"[\"1001\", \"1002\"]"},
+                 {"conditionId":"0xdef","question":"Will it snow?","slug":"will-it-snow",
+                  "closed":false,"outcomes":"[\"Yes\", \"No\"]","clobTokenIds":"[\"2001\"]"}]"#,
+        )
+        .unwrap();
+
+        let outcomes = &markets[0].outcomes;
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].market_id, "polymarket:0xabc");
+        assert_eq!(outcomes[1].outcome, "NO");
+        assert_eq!(outcomes[1].token_id.as_deref(), Some("1002"));
+
+        // A mismatched list drops the outcomes, not the market
+        assert_eq!(markets.len(), 2);
+        assert!(markets[1].outcomes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rule_keeps_fetch_time() {
+        let as_of = Utc::now() - chrono::Duration::days(1);
+        let rule = parse_rule(
+            "polymarket:0xabc",
+            br#"{"description":"Resolves at the committee's discretion.","resolutionSource":"https://example.com"}"#,
+            as_of,
+        )
+        .unwrap();
+
+        assert_eq!(rule.as_of, as_of);
+        assert_eq!(rule.risk_flags[0].code, "SUBJECTIVE_RESOLUTION");
+        assert_eq!(
+            rule.settlement_source.as_deref(),
+            Some("https://example.com")
+        );
+    }
+
+    #[test]
+    fn test_parse_trades() {
+        let body = br#"[
+            {"proxyWallet":"0x1","side":"BUY","asset":"1002","conditionId":"0xabc","size":250,
+             "price":0.96,"timestamp":1767225600,"outcome":"No","outcomeIndex":1,
+             "transactionHash":"0xt1"},
+            {"proxyWallet":"0x2","side":"SELL","asset":"1001","conditionId":"0xabc","size":"40.5",
+             "price":"0.035","timestamp":1767225500,"outcome":"Yes","outcomeIndex":0,
+             "transactionHash":"0xt2"}
+        ]"#;
+
+        let trades = parse_trades("polymarket:0xabc", body).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].outcome, "NO");
+        assert_eq!(trades[0].side, TradeSide::Buy);
+        assert_eq!(trades[0].traded_at.timestamp(), 1767225600);
+        assert_eq!(trades[1].size, 40.5);
+        assert_ne!(trades[0].trade_id, trades[1].trade_id);
+
+        // Two identical fills in one transaction are both kept
+        let repeated = br#"[
+            {"side":"BUY","asset":"1002","size":10,"price":0.96,"timestamp":1,
+             "outcome":"No","transactionHash":"0xt3"},
+            {"side":"BUY","asset":"1002","size":10,"price":0.96,"timestamp":1,
+             "outcome":"No","transactionHash":"0xt3"}
+        ]"#;
+        let trades = parse_trades("polymarket:0xabc", repeated).unwrap();
+        assert_ne!(trades[0].trade_id, trades[1].trade_id);
+
+        let bad_side = br#"[{"side":"HOLD","asset":"1","size":1,"price":0.5,"timestamp":1,
+            "outcome":"Yes","transactionHash":"0x"}]"#;
+        assert!(parse_trades("polymarket:0xabc", bad_side).is_err());
+    }
+
+    #[test]
+    fn test_parse_string_list() {
+        let encoded = serde_json::json!("[\"Yes\", \"No\"]");
+        assert_eq!(
+            parse_string_list(Some(&encoded)).unwrap(),
+            vec!["Yes".to_string(), "No".to_string()]
+        );
+
+        let array = serde_json::json!(["123", "456"]);
+        assert_eq!(parse_string_list(Some(&array)).unwrap().len(), 2);
+        assert!(parse_string_list(None).unwrap().is_empty());
+    }
+}
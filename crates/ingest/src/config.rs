@@ -1,7 +1,15 @@
 //! Ingestion service configuration
 
+use pm_domain::QuoteLimits;
+use pm_metrics::MetricsServerConfig;
+use pm_storage::leader::LeaderConfig;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    chaos::ChaosConfig, fetch_queue::RuleQueueConfig, shutdown::ShutdownConfig,
+    supervisor::SupervisorConfig,
+};
+
 /// Configuration for ingestion cadences and resource bounds
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IngestConfig {
@@ -14,6 +22,16 @@ pub struct IngestConfig {
     /// How often to refresh rule text (seconds)
     pub rules_refresh_cadence_sec: u64,
 
+    /// How often to reconcile markets missing from discovery (seconds)
+    pub reconcile_cadence_sec: u64,
+
+    /// Re-check active markets discovery has not returned for this long
+    /// (seconds)
+    pub reconcile_unseen_after_sec: u64,
+
+    /// Maximum markets to re-check per reconciliation pass
+    pub max_markets_per_reconcile: usize,
+
     /// Maximum markets to discover per batch
     pub max_markets_per_discovery: usize,
 
@@ -23,8 +41,127 @@ pub struct IngestConfig {
     /// Maximum channel size for bounded queues
     pub max_channel_size: usize,
 
+    /// Venue endpoints
+    #[serde(default)]
+    pub venue: VenueConfig,
+
     /// Retry configuration
     pub retry: RetryConfig,
+
+    /// Venue fault injection (debug builds only)
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+
+    /// Raw venue payload archive
+    #[serde(default)]
+    pub payloads: PayloadArchiveConfig,
+
+    /// Venue response schema-drift detection
+    #[serde(default)]
+    pub schema_drift: SchemaDriftConfig,
+
+    /// Sanity limits for fetched quotes; quotes outside them are
+    /// quarantined
+    #[serde(default)]
+    pub quote_validation: QuoteLimits,
+
+    /// Trade print ingestion
+    #[serde(default)]
+    pub trades: TradesConfig,
+
+    /// Restart policy for ingest tasks
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
+
+    /// Draining of persistence channels on shutdown
+    #[serde(default)]
+    pub shutdown: ShutdownConfig,
+
+    /// Leader election between ingest replicas
+    #[serde(default)]
+    pub leader: LeaderConfig,
+
+    /// Queue of rule and outcome fetches
+    #[serde(default)]
+    pub rule_queue: RuleQueueConfig,
+
+    /// `/metrics` and `/healthz` listener
+    #[serde(default = "default_metrics_server")]
+    pub metrics: MetricsServerConfig,
+}
+
+/// Trade print ingestion, keeping a bounded window of recent fills per
+/// market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradesConfig {
+    /// Whether to poll trades
+    pub enabled: bool,
+
+    /// How often to poll trades (seconds)
+    pub cadence_sec: u64,
+
+    /// Fills requested per market per poll
+    pub fetch_limit: usize,
+
+    /// Fills kept per market
+    pub max_per_market: usize,
+
+    /// Fills older than this are pruned (hours)
+    pub retention_hours: u64,
+}
+
+/// Venue response schema-drift detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaDriftConfig {
+    /// Whether to check responses against the recorded baseline
+    pub enabled: bool,
+
+    /// How often to persist drift (seconds)
+    pub flush_cadence_sec: u64,
+
+    /// Stop persisting data from an endpoint while critical fields are
+    /// missing or retyped in its responses
+    pub pause_on_critical: bool,
+}
+
+/// Raw venue payload archive, kept for re-parsing after parser fixes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadArchiveConfig {
+    /// Whether to capture and store response bodies
+    pub enabled: bool,
+
+    /// Skip bodies larger than this, before compression (bytes)
+    pub max_payload_bytes: usize,
+
+    /// Cap on total compressed archive size (bytes)
+    pub max_total_bytes: i64,
+
+    /// Delete payloads older than this (hours)
+    pub retention_hours: u64,
+
+    /// How often to enforce the size and age bounds (seconds)
+    pub prune_cadence_sec: u64,
+
+    /// Capture queue size; payloads are dropped while it is full
+    pub channel_size: usize,
+}
+
+/// Venue API endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueConfig {
+    /// Gamma (market metadata) API base URL
+    pub gamma_base_url: String,
+
+    /// CLOB (order book) API base URL
+    pub clob_base_url: String,
+
+    /// Data (trade history) API base URL
+    #[serde(default = "default_data_base_url")]
+    pub data_base_url: String,
+}
+
+fn default_data_base_url() -> String {
+    "https://data-api.polymarket.com".to_string()
 }
 
 /// Retry configuration for HTTP requests
@@ -43,16 +180,79 @@ pub struct RetryConfig {
     pub jitter: bool,
 }
 
+fn default_metrics_server() -> MetricsServerConfig {
+    MetricsServerConfig::on_port(9101)
+}
+
 impl Default for IngestConfig {
     fn default() -> Self {
         Self {
             quotes_cadence_sec: 60,
             discovery_cadence_sec: 1800,
             rules_refresh_cadence_sec: 3600,
+            reconcile_cadence_sec: 900,
+            reconcile_unseen_after_sec: 3600,
+            max_markets_per_reconcile: 200,
             max_markets_per_discovery: 1000,
             max_quotes_per_fetch: 100,
             max_channel_size: 10000,
+            venue: VenueConfig::default(),
             retry: RetryConfig::default(),
+            chaos: ChaosConfig::default(),
+            payloads: PayloadArchiveConfig::default(),
+            schema_drift: SchemaDriftConfig::default(),
+            quote_validation: QuoteLimits::default(),
+            trades: TradesConfig::default(),
+            supervisor: SupervisorConfig::default(),
+            shutdown: ShutdownConfig::default(),
+            leader: LeaderConfig::default(),
+            rule_queue: RuleQueueConfig::default(),
+            metrics: default_metrics_server(),
+        }
+    }
+}
+
+impl Default for VenueConfig {
+    fn default() -> Self {
+        Self {
+            gamma_base_url: "https://gamma-api.polymarket.com".to_string(),
+            clob_base_url: "https://clob.polymarket.com".to_string(),
+            data_base_url: default_data_base_url(),
+        }
+    }
+}
+
+impl Default for PayloadArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_payload_bytes: 2 * 1024 * 1024,
+            max_total_bytes: 1024 * 1024 * 1024,
+            retention_hours: 72,
+            prune_cadence_sec: 600,
+            channel_size: 1000,
+        }
+    }
+}
+
+impl Default for TradesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cadence_sec: 120,
+            fetch_limit: 100,
+            max_per_market: 500,
+            retention_hours: 24,
+        }
+    }
+}
+
+impl Default for SchemaDriftConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flush_cadence_sec: 60,
+            pause_on_critical: true,
         }
     }
 }
@@ -0,0 +1,268 @@
+//! Bounded priority queue of per-market venue fetches
+//!
+//! Rule extraction drains this queue. Markets seen for the first time are
+//! pushed at [`FetchPriority::New`] as soon as they are persisted, and the
+//! periodic refresh pushes due markets at [`FetchPriority::Refresh`]. A
+//! market is queued at most once; pushing it again at a higher priority
+//! promotes it.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+};
+
+use prometheus::{IntCounter, IntGauge};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+/// Rule fetch queue settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleQueueConfig {
+    /// Maximum markets waiting for a rule and outcome fetch
+    pub capacity: usize,
+
+    /// Due markets enqueued by each periodic refresh
+    pub refresh_batch: usize,
+}
+
+impl Default for RuleQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1000,
+            refresh_batch: 100,
+        }
+    }
+}
+
+/// Urgency of a queued fetch; higher is served first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FetchPriority {
+    /// Periodic refresh of a known market
+    Refresh,
+    /// Market just discovered, with no rules yet
+    New,
+}
+
+/// Ordered so the last entry is the next to serve: highest priority, then
+/// oldest
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    priority: FetchPriority,
+    seq: Reverse<u64>,
+    market_id: String,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: BTreeSet<Entry>,
+    queued: HashMap<String, (FetchPriority, u64)>,
+    next_seq: u64,
+}
+
+impl State {
+    fn remove(&mut self, market_id: &str) {
+        if let Some((priority, seq)) = self.queued.remove(market_id) {
+            self.entries.remove(&Entry {
+                priority,
+                seq: Reverse(seq),
+                market_id: market_id.to_string(),
+            });
+        }
+    }
+}
+
+/// Bounded, deduplicating priority queue of market ids
+///
+/// When full, a push evicts the least urgent entry if it is strictly less
+/// urgent than the pushed one, and is dropped otherwise.
+#[derive(Debug)]
+pub struct FetchQueue {
+    state: Mutex<State>,
+    notify: Notify,
+    capacity: usize,
+    depth: Option<IntGauge>,
+    dropped: Option<IntCounter>,
+}
+
+impl FetchQueue {
+    /// Create an empty queue holding at most `capacity` markets
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            notify: Notify::new(),
+            capacity: capacity.max(1),
+            depth: None,
+            dropped: None,
+        }
+    }
+
+    /// Report queue depth and dropped pushes to these metrics
+    pub fn with_metrics(mut self, depth: IntGauge, dropped: IntCounter) -> Self {
+        self.depth = Some(depth);
+        self.dropped = Some(dropped);
+        self
+    }
+
+    /// Markets currently queued
+    pub fn len(&self) -> usize {
+        self.lock().queued.len()
+    }
+
+    /// Whether nothing is queued
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue a fetch for `market_id`
+    ///
+    /// Returns false if the queue is full of entries at least as urgent. A
+    /// market already queued at `priority` or higher is left in place.
+    pub fn push(&self, market_id: &str, priority: FetchPriority) -> bool {
+        let mut state = self.lock();
+
+        match state.queued.get(market_id) {
+            Some(&(queued, _)) if queued >= priority => return true,
+            Some(_) => state.remove(market_id),
+            None if state.queued.len() >= self.capacity => {
+                let lowest = state.entries.first().cloned();
+                match lowest {
+                    Some(lowest) if lowest.priority < priority => {
+                        state.remove(&lowest.market_id);
+                        self.count_dropped();
+                    }
+                    _ => {
+                        self.count_dropped();
+                        return false;
+                    }
+                }
+            }
+            None => {}
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queued.insert(market_id.to_string(), (priority, seq));
+        state.entries.insert(Entry {
+            priority,
+            seq: Reverse(seq),
+            market_id: market_id.to_string(),
+        });
+        self.set_depth(&state);
+        drop(state);
+
+        self.notify.notify_one();
+        true
+    }
+
+    /// Take the most urgent market, if any
+    pub fn try_pop(&self) -> Option<(String, FetchPriority)> {
+        let mut state = self.lock();
+        let entry = state.entries.pop_last()?;
+        state.queued.remove(&entry.market_id);
+        self.set_depth(&state);
+        Some((entry.market_id, entry.priority))
+    }
+
+    /// Wait for the most urgent market
+    ///
+    /// Cancel safe: an entry is only removed once returned.
+    pub async fn pop(&self) -> (String, FetchPriority) {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(next) = self.try_pop() {
+                return next;
+            }
+            notified.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_depth(&self, state: &State) {
+        if let Some(depth) = &self.depth {
+            depth.set(state.queued.len() as i64);
+        }
+    }
+
+    fn count_dropped(&self) {
+        if let Some(dropped) = &self.dropped {
+            dropped.inc();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_markets_are_served_before_refreshes_in_arrival_order() {
+        let queue = FetchQueue::new(10);
+        queue.push("a", FetchPriority::Refresh);
+        queue.push("b", FetchPriority::New);
+        queue.push("c", FetchPriority::Refresh);
+        queue.push("d", FetchPriority::New);
+
+        let order: Vec<_> = std::iter::from_fn(|| queue.try_pop())
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(order, ["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn test_duplicates_are_queued_once_and_promoted() {
+        let queue = FetchQueue::new(10);
+        queue.push("a", FetchPriority::Refresh);
+        queue.push("b", FetchPriority::Refresh);
+        queue.push("b", FetchPriority::New);
+        queue.push("b", FetchPriority::Refresh);
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.try_pop(), Some(("b".to_string(), FetchPriority::New)));
+        assert_eq!(
+            queue.try_pop(),
+            Some(("a".to_string(), FetchPriority::Refresh))
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_full_queue_evicts_newest_refresh_for_a_new_market() {
+        let dropped = IntCounter::new("dropped", "dropped").unwrap();
+        let depth = IntGauge::new("depth", "depth").unwrap();
+        let queue = FetchQueue::new(2).with_metrics(depth.clone(), dropped.clone());
+
+        assert!(queue.push("a", FetchPriority::Refresh));
+        assert!(queue.push("b", FetchPriority::Refresh));
+        assert!(!queue.push("c", FetchPriority::Refresh));
+        assert!(queue.push("d", FetchPriority::New));
+        assert!(queue.push("e", FetchPriority::New));
+        assert!(!queue.push("f", FetchPriority::New));
+
+        assert_eq!(dropped.get(), 4);
+        assert_eq!(depth.get(), 2);
+        let order: Vec<_> = std::iter::from_fn(|| queue.try_pop())
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(order, ["d", "e"]);
+        assert_eq!(depth.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pop_waits_for_a_push() {
+        let queue = std::sync::Arc::new(FetchQueue::new(10));
+        let waiter = tokio::spawn({
+            let queue = std::sync::Arc::clone(&queue);
+            async move { queue.pop().await }
+        });
+
+        tokio::task::yield_now().await;
+        queue.push("a", FetchPriority::New);
+        let (id, priority) = waiter.await.unwrap();
+        assert_eq!(id, "a");
+        assert_eq!(priority, FetchPriority::New);
+    }
+}
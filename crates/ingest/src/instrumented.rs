@@ -0,0 +1,148 @@
+//! Metrics decorator for venue clients
+//!
+//! `InstrumentedVenueClient` wraps any `VenueClient` and records a call
+//! count by result and a duration for every method. The orchestrator wraps
+//! its client in one, so every venue call made by ingest is measured.
+
+use std::{future::Future, sync::Arc, time::Instant};
+
+use async_trait::async_trait;
+use pm_domain::{Market, MarketStatus, Outcome, Quote, RuleSnapshot, Trade};
+
+use crate::{
+    client::{Result, VenueClient},
+    metrics::IngestMetrics,
+};
+
+/// Venue client decorator that records call metrics
+pub struct InstrumentedVenueClient<C: VenueClient> {
+    inner: Arc<C>,
+    metrics: IngestMetrics,
+}
+
+impl<C: VenueClient> InstrumentedVenueClient<C> {
+    /// Wrap a shared client, recording into `metrics`
+    pub fn new(inner: Arc<C>, metrics: IngestMetrics) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Run a call and record its duration and result
+    async fn measure<T>(&self, method: &str, call: impl Future<Output = Result<T>>) -> Result<T> {
+        let started = Instant::now();
+        let result = call.await;
+
+        self.metrics
+            .venue_request_duration_seconds
+            .with_label_values(&[method])
+            .observe(started.elapsed().as_secs_f64());
+        self.metrics
+            .venue_requests_total
+            .with_label_values(&[method, if result.is_ok() { "ok" } else { "error" }])
+            .inc();
+
+        result
+    }
+}
+
+#[async_trait]
+impl<C: VenueClient> VenueClient for InstrumentedVenueClient<C> {
+    async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>> {
+        self.measure(
+            "discover_markets",
+            self.inner.discover_markets(limit, offset),
+        )
+        .await
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        self.measure("get_quotes", self.inner.get_quotes(outcomes))
+            .await
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        self.measure("get_rules", self.inner.get_rules(market_id))
+            .await
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        self.measure("get_market_status", self.inner.get_market_status(market_id))
+            .await
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        self.measure("get_outcomes", self.inner.get_outcomes(market_id))
+            .await
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        self.measure("get_trades", self.inner.get_trades(market_id, limit))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientError;
+
+    /// Client whose rule fetches fail and everything else succeeds empty
+    struct FlakyRules;
+
+    #[async_trait]
+    impl VenueClient for FlakyRules {
+        async fn discover_markets(&self, _limit: usize, _offset: usize) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_quotes(&self, _outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+            Err(ClientError::MarketNotFound(market_id.to_string()))
+        }
+
+        async fn get_market_status(&self, _market_id: &str) -> Result<MarketStatus> {
+            Ok(MarketStatus::Active)
+        }
+
+        async fn get_outcomes(&self, _market_id: &str) -> Result<Vec<Outcome>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_trades(&self, _market_id: &str, _limit: usize) -> Result<Vec<Trade>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calls_are_counted_by_method_and_result() {
+        let metrics = IngestMetrics::default();
+        let client = InstrumentedVenueClient::new(Arc::new(FlakyRules), metrics.clone());
+
+        client.discover_markets(10, 0).await.unwrap();
+        client.discover_markets(10, 10).await.unwrap();
+        assert!(client.get_rules("polymarket:0xa1").await.is_err());
+
+        let requests = &metrics.venue_requests_total;
+        assert_eq!(
+            requests
+                .with_label_values(&["discover_markets", "ok"])
+                .get(),
+            2
+        );
+        assert_eq!(requests.with_label_values(&["get_rules", "error"]).get(), 1);
+        assert_eq!(
+            metrics
+                .venue_request_duration_seconds
+                .with_label_values(&["discover_markets"])
+                .get_sample_count(),
+            2
+        );
+    }
+}
@@ -2,11 +2,29 @@
 //!
 //! Discovers markets, polls quotes, and extracts rules from Polymarket.
 
+pub mod chaos;
 pub mod client;
 pub mod config;
+pub mod fetch_queue;
+pub mod instrumented;
+pub mod metrics;
 pub mod orchestrator;
+pub mod reparse;
+pub mod replay;
 pub mod retry;
+pub mod schema;
+pub mod shutdown;
+pub mod supervisor;
 
+pub use chaos::{ChaosConfig, ChaosVenueClient};
 pub use client::{PolymarketClient, VenueClient};
-pub use config::IngestConfig;
+pub use config::{IngestConfig, VenueConfig};
+pub use fetch_queue::{FetchPriority, FetchQueue, RuleQueueConfig};
+pub use instrumented::InstrumentedVenueClient;
+pub use metrics::IngestMetrics;
 pub use orchestrator::IngestOrchestrator;
+pub use reparse::{reparse, ReparseOptions, ReparseSummary};
+pub use replay::{RecordingVenueClient, ReplayVenueClient};
+pub use schema::SchemaTracker;
+pub use shutdown::{ShutdownConfig, ShutdownDrain};
+pub use supervisor::{Supervisor, SupervisorConfig};
@@ -1,9 +1,22 @@
 //! PM Endgame Sweep - Ingestion service
 //!
 //! Discovers markets, polls quotes, and extracts rules from Polymarket.
+//!
+//! `pm-ingest reparse [--since <rfc3339>] [--market <id>] [--dry-run]`
+//! instead rebuilds market, quote and rule rows from archived payloads.
+
+use std::sync::Arc;
 
-use pm_ingest::{IngestConfig, IngestOrchestrator, PolymarketClient};
-use sqlx::postgres::PgPoolOptions;
+use anyhow::Context;
+use pm_domain::RawPayload;
+use pm_ingest::{
+    ChaosConfig, ChaosVenueClient, IngestConfig, IngestMetrics, IngestOrchestrator,
+    PolymarketClient, ReparseOptions, SchemaTracker, VenueClient,
+};
+use pm_metrics::MetricsServer;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -19,7 +32,35 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("pm-ingest starting");
 
     // Load configuration
-    let config = IngestConfig::default();
+    let mut config = IngestConfig::default();
+    if let Ok(url) = std::env::var("POLYMARKET_GAMMA_URL") {
+        config.venue.gamma_base_url = url;
+    }
+    if let Ok(url) = std::env::var("POLYMARKET_CLOB_URL") {
+        config.venue.clob_base_url = url;
+    }
+    if let Ok(url) = std::env::var("POLYMARKET_DATA_URL") {
+        config.venue.data_base_url = url;
+    }
+    if std::env::var("INGEST_CHAOS").is_ok_and(|v| v == "1" || v == "true") {
+        config.chaos = ChaosConfig::flaky();
+    }
+    if std::env::var("INGEST_ARCHIVE_PAYLOADS").is_ok_and(|v| v == "1" || v == "true") {
+        config.payloads.enabled = true;
+    }
+    if std::env::var("LEADER_ELECTION").is_ok_and(|v| v == "0" || v == "false") {
+        config.leader.enabled = false;
+    }
+    if let Ok(port) = std::env::var("METRICS_PORT") {
+        config.metrics.port = port.parse().context("invalid METRICS_PORT")?;
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let reparse_options = match args.first().map(String::as_str) {
+        Some("reparse") => Some(parse_reparse_args(&args[1..])?),
+        Some(other) => anyhow::bail!("unknown command: {}", other),
+        None => None,
+    };
 
     // Connect to database
     let database_url = std::env::var("DATABASE_URL")
@@ -34,11 +75,71 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Connected to database");
 
-    // Create Polymarket client
-    let client = PolymarketClient::new(config.retry.clone());
+    if let Some(mut options) = reparse_options {
+        options.quote_limits = config.quote_validation.clone();
+        let summary = pm_ingest::reparse(&pool, &options).await?;
+        tracing::info!(?summary, dry_run = options.dry_run, "Re-parse complete");
+        return Ok(());
+    }
 
-    // Create orchestrator
-    let orchestrator = IngestOrchestrator::new(client, pool, config);
+    // Create Polymarket client, capturing raw payloads if archiving
+    let metrics = IngestMetrics::default();
+    let mut client = PolymarketClient::with_venue(&config.venue, config.retry.clone())
+        .with_retry_counter(metrics.venue_retries_total.clone());
+    let mut payload_rx = None;
+    if config.payloads.enabled {
+        let (tx, rx) = mpsc::channel::<RawPayload>(config.payloads.channel_size);
+        client = client.with_payload_capture(tx);
+        payload_rx = Some(rx);
+        tracing::info!("Archiving raw venue payloads");
+    }
+    let mut schema = None;
+    if config.schema_drift.enabled {
+        let tracker = Arc::new(SchemaTracker::polymarket());
+        client = client.with_schema_tracking(Arc::clone(&tracker));
+        schema = Some(tracker);
+    }
+    tracing::info!(
+        gamma = %config.venue.gamma_base_url,
+        clob = %config.venue.clob_base_url,
+        data = %config.venue.data_base_url,
+        "Using venue endpoints"
+    );
+
+    // Fault injection is only honoured by debug builds
+    if config.chaos.enabled && cfg!(debug_assertions) {
+        tracing::warn!("Venue fault injection enabled");
+        let client = ChaosVenueClient::new(client, config.chaos.clone());
+        run(client, pool, config, metrics, payload_rx, schema).await?;
+    } else {
+        if config.chaos.enabled {
+            tracing::warn!("Ignoring venue fault injection in release build");
+        }
+        run(client, pool, config, metrics, payload_rx, schema).await?;
+    }
+
+    tracing::info!("pm-ingest shutdown complete");
+    Ok(())
+}
+
+/// Run the orchestrator until a shutdown signal
+async fn run<C: VenueClient + 'static>(
+    client: C,
+    pool: PgPool,
+    config: IngestConfig,
+    metrics: IngestMetrics,
+    payload_rx: Option<mpsc::Receiver<RawPayload>>,
+    schema: Option<Arc<SchemaTracker>>,
+) -> anyhow::Result<()> {
+    let metrics_config = config.metrics.clone();
+    let mut orchestrator =
+        IngestOrchestrator::new(client, pool, config).with_metrics(metrics.clone());
+    if let Some(rx) = payload_rx {
+        orchestrator = orchestrator.with_payload_archive(rx);
+    }
+    if let Some(tracker) = schema {
+        orchestrator = orchestrator.with_schema_tracker(tracker);
+    }
 
     // Setup signal handler for graceful shutdown
     tokio::spawn({
@@ -50,8 +151,45 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    orchestrator.run().await?;
+    // Metrics stay up until persistence has drained
+    let orchestrator = Arc::new(orchestrator);
+    let metrics_shutdown = CancellationToken::new();
+    MetricsServer::new("pm-ingest", metrics.registry())
+        .with_leader({
+            let orchestrator = Arc::clone(&orchestrator);
+            move || orchestrator.is_leader()
+        })
+        .spawn(&metrics_config, metrics_shutdown.clone())
+        .await?;
 
-    tracing::info!("pm-ingest shutdown complete");
+    let result = orchestrator.run().await;
+    metrics_shutdown.cancel();
+    result?;
     Ok(())
 }
+
+/// Parse `reparse` command options
+fn parse_reparse_args(args: &[String]) -> anyhow::Result<ReparseOptions> {
+    let mut options = ReparseOptions::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--since" => {
+                let value = args.next().context("--since needs a timestamp")?;
+                options.since = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid --since timestamp: {}", value))?,
+                );
+            }
+            "--market" => {
+                options.market_id = Some(args.next().context("--market needs an id")?.clone());
+            }
+            "--dry-run" => options.dry_run = true,
+            other => anyhow::bail!("unknown reparse option: {}", other),
+        }
+    }
+
+    Ok(options)
+}
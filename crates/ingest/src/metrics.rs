@@ -0,0 +1,143 @@
+//! Prometheus metrics for the ingest service
+
+use std::sync::Arc;
+
+use pm_metrics::{LoopMetrics, DURATION_BUCKETS};
+use prometheus::{
+    histogram_opts, opts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+};
+
+/// Metrics collector for the ingest service
+#[derive(Clone)]
+pub struct IngestMetrics {
+    /// Cycle durations and results, rows upserted and markets skipped
+    pub loops: LoopMetrics,
+
+    /// Venue client calls by method and result (`ok` or `error`)
+    pub venue_requests_total: IntCounterVec,
+
+    /// Venue client call duration by method, including retries
+    pub venue_request_duration_seconds: HistogramVec,
+
+    /// HTTP retries against the venue by endpoint class
+    pub venue_retries_total: IntCounterVec,
+
+    /// Items queued in each persistence channel
+    pub channel_depth: IntGaugeVec,
+
+    /// Supervised task restarts by task name
+    pub task_restarts_total: IntCounterVec,
+
+    /// 1 while this replica holds ingest leadership
+    pub leader: IntGauge,
+
+    /// Markets waiting for a rule and outcome fetch
+    pub rule_queue_depth: IntGauge,
+
+    /// Rule fetches dropped or evicted because the queue was full
+    pub rule_queue_dropped_total: IntCounter,
+
+    registry: Arc<Registry>,
+}
+
+impl IngestMetrics {
+    /// Create new metrics collector
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let loops = LoopMetrics::new(&registry, "pm_ingest")?;
+
+        let venue_requests_total = IntCounterVec::new(
+            opts!(
+                "pm_ingest_venue_requests_total",
+                "Venue client calls by method and result"
+            ),
+            &["method", "result"],
+        )?;
+        registry.register(Box::new(venue_requests_total.clone()))?;
+
+        let venue_request_duration_seconds = HistogramVec::new(
+            histogram_opts!(
+                "pm_ingest_venue_request_duration_seconds",
+                "Duration of venue client calls, including retries",
+                DURATION_BUCKETS.to_vec()
+            ),
+            &["method"],
+        )?;
+        registry.register(Box::new(venue_request_duration_seconds.clone()))?;
+
+        let venue_retries_total = IntCounterVec::new(
+            opts!(
+                "pm_ingest_venue_retries_total",
+                "HTTP requests to the venue retried after a failure"
+            ),
+            &["endpoint"],
+        )?;
+        registry.register(Box::new(venue_retries_total.clone()))?;
+
+        let channel_depth = IntGaugeVec::new(
+            opts!(
+                "pm_ingest_channel_depth",
+                "Items queued in each persistence channel"
+            ),
+            &["channel"],
+        )?;
+        registry.register(Box::new(channel_depth.clone()))?;
+
+        let task_restarts_total = IntCounterVec::new(
+            opts!(
+                "pm_ingest_task_restarts_total",
+                "Restarts of supervised ingest tasks after they exited or panicked"
+            ),
+            &["task"],
+        )?;
+        registry.register(Box::new(task_restarts_total.clone()))?;
+
+        let leader = IntGauge::with_opts(opts!(
+            "pm_ingest_leader",
+            "Whether this replica leads ingest, or runs it alone without election"
+        ))?;
+        registry.register(Box::new(leader.clone()))?;
+
+        let rule_queue_depth = IntGauge::with_opts(opts!(
+            "pm_ingest_rule_queue_depth",
+            "Markets queued for a rule and outcome fetch"
+        ))?;
+        registry.register(Box::new(rule_queue_depth.clone()))?;
+
+        let rule_queue_dropped_total = IntCounter::with_opts(opts!(
+            "pm_ingest_rule_queue_dropped_total",
+            "Rule fetches dropped or evicted because the queue was full"
+        ))?;
+        registry.register(Box::new(rule_queue_dropped_total.clone()))?;
+
+        Ok(Self {
+            loops,
+            venue_requests_total,
+            venue_request_duration_seconds,
+            venue_retries_total,
+            channel_depth,
+            task_restarts_total,
+            leader,
+            rule_queue_depth,
+            rule_queue_dropped_total,
+            registry: Arc::new(registry),
+        })
+    }
+
+    /// Registry holding every ingest metric, for the metrics listener
+    pub fn registry(&self) -> Arc<Registry> {
+        Arc::clone(&self.registry)
+    }
+
+    /// Get metrics in Prometheus text format
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        pm_metrics::render(&self.registry)
+    }
+}
+
+impl Default for IngestMetrics {
+    fn default() -> Self {
+        Self::new().expect("Failed to create metrics")
+    }
+}
@@ -1,14 +1,63 @@
 //! Ingestion orchestrator with bounded channels and periodic tasks
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use pm_domain::{Market, Quote, RuleSnapshot};
-use pm_storage::{markets, quotes, rules};
+use chrono::{DateTime, Utc};
+use pm_domain::{
+    Clock, Market, MarketStatus, Outcome, QuarantinedQuote, Quote, QuoteLimits, QuoteValidity,
+    RawPayload, RuleSnapshot, SharedClock, SystemClock, Trade,
+};
+use pm_storage::{
+    changes,
+    ingest_state::{self, MarketFetch},
+    leader::{self, LeaderElection, LeaderRole},
+    markets, payloads, quotes, rules, schema_drift, trades,
+};
 use sqlx::PgPool;
-use tokio::{sync::mpsc, time::interval};
+use tokio::{
+    sync::{mpsc, Mutex as AsyncMutex},
+    time::{interval, interval_at, Instant, Interval},
+};
 use tokio_util::sync::CancellationToken;
 
-use crate::{client::VenueClient, config::IngestConfig};
+use crate::{
+    client::{
+        ClientError, VenueClient, ENDPOINT_BOOK, ENDPOINT_MARKET, ENDPOINT_MARKETS, ENDPOINT_TRADES,
+    },
+    config::{IngestConfig, PayloadArchiveConfig, TradesConfig},
+    fetch_queue::{FetchPriority, FetchQueue},
+    instrumented::InstrumentedVenueClient,
+    metrics::IngestMetrics,
+    schema::SchemaTracker,
+    shutdown::ShutdownDrain,
+    supervisor::{Supervisor, SupervisorError},
+};
+
+/// Source recorded in the status history for reconciliation updates
+const RECONCILIATION_SOURCE: &str = "reconciliation";
+
+/// Checkpointed task names in `ingest_state`
+const TASK_DISCOVERY: &str = "discovery";
+const TASK_QUOTE_POLLING: &str = "quote_polling";
+const TASK_TRADE_POLLING: &str = "trade_polling";
+const TASK_RULE_EXTRACTION: &str = "rule_extraction";
+const TASK_RECONCILIATION: &str = "reconciliation";
+
+/// Skip reason recorded while schema drift pauses an endpoint
+const SKIP_SCHEMA_DEGRADED: &str = "schema_degraded";
+
+/// Status change observed by reconciliation, pending persistence
+#[derive(Debug, Clone)]
+struct StatusUpdate {
+    market_id: String,
+    status: MarketStatus,
+    reason: String,
+    observed_at: DateTime<Utc>,
+}
 
 /// Error type for orchestrator operations
 #[derive(Debug, thiserror::Error)]
@@ -21,10 +70,37 @@ pub enum OrchestratorError {
 
     #[error("Channel send failed")]
     ChannelSend,
+
+    #[error("Supervision failed: {0}")]
+    Supervisor(#[from] SupervisorError),
+
+    #[error("Lost ingest leadership")]
+    LeadershipLost,
 }
 
 pub type Result<T> = std::result::Result<T, OrchestratorError>;
 
+/// Channel receiver shared across restarts of the task consuming it
+type SharedReceiver<T> = Arc<AsyncMutex<mpsc::Receiver<T>>>;
+
+/// Reads a channel's depth while any of its senders is alive
+type DepthProbe = (&'static str, Box<dyn Fn() -> Option<usize> + Send + Sync>);
+
+/// Probe `tx`'s depth without keeping the channel open
+fn depth_probe<T: Send + 'static>(name: &'static str, tx: &mpsc::Sender<T>) -> DepthProbe {
+    let tx = tx.downgrade();
+    (
+        name,
+        Box::new(move || tx.upgrade().map(|tx| tx.max_capacity() - tx.capacity())),
+    )
+}
+
+/// Bounded channel whose receiver can be handed to a restarted task
+fn channel<T>(size: usize) -> (mpsc::Sender<T>, SharedReceiver<T>) {
+    let (tx, rx) = mpsc::channel(size);
+    (tx, Arc::new(AsyncMutex::new(rx)))
+}
+
 /// Ingestion orchestrator coordinates discovery, quote polling, and rule
 /// extraction
 pub struct IngestOrchestrator<C: VenueClient> {
@@ -32,113 +108,518 @@ pub struct IngestOrchestrator<C: VenueClient> {
     pool: PgPool,
     config: IngestConfig,
     cancellation: CancellationToken,
+    payload_rx: Mutex<Option<mpsc::Receiver<RawPayload>>>,
+    schema: Option<Arc<SchemaTracker>>,
+    metrics: IngestMetrics,
+    leader: Option<LeaderElection>,
+    clock: SharedClock,
 }
 
 impl<C: VenueClient + 'static> IngestOrchestrator<C> {
     /// Create a new orchestrator
+    ///
+    /// With `config.leader.enabled`, `run` first waits to be elected ingest
+    /// leader.
     pub fn new(client: C, pool: PgPool, config: IngestConfig) -> Self {
+        let leader = config.leader.enabled.then(|| {
+            LeaderElection::new(
+                pool.clone(),
+                LeaderRole::Ingest,
+                &leader::instance_name(LeaderRole::Ingest),
+                config.leader.clone(),
+            )
+        });
+
         Self {
             client: Arc::new(client),
             pool,
             config,
             cancellation: CancellationToken::new(),
+            payload_rx: Mutex::new(None),
+            schema: None,
+            metrics: IngestMetrics::default(),
+            leader,
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Read time from `clock` for eligibility windows, reconciliation and
+    /// retention
+    ///
+    /// The client stamps quotes and rules itself; give it the same clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record into `metrics` instead of a fresh collector, e.g. one the
+    /// venue client also records retries into
+    pub fn with_metrics(mut self, metrics: IngestMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Metrics recorded by this orchestrator
+    pub fn metrics(&self) -> &IngestMetrics {
+        &self.metrics
+    }
+
+    /// Whether this replica may ingest: it holds the leader lock, or leader
+    /// election is disabled
+    pub fn is_leader(&self) -> bool {
+        self.leader.as_ref().is_none_or(LeaderElection::is_leader)
+    }
+
+    /// Persist drift seen by `tracker` and, if configured, pause writes
+    /// sourced from endpoints it reports as degraded
+    ///
+    /// `tracker` should be the one given to the client's schema tracking.
+    pub fn with_schema_tracker(mut self, tracker: Arc<SchemaTracker>) -> Self {
+        self.schema = Some(tracker);
+        self
+    }
+
+    /// Archive raw payloads captured by the client into `raw_payloads`
+    ///
+    /// `rx` is the receiving end of the channel passed to the client's
+    /// payload capture.
+    pub fn with_payload_archive(self, rx: mpsc::Receiver<RawPayload>) -> Self {
+        *self.payload_rx.lock().unwrap_or_else(|e| e.into_inner()) = Some(rx);
+        self
+    }
+
     /// Get cancellation token for external shutdown
     pub fn cancellation_token(&self) -> CancellationToken {
         self.cancellation.clone()
     }
 
-    /// Start all ingestion tasks
+    /// Start all ingestion tasks under supervision
+    ///
+    /// Returns once cancelled and every task has stopped, or with an error
+    /// after a task exceeds its restart limit. On cancellation the venue
+    /// pollers stop first; persistence tasks then drain their channels for
+    /// up to `shutdown.drain_timeout_ms`.
+    ///
+    /// With leader election, a standby waits here until it holds the lock.
+    /// If the leader's lock session drops, every task is stopped and
+    /// [`OrchestratorError::LeadershipLost`] returned, as a standby may
+    /// already have taken over.
     pub async fn run(&self) -> Result<()> {
+        let leadership = match &self.leader {
+            Some(election) => match election.acquire(&self.cancellation).await {
+                Some(leadership) => Some(leadership),
+                None => {
+                    tracing::info!("Ingestion orchestrator cancelled while standing by");
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+        // A replica running without election is the sole runner and reports
+        // as leader, as in scoring
+        self.metrics.leader.set(1);
+
         tracing::info!("Starting ingestion orchestrator");
 
-        // Create bounded channels for work distribution
-        let (market_tx, market_rx) = mpsc::channel::<Market>(self.config.max_channel_size);
-        let (quote_tx, quote_rx) = mpsc::channel::<Vec<Quote>>(self.config.max_channel_size);
-        let (rule_tx, rule_rx) = mpsc::channel::<RuleSnapshot>(self.config.max_channel_size);
+        // Every venue call is measured; the rule queue outlives task restarts
+        let client = Arc::new(InstrumentedVenueClient::new(
+            Arc::clone(&self.client),
+            self.metrics.clone(),
+        ));
+        let rule_queue = Arc::new(
+            FetchQueue::new(self.config.rule_queue.capacity).with_metrics(
+                self.metrics.rule_queue_depth.clone(),
+                self.metrics.rule_queue_dropped_total.clone(),
+            ),
+        );
 
-        // Spawn worker tasks
-        let mut handles = vec![];
+        // Create bounded channels for work distribution. Receivers are
+        // shared so a restarted persistence task picks up where the
+        // previous run stopped.
+        let (market_tx, market_rx) = channel::<Market>(self.config.max_channel_size);
+        let (quote_tx, quote_rx) = channel::<Vec<Quote>>(self.config.max_channel_size);
+        let (rule_tx, rule_rx) = channel::<RuleSnapshot>(self.config.max_channel_size);
+        let (outcome_tx, outcome_rx) = channel::<Vec<Outcome>>(self.config.max_channel_size);
+        let (status_tx, status_rx) = channel::<StatusUpdate>(self.config.max_channel_size);
+        let mut probes = vec![
+            depth_probe("markets", &market_tx),
+            depth_probe("quotes", &quote_tx),
+            depth_probe("rules", &rule_tx),
+            depth_probe("outcomes", &outcome_tx),
+            depth_probe("statuses", &status_tx),
+        ];
+
+        // Producers stop on cancellation; persistence tasks keep running
+        // until every producer has stopped, then drain their channels
+        let mut producers =
+            Supervisor::new(self.config.supervisor.clone(), self.cancellation.clone())
+                .with_restart_counter(self.metrics.task_restarts_total.clone());
+        let persistence_cancellation = CancellationToken::new();
+        let mut persistence = Supervisor::new(
+            self.config.supervisor.clone(),
+            persistence_cancellation.clone(),
+        )
+        .with_restart_counter(self.metrics.task_restarts_total.clone());
+        let drain = ShutdownDrain::new(&self.config.shutdown);
 
         // Market discovery task
-        handles.push(tokio::spawn({
-            let client = Arc::clone(&self.client);
+        producers.add("discovery", {
+            let client = Arc::clone(&client);
+            let pool = self.pool.clone();
             let config = self.config.clone();
-            let market_tx = market_tx.clone();
+            let schema = self.schema.clone();
+            let clock = Arc::clone(&self.clock);
+            let metrics = self.metrics.clone();
             let cancellation = self.cancellation.clone();
 
-            async move {
-                Self::discovery_task(client, config, market_tx, cancellation).await;
+            move || {
+                Self::discovery_task(
+                    Arc::clone(&client),
+                    pool.clone(),
+                    config.clone(),
+                    market_tx.clone(),
+                    schema.clone(),
+                    Arc::clone(&clock),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
 
         // Quote polling task
-        handles.push(tokio::spawn({
-            let client = Arc::clone(&self.client);
+        producers.add("quote_polling", {
+            let client = Arc::clone(&client);
             let pool = self.pool.clone();
             let config = self.config.clone();
-            let quote_tx = quote_tx.clone();
+            let schema = self.schema.clone();
+            let clock = Arc::clone(&self.clock);
+            let metrics = self.metrics.clone();
             let cancellation = self.cancellation.clone();
 
-            async move {
-                Self::quote_polling_task(client, pool, config, quote_tx, cancellation).await;
+            move || {
+                Self::quote_polling_task(
+                    Arc::clone(&client),
+                    pool.clone(),
+                    config.clone(),
+                    quote_tx.clone(),
+                    schema.clone(),
+                    Arc::clone(&clock),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
+
+        // Trade polling and persistence tasks
+        if self.config.trades.enabled {
+            let (trade_tx, trade_rx) = channel::<Vec<Trade>>(self.config.max_channel_size);
+            probes.push(depth_probe("trades", &trade_tx));
+
+            producers.add("trade_polling", {
+                let client = Arc::clone(&client);
+                let pool = self.pool.clone();
+                let config = self.config.clone();
+                let schema = self.schema.clone();
+                let clock = Arc::clone(&self.clock);
+                let metrics = self.metrics.clone();
+                let cancellation = self.cancellation.clone();
+
+                move || {
+                    Self::trade_polling_task(
+                        Arc::clone(&client),
+                        pool.clone(),
+                        config.clone(),
+                        trade_tx.clone(),
+                        schema.clone(),
+                        Arc::clone(&clock),
+                        metrics.clone(),
+                        cancellation.clone(),
+                    )
+                }
+            });
+
+            persistence.add("trade_persistence", {
+                let pool = self.pool.clone();
+                let config = self.config.trades.clone();
+                let drain = drain.clone();
+                let clock = Arc::clone(&self.clock);
+                let metrics = self.metrics.clone();
+                let cancellation = persistence_cancellation.clone();
+
+                move || {
+                    Self::trade_persistence_task(
+                        pool.clone(),
+                        config.clone(),
+                        Arc::clone(&trade_rx),
+                        drain.clone(),
+                        Arc::clone(&clock),
+                        metrics.clone(),
+                        cancellation.clone(),
+                    )
+                }
+            });
+        }
 
         // Rule extraction task
-        handles.push(tokio::spawn({
-            let client = Arc::clone(&self.client);
+        producers.add("rule_extraction", {
+            let client = Arc::clone(&client);
             let pool = self.pool.clone();
             let config = self.config.clone();
-            let rule_tx = rule_tx.clone();
+            let schema = self.schema.clone();
+            let queue = Arc::clone(&rule_queue);
+            let clock = Arc::clone(&self.clock);
+            let metrics = self.metrics.clone();
             let cancellation = self.cancellation.clone();
 
-            async move {
-                Self::rule_extraction_task(client, pool, config, rule_tx, cancellation).await;
+            move || {
+                Self::rule_extraction_task(
+                    Arc::clone(&client),
+                    pool.clone(),
+                    config.clone(),
+                    rule_tx.clone(),
+                    outcome_tx.clone(),
+                    schema.clone(),
+                    Arc::clone(&queue),
+                    Arc::clone(&clock),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
 
-        // Market persistence task
-        handles.push(tokio::spawn({
+        // Lifecycle reconciliation task
+        producers.add("reconciliation", {
+            let client = Arc::clone(&client);
             let pool = self.pool.clone();
+            let config = self.config.clone();
+            let clock = Arc::clone(&self.clock);
+            let metrics = self.metrics.clone();
             let cancellation = self.cancellation.clone();
 
-            async move {
-                Self::market_persistence_task(pool, market_rx, cancellation).await;
+            move || {
+                Self::reconciliation_task(
+                    Arc::clone(&client),
+                    pool.clone(),
+                    config.clone(),
+                    status_tx.clone(),
+                    Arc::clone(&clock),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
+
+        // Market persistence task
+        persistence.add("market_persistence", {
+            let pool = self.pool.clone();
+            let queue = Arc::clone(&rule_queue);
+            let clock = Arc::clone(&self.clock);
+            let drain = drain.clone();
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
+
+            move || {
+                Self::market_persistence_task(
+                    pool.clone(),
+                    Arc::clone(&market_rx),
+                    Arc::clone(&queue),
+                    Arc::clone(&clock),
+                    drain.clone(),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
+            }
+        });
 
         // Quote persistence task
-        handles.push(tokio::spawn({
+        persistence.add("quote_persistence", {
             let pool = self.pool.clone();
-            let cancellation = self.cancellation.clone();
+            let limits = self.config.quote_validation.clone();
+            let drain = drain.clone();
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
 
-            async move {
-                Self::quote_persistence_task(pool, quote_rx, cancellation).await;
+            move || {
+                Self::quote_persistence_task(
+                    pool.clone(),
+                    Arc::clone(&quote_rx),
+                    limits.clone(),
+                    drain.clone(),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
 
         // Rule persistence task
-        handles.push(tokio::spawn({
+        persistence.add("rule_persistence", {
             let pool = self.pool.clone();
-            let cancellation = self.cancellation.clone();
+            let drain = drain.clone();
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
 
-            async move {
-                Self::rule_persistence_task(pool, rule_rx, cancellation).await;
+            move || {
+                Self::rule_persistence_task(
+                    pool.clone(),
+                    Arc::clone(&rule_rx),
+                    drain.clone(),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
             }
-        }));
+        });
 
-        // Wait for cancellation signal
-        self.cancellation.cancelled().await;
-        tracing::info!("Shutting down ingestion orchestrator");
+        // Outcome persistence task
+        persistence.add("outcome_persistence", {
+            let pool = self.pool.clone();
+            let drain = drain.clone();
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
+
+            move || {
+                Self::outcome_persistence_task(
+                    pool.clone(),
+                    Arc::clone(&outcome_rx),
+                    drain.clone(),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
+            }
+        });
 
-        // Wait for all tasks to complete
-        for handle in handles {
-            let _ = handle.await;
+        // Status persistence task
+        persistence.add("status_persistence", {
+            let pool = self.pool.clone();
+            let drain = drain.clone();
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
+
+            move || {
+                Self::status_persistence_task(
+                    pool.clone(),
+                    Arc::clone(&status_rx),
+                    drain.clone(),
+                    metrics.clone(),
+                    cancellation.clone(),
+                )
+            }
+        });
+
+        // Channel depth sampling
+        persistence.add("channel_depth", {
+            let probes = Arc::new(probes);
+            let metrics = self.metrics.clone();
+            let cancellation = persistence_cancellation.clone();
+
+            move || {
+                Self::channel_depth_task(Arc::clone(&probes), metrics.clone(), cancellation.clone())
+            }
+        });
+
+        // Payload archive task
+        let payload_rx = self
+            .payload_rx
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(payload_rx) = payload_rx {
+            let payload_rx = Arc::new(AsyncMutex::new(payload_rx));
+
+            persistence.add("payload_archive", {
+                let pool = self.pool.clone();
+                let config = self.config.payloads.clone();
+                let drain = drain.clone();
+                let clock = Arc::clone(&self.clock);
+                let metrics = self.metrics.clone();
+                let cancellation = persistence_cancellation.clone();
+
+                move || {
+                    Self::payload_archive_task(
+                        pool.clone(),
+                        config.clone(),
+                        Arc::clone(&payload_rx),
+                        drain.clone(),
+                        Arc::clone(&clock),
+                        metrics.clone(),
+                        cancellation.clone(),
+                    )
+                }
+            });
         }
 
+        // Schema drift persistence task
+        if let Some(tracker) = &self.schema {
+            persistence.add("schema_drift", {
+                let pool = self.pool.clone();
+                let tracker = Arc::clone(tracker);
+                let cadence = Duration::from_secs(self.config.schema_drift.flush_cadence_sec);
+                let metrics = self.metrics.clone();
+                let cancellation = persistence_cancellation.clone();
+
+                move || {
+                    Self::schema_drift_task(
+                        pool.clone(),
+                        Arc::clone(&tracker),
+                        cadence,
+                        metrics.clone(),
+                        cancellation.clone(),
+                    )
+                }
+            });
+        }
+
+        let producers = async {
+            let result = producers.run().await;
+            tracing::info!("Producers stopped, draining persistence channels");
+            persistence_cancellation.cancel();
+            result
+        };
+        let persistence = async {
+            let result = persistence.run().await;
+            // Producers have nowhere to send once persistence gives up
+            if result.is_err() {
+                self.cancellation.cancel();
+            }
+            result
+        };
+        let stopped = CancellationToken::new();
+        let tasks = async {
+            let results = tokio::join!(producers, persistence);
+            stopped.cancel();
+            results
+        };
+
+        // Leadership is held until persistence has drained
+        let watch = async {
+            let Some(mut leadership) = leadership else {
+                return false;
+            };
+            tokio::select! {
+                _ = leadership.lost() => {
+                    self.cancellation.cancel();
+                    true
+                }
+                _ = stopped.cancelled() => {
+                    leadership.release().await;
+                    false
+                }
+            }
+        };
+
+        let ((producers, persistence), lost) = tokio::join!(tasks, watch);
+        self.metrics.leader.set(0);
+
+        tracing::info!(
+            persisted = drain.totals().persisted(),
+            dropped = drain.totals().dropped(),
+            "Ingestion orchestrator stopped"
+        );
+
+        if lost {
+            return Err(OrchestratorError::LeadershipLost);
+        }
+        producers?;
+        persistence?;
         Ok(())
     }
 
@@ -148,31 +629,56 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
     }
 
     /// Market discovery task - periodically discovers new markets
+    ///
+    /// Each page's offset is checkpointed, so a pass interrupted by a
+    /// restart resumes where it stopped.
+    #[allow(clippy::too_many_arguments)]
     async fn discovery_task(
-        client: Arc<C>,
+        client: Arc<InstrumentedVenueClient<C>>,
+        pool: PgPool,
         config: IngestConfig,
         market_tx: mpsc::Sender<Market>,
+        schema: Option<Arc<SchemaTracker>>,
+        clock: SharedClock,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
-        let mut ticker = interval(Duration::from_secs(config.discovery_cadence_sec));
+        let cadence = Duration::from_secs(config.discovery_cadence_sec);
+        let mut ticker = Self::resume_ticker(&pool, TASK_DISCOVERY, cadence, clock.as_ref()).await;
 
         loop {
             tokio::select! {
                 _ = ticker.tick() => {
-                    tracing::info!("Running market discovery");
-
+                    let mut cycle = metrics.loops.cycle(TASK_DISCOVERY);
                     let limit = config.max_markets_per_discovery;
-                    let mut offset = 0;
+                    let mut offset = match ingest_state::get_task_state(&pool, TASK_DISCOVERY).await {
+                        Ok(state) => state
+                            .and_then(|s| s.cursor)
+                            .and_then(|c| c.parse().ok())
+                            .unwrap_or(0),
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Failed to read discovery cursor");
+                            0
+                        }
+                    };
+                    tracing::info!(offset, "Running market discovery");
 
                     loop {
                         match client.discover_markets(limit, offset).await {
                             Ok(markets) => {
                                 if markets.is_empty() {
+                                    Self::checkpoint(&pool, TASK_DISCOVERY, clock.now()).await;
                                     break;
                                 }
 
                                 tracing::info!(count = markets.len(), "Discovered markets");
 
+                                if Self::paused(&schema, &config, ENDPOINT_MARKETS) {
+                                    tracing::warn!("Venue schema degraded, not persisting discovered markets");
+                                    metrics.loops.record_skipped(SKIP_SCHEMA_DEGRADED, markets.len());
+                                    break;
+                                }
+
                                 for market in markets {
                                     if market_tx.send(market).await.is_err() {
                                         tracing::error!("Market channel closed");
@@ -181,9 +687,16 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
                                 }
 
                                 offset += limit;
+                                let cursor = offset.to_string();
+                                if let Err(e) =
+                                    ingest_state::set_task_cursor(&pool, TASK_DISCOVERY, Some(&cursor), clock.now()).await
+                                {
+                                    tracing::warn!(error = %e, "Failed to checkpoint discovery cursor");
+                                }
                             }
                             Err(e) => {
                                 tracing::error!(error = %e, "Market discovery failed");
+                                cycle.fail();
                                 break;
                             }
                         }
@@ -198,52 +711,103 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
     }
 
     /// Quote polling task - periodically fetches quotes for active markets
+    #[allow(clippy::too_many_arguments)]
     async fn quote_polling_task(
-        client: Arc<C>,
+        client: Arc<InstrumentedVenueClient<C>>,
         pool: PgPool,
         config: IngestConfig,
         quote_tx: mpsc::Sender<Vec<Quote>>,
+        schema: Option<Arc<SchemaTracker>>,
+        clock: SharedClock,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
-        let mut ticker = interval(Duration::from_secs(config.quotes_cadence_sec));
+        let cadence = Duration::from_secs(config.quotes_cadence_sec);
+        let mut ticker =
+            Self::resume_ticker(&pool, TASK_QUOTE_POLLING, cadence, clock.as_ref()).await;
 
         loop {
             tokio::select! {
                 _ = ticker.tick() => {
+                    let mut cycle = metrics.loops.cycle(TASK_QUOTE_POLLING);
                     tracing::info!("Polling quotes");
 
-                    // Get active markets from database
-                    let market_ids = match markets::list_active_markets(
+                    // Active markets, least recently quoted first
+                    let market_ids = match ingest_state::list_due_markets(
                         &pool,
+                        MarketFetch::Quote,
+                        clock.now(),
                         3600,          // min 1 hour remaining
                         1209600,       // max 14 days remaining
                         config.max_quotes_per_fetch as i64,
                     )
                     .await
                     {
-                        Ok(markets) => markets.into_iter().map(|m| m.market_id).collect::<Vec<_>>(),
+                        Ok(ids) => ids,
                         Err(e) => {
                             tracing::error!(error = %e, "Failed to fetch active markets");
+                            cycle.fail();
                             continue;
                         }
                     };
 
                     if market_ids.is_empty() {
                         tracing::debug!("No active markets to poll");
+                        Self::checkpoint(&pool, TASK_QUOTE_POLLING, clock.now()).await;
                         continue;
                     }
 
-                    match client.get_quotes(&market_ids).await {
+                    // Order books are fetched per outcome token
+                    let outcomes = match markets::get_outcomes_batch(&pool, &market_ids).await {
+                        Ok(outcomes) => outcomes,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to fetch market outcomes");
+                            cycle.fail();
+                            continue;
+                        }
+                    };
+
+                    let with_tokens = outcomes
+                        .iter()
+                        .filter(|o| o.token_id.is_some())
+                        .map(|o| o.market_id.as_str())
+                        .collect::<HashSet<_>>()
+                        .len();
+                    if with_tokens < market_ids.len() {
+                        tracing::debug!(
+                            missing = market_ids.len() - with_tokens,
+                            "Skipping markets without known CLOB token ids"
+                        );
+                        metrics
+                            .loops
+                            .record_skipped("no_token_ids", market_ids.len() - with_tokens);
+                    }
+
+                    match client.get_quotes(&outcomes).await {
                         Ok(quotes) => {
                             tracing::info!(count = quotes.len(), "Fetched quotes");
 
+                            if Self::paused(&schema, &config, ENDPOINT_BOOK) {
+                                tracing::warn!("Venue schema degraded, not persisting quotes");
+                                metrics.loops.record_skipped(SKIP_SCHEMA_DEGRADED, quotes.len());
+                                continue;
+                            }
+
                             if quote_tx.send(quotes).await.is_err() {
                                 tracing::error!("Quote channel closed");
                                 return;
                             }
+
+                            // Markets without tokens or a usable book count as
+                            // polled too, or they would hold the front of the
+                            // queue forever
+                            let now = clock.now();
+                            Self::record_fetches(&pool, MarketFetch::Quote, &market_ids, now).await;
+                            Self::checkpoint(&pool, TASK_QUOTE_POLLING, now).await;
                         }
                         Err(e) => {
                             tracing::error!(error = %e, "Quote polling failed");
+                            cycle.fail();
                         }
                     }
                 }
@@ -255,83 +819,377 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
         }
     }
 
-    /// Rule extraction task - periodically extracts rule text and risk flags
+    /// Trade polling task - periodically fetches recent fills for active
+    /// markets
+    #[allow(clippy::too_many_arguments)]
+    async fn trade_polling_task(
+        client: Arc<InstrumentedVenueClient<C>>,
+        pool: PgPool,
+        config: IngestConfig,
+        trade_tx: mpsc::Sender<Vec<Trade>>,
+        schema: Option<Arc<SchemaTracker>>,
+        clock: SharedClock,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let cadence = Duration::from_secs(config.trades.cadence_sec);
+        let mut ticker =
+            Self::resume_ticker(&pool, TASK_TRADE_POLLING, cadence, clock.as_ref()).await;
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let mut cycle = metrics.loops.cycle(TASK_TRADE_POLLING);
+                    tracing::info!("Polling trades");
+
+                    // Active markets, least recently polled first
+                    let market_ids = match ingest_state::list_due_markets(
+                        &pool,
+                        MarketFetch::Trade,
+                        clock.now(),
+                        3600,          // min 1 hour remaining
+                        1209600,       // max 14 days remaining
+                        config.max_quotes_per_fetch as i64,
+                    )
+                    .await
+                    {
+                        Ok(ids) => ids,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to fetch active markets");
+                            cycle.fail();
+                            continue;
+                        }
+                    };
+
+                    let mut polled = Vec::new();
+                    let mut fetched = Vec::new();
+                    for market_id in market_ids {
+                        if cancellation.is_cancelled() {
+                            break;
+                        }
+
+                        match client.get_trades(&market_id, config.trades.fetch_limit).await {
+                            Ok(trades) => {
+                                fetched.extend(trades);
+                                polled.push(market_id);
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    market_id = %market_id,
+                                    error = %e,
+                                    "Failed to fetch trades"
+                                );
+                            }
+                        }
+                    }
+
+                    if !fetched.is_empty() {
+                        tracing::info!(count = fetched.len(), "Fetched trades");
+
+                        if Self::paused(&schema, &config, ENDPOINT_TRADES) {
+                            tracing::warn!("Venue schema degraded, not persisting trades");
+                            metrics.loops.record_skipped(SKIP_SCHEMA_DEGRADED, fetched.len());
+                            continue;
+                        }
+
+                        if trade_tx.send(fetched).await.is_err() {
+                            tracing::error!("Trade channel closed");
+                            return;
+                        }
+                    }
+
+                    // Markets without recent fills count as polled too, or
+                    // they would hold the front of the queue forever
+                    let now = clock.now();
+                    Self::record_fetches(&pool, MarketFetch::Trade, &polled, now).await;
+                    Self::checkpoint(&pool, TASK_TRADE_POLLING, now).await;
+                }
+                _ = cancellation.cancelled() => {
+                    tracing::info!("Trade polling task cancelled");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Rule extraction task - extracts rule text and risk flags, and
+    /// resolves outcome token ids, for markets taken from the rule queue
+    ///
+    /// New markets are queued by market persistence as soon as they are
+    /// stored. Every refresh cadence the least recently checked markets are
+    /// queued behind them, so existing markets are refreshed in rotation.
+    #[allow(clippy::too_many_arguments)]
     async fn rule_extraction_task(
-        client: Arc<C>,
+        client: Arc<InstrumentedVenueClient<C>>,
         pool: PgPool,
         config: IngestConfig,
         rule_tx: mpsc::Sender<RuleSnapshot>,
+        outcome_tx: mpsc::Sender<Vec<Outcome>>,
+        schema: Option<Arc<SchemaTracker>>,
+        rule_queue: Arc<FetchQueue>,
+        clock: SharedClock,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
-        let mut ticker = interval(Duration::from_secs(config.rules_refresh_cadence_sec));
+        let cadence = Duration::from_secs(config.rules_refresh_cadence_sec);
+        let mut ticker =
+            Self::resume_ticker(&pool, TASK_RULE_EXTRACTION, cadence, clock.as_ref()).await;
 
         loop {
+            // The queue is rarely empty, so shutdown is checked first rather
+            // than competing with it
             tokio::select! {
-                _ = ticker.tick() => {
-                    tracing::info!("Extracting rules");
+                biased;
 
-                    // Get active markets from database
-                    let markets = match markets::list_active_markets(
+                _ = cancellation.cancelled() => {
+                    tracing::info!("Rule extraction task cancelled");
+                    return;
+                }
+                _ = ticker.tick() => {
+                    let mut cycle = metrics.loops.cycle("rule_refresh");
+                    // Active markets, least recently checked first
+                    let market_ids = match ingest_state::list_due_markets(
                         &pool,
+                        MarketFetch::Rule,
+                        clock.now(),
                         3600,          // min 1 hour remaining
                         1209600,       // max 14 days remaining
-                        100,           // limit to 100 markets per cycle
+                        config.rule_queue.refresh_batch as i64,
                     )
                     .await
                     {
-                        Ok(m) => m,
+                        Ok(ids) => ids,
                         Err(e) => {
                             tracing::error!(error = %e, "Failed to fetch active markets");
+                            cycle.fail();
                             continue;
                         }
                     };
 
-                    for market in markets {
-                        match client.get_rules(&market.market_id).await {
-                            Ok(rule) => {
-                                // Check if rule hash has changed before sending
-                                let has_changed = match rules::has_rule_changed(
-                                    &pool,
-                                    &rule.market_id,
-                                    &rule.rule_hash,
-                                )
-                                .await
-                                {
-                                    Ok(changed) => changed,
-                                    Err(e) => {
-                                        tracing::error!(
-                                            market_id = %market.market_id,
-                                            error = %e,
-                                            "Failed to check rule hash"
-                                        );
-                                        true // Assume changed on error
-                                    }
-                                };
+                    let queued = market_ids
+                        .iter()
+                        .filter(|id| rule_queue.push(id, FetchPriority::Refresh))
+                        .count();
+                    tracing::info!(queued, "Queued rule refresh");
 
-                                if has_changed {
-                                    tracing::info!(
-                                        market_id = %market.market_id,
-                                        "Rule text changed"
-                                    );
+                    Self::checkpoint(&pool, TASK_RULE_EXTRACTION, clock.now()).await;
+                }
+                (market_id, priority) = rule_queue.pop() => {
+                    let mut cycle = metrics.loops.cycle(TASK_RULE_EXTRACTION);
 
-                                    if rule_tx.send(rule).await.is_err() {
-                                        tracing::error!("Rule channel closed");
-                                        return;
-                                    }
-                                }
+                    if priority == FetchPriority::New {
+                        tracing::info!(market_id = %market_id, "Fetching rules for new market");
+                    }
+
+                    let (open, fetched) = Self::fetch_rules_and_outcomes(
+                        client.as_ref(),
+                        &pool,
+                        &config,
+                        &rule_tx,
+                        &outcome_tx,
+                        &schema,
+                        clock.as_ref(),
+                        &market_id,
+                    )
+                    .await;
+                    if !open {
+                        return;
+                    }
+                    if !fetched {
+                        cycle.fail();
+                    }
+
+                    // Fetching continues while degraded so a conforming
+                    // response can clear the drift; only persistence waits,
+                    // and skipped markets keep their fetch time and stay due
+                    if Self::paused(&schema, &config, ENDPOINT_MARKET) {
+                        tracing::warn!(
+                            market_id = %market_id,
+                            "Venue schema degraded, not persisting rules or outcomes"
+                        );
+                        metrics.loops.record_skipped(SKIP_SCHEMA_DEGRADED, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetch and forward one market's rules and outcomes
+    ///
+    /// Returns whether the downstream channels are still open, and whether
+    /// both fetches succeeded.
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_rules_and_outcomes(
+        client: &InstrumentedVenueClient<C>,
+        pool: &PgPool,
+        config: &IngestConfig,
+        rule_tx: &mpsc::Sender<RuleSnapshot>,
+        outcome_tx: &mpsc::Sender<Vec<Outcome>>,
+        schema: &Option<Arc<SchemaTracker>>,
+        clock: &dyn Clock,
+        market_id: &str,
+    ) -> (bool, bool) {
+        let fetched = [market_id.to_string()];
+        let mut ok = true;
+
+        match client.get_rules(market_id).await {
+            Ok(_) if Self::paused(schema, config, ENDPOINT_MARKET) => {}
+            Ok(rule) => {
+                Self::record_fetches(pool, MarketFetch::Rule, &fetched, clock.now()).await;
+
+                // Check if rule hash has changed before sending
+                let has_changed =
+                    match rules::has_rule_changed(pool, &rule.market_id, &rule.rule_hash).await {
+                        Ok(changed) => changed,
+                        Err(e) => {
+                            tracing::error!(
+                                market_id = %market_id,
+                                error = %e,
+                                "Failed to check rule hash"
+                            );
+                            true // Assume changed on error
+                        }
+                    };
+
+                if has_changed {
+                    tracing::info!(market_id = %market_id, "Rule text changed");
+
+                    if rule_tx.send(rule).await.is_err() {
+                        tracing::error!("Rule channel closed");
+                        return (false, ok);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    market_id = %market_id,
+                    error = %e,
+                    "Rule extraction failed"
+                );
+                ok = false;
+            }
+        }
+
+        match client.get_outcomes(market_id).await {
+            Ok(_) if Self::paused(schema, config, ENDPOINT_MARKET) => {}
+            Ok(outcomes) => {
+                if outcome_tx.send(outcomes).await.is_err() {
+                    tracing::error!("Outcome channel closed");
+                    return (false, ok);
+                }
+                Self::record_fetches(pool, MarketFetch::Outcome, &fetched, clock.now()).await;
+            }
+            Err(e) => {
+                tracing::error!(
+                    market_id = %market_id,
+                    error = %e,
+                    "Outcome resolution failed"
+                );
+                ok = false;
+            }
+        }
+
+        (true, ok)
+    }
+
+    /// Reconciliation task - re-checks active and halted markets that
+    /// discovery no longer returns and records their venue lifecycle status
+    ///
+    /// Markets whose status the venue confirms are marked seen, so they are
+    /// not re-checked until they go unseen again.
+    async fn reconciliation_task(
+        client: Arc<InstrumentedVenueClient<C>>,
+        pool: PgPool,
+        config: IngestConfig,
+        status_tx: mpsc::Sender<StatusUpdate>,
+        clock: SharedClock,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let cadence = Duration::from_secs(config.reconcile_cadence_sec);
+        let mut ticker =
+            Self::resume_ticker(&pool, TASK_RECONCILIATION, cadence, clock.as_ref()).await;
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let mut cycle = metrics.loops.cycle(TASK_RECONCILIATION);
+                    let seen_before = clock.now()
+                        - chrono::Duration::seconds(config.reconcile_unseen_after_sec as i64);
+
+                    let markets = match markets::list_unseen_markets(
+                        &pool,
+                        seen_before,
+                        config.max_markets_per_reconcile as i64,
+                    )
+                    .await
+                    {
+                        Ok(m) => m,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to fetch unseen markets");
+                            cycle.fail();
+                            continue;
+                        }
+                    };
+
+                    if markets.is_empty() {
+                        tracing::debug!("No markets to reconcile");
+                        Self::checkpoint(&pool, TASK_RECONCILIATION, clock.now()).await;
+                        continue;
+                    }
+
+                    tracing::info!(count = markets.len(), "Reconciling markets missing from discovery");
+
+                    let mut confirmed = Vec::new();
+                    for market in markets {
+                        let update = match client.get_market_status(&market.market_id).await {
+                            Ok(status) if status == market.status => {
+                                confirmed.push(market.market_id);
+                                continue;
                             }
+                            // Still delisted
+                            Err(ClientError::MarketNotFound(_))
+                                if market.status == MarketStatus::Halted =>
+                            {
+                                confirmed.push(market.market_id);
+                                continue;
+                            }
+                            Ok(status) => StatusUpdate {
+                                market_id: market.market_id,
+                                status,
+                                reason: "venue reported status change".to_string(),
+                                observed_at: clock.now(),
+                            },
+                            Err(ClientError::MarketNotFound(_)) => StatusUpdate {
+                                market_id: market.market_id,
+                                status: MarketStatus::Halted,
+                                reason: "delisted by venue".to_string(),
+                                observed_at: clock.now(),
+                            },
                             Err(e) => {
-                                tracing::error!(
+                                tracing::warn!(
                                     market_id = %market.market_id,
                                     error = %e,
-                                    "Rule extraction failed"
+                                    "Market reconciliation failed"
                                 );
+                                continue;
                             }
+                        };
+
+                        if status_tx.send(update).await.is_err() {
+                            tracing::error!("Status channel closed");
+                            return;
                         }
                     }
+
+                    if let Err(e) = markets::mark_markets_seen(&pool, &confirmed, clock.now()).await {
+                        tracing::warn!(error = %e, "Failed to mark reconciled markets as seen");
+                    }
+
+                    Self::checkpoint(&pool, TASK_RECONCILIATION, clock.now()).await;
                 }
                 _ = cancellation.cancelled() => {
-                    tracing::info!("Rule extraction task cancelled");
+                    tracing::info!("Reconciliation task cancelled");
                     return;
                 }
             }
@@ -341,9 +1199,14 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
     /// Market persistence task - saves markets to database
     async fn market_persistence_task(
         pool: PgPool,
-        mut market_rx: mpsc::Receiver<Market>,
+        market_rx: SharedReceiver<Market>,
+        rule_queue: Arc<FetchQueue>,
+        clock: SharedClock,
+        drain: ShutdownDrain,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
+        let mut market_rx = market_rx.lock().await;
         let mut batch = Vec::new();
         let batch_size = 100;
 
@@ -352,15 +1215,42 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
                 Some(market) = market_rx.recv() => {
                     batch.push(market);
 
-                    if batch.len() >= batch_size {
-                        Self::flush_markets(&pool, &mut batch).await;
+                    // Flush full batches, and partial ones once discovery has
+                    // nothing more queued
+                    if batch.len() >= batch_size || market_rx.is_empty() {
+                        Self::flush_markets(&pool, &mut batch, &rule_queue, clock.now(), &metrics).await;
                     }
                 }
                 _ = cancellation.cancelled() => {
-                    // Flush remaining markets
-                    if !batch.is_empty() {
-                        Self::flush_markets(&pool, &mut batch).await;
+                    // The local batch is drained along with the channel
+                    let mut channel = drain.start("markets", &mut market_rx, |_| 1);
+                    loop {
+                        let next = channel.next().await;
+                        let done = next.is_none();
+                        batch.extend(next);
+
+                        if !batch.is_empty()
+                            && (done || batch.len() >= batch_size || channel.is_empty())
+                        {
+                            channel
+                                .persist(
+                                    batch.len(),
+                                    Self::flush_markets(
+                                        &pool,
+                                        &mut batch,
+                                        &rule_queue,
+                                        clock.now(),
+                                        &metrics,
+                                    ),
+                                )
+                                .await;
+                            batch.clear();
+                        }
+                        if done {
+                            break;
+                        }
                     }
+                    channel.finish();
                     tracing::info!("Market persistence task cancelled");
                     return;
                 }
@@ -368,83 +1258,636 @@ impl<C: VenueClient + 'static> IngestOrchestrator<C> {
         }
     }
 
-    /// Quote persistence task - saves quotes to database
+    /// Quote persistence task - validates quotes and saves them to database
     async fn quote_persistence_task(
         pool: PgPool,
-        mut quote_rx: mpsc::Receiver<Vec<Quote>>,
+        quote_rx: SharedReceiver<Vec<Quote>>,
+        limits: QuoteLimits,
+        drain: ShutdownDrain,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
+        let mut quote_rx = quote_rx.lock().await;
         loop {
             tokio::select! {
                 Some(quotes) = quote_rx.recv() => {
-                    // Save to latest table
-                    if let Err(e) = quotes::upsert_quotes_latest_batch(&pool, &quotes).await {
-                        tracing::error!(error = %e, "Failed to save latest quotes");
+                    Self::persist_quotes(&pool, quotes, &limits, &metrics).await;
+                }
+                _ = cancellation.cancelled() => {
+                    let mut channel = drain.start("quotes", &mut quote_rx, Vec::len);
+                    while let Some(quotes) = channel.next().await {
+                        channel
+                            .persist(quotes.len(), Self::persist_quotes(&pool, quotes, &limits, &metrics))
+                            .await;
                     }
+                    channel.finish();
+                    tracing::info!("Quote persistence task cancelled");
+                    return;
+                }
+            }
+        }
+    }
 
-                    // Sample to 5m table
-                    for quote in &quotes {
-                        if let Err(e) = quotes::insert_quote_5m(&pool, quote).await {
-                            tracing::error!(
-                                market_id = %quote.market_id,
-                                error = %e,
-                                "Failed to save 5m quote sample"
-                            );
-                        }
-                    }
+    /// Validate a quote batch, quarantine failures, and save the rest;
+    /// returns whether the latest quotes were written
+    async fn persist_quotes(
+        pool: &PgPool,
+        quotes: Vec<Quote>,
+        limits: &QuoteLimits,
+        metrics: &IngestMetrics,
+    ) -> bool {
+        let mut cycle = metrics.loops.cycle("quote_persistence");
+        let (quotes, quarantined) = Self::validate_quotes(quotes, limits);
+
+        match quotes::insert_quarantined_batch(pool, &quarantined).await {
+            Ok(()) => metrics
+                .loops
+                .record_rows("quotes_quarantine", quarantined.len()),
+            Err(e) => tracing::error!(error = %e, "Failed to save quarantined quotes"),
+        }
+
+        // Save to latest table
+        if let Err(e) = quotes::upsert_quotes_latest_batch(pool, &quotes).await {
+            tracing::error!(error = %e, "Failed to save latest quotes");
+            cycle.fail();
+            return false;
+        }
+        metrics.loops.record_rows("quotes_latest", quotes.len());
+        let ids: Vec<String> = quotes.iter().map(|q| q.market_id.clone()).collect();
+        Self::notify_changed(pool, &ids).await;
 
-                    tracing::info!(count = quotes.len(), "Persisted quotes");
+        // Sample to 5m table
+        for quote in &quotes {
+            match quotes::insert_quote_5m(pool, quote).await {
+                Ok(_) => metrics.loops.record_rows("quotes_5m", 1),
+                Err(e) => {
+                    tracing::error!(
+                        market_id = %quote.market_id,
+                        error = %e,
+                        "Failed to save 5m quote sample"
+                    );
+                }
+            }
+        }
+
+        tracing::info!(
+            count = quotes.len(),
+            quarantined = quarantined.len(),
+            "Persisted quotes"
+        );
+        true
+    }
+
+    /// Tell scoring that quotes or rules changed for `market_ids`
+    async fn notify_changed(pool: &PgPool, market_ids: &[String]) {
+        if let Err(e) = changes::notify_markets_changed(pool, market_ids).await {
+            tracing::warn!(error = %e, "Failed to notify market changes");
+        }
+    }
+
+    /// Split quotes into those to persist and those to quarantine.
+    ///
+    /// Invalid quotes are held back so the last good quote stays in
+    /// `quotes_latest`; suspicious quotes are persisted and also recorded.
+    fn validate_quotes(
+        quotes: Vec<Quote>,
+        limits: &QuoteLimits,
+    ) -> (Vec<Quote>, Vec<QuarantinedQuote>) {
+        let mut keep = Vec::with_capacity(quotes.len());
+        let mut quarantined = Vec::new();
+
+        for quote in quotes {
+            let check = quote.validate(limits);
+            if check.validity == QuoteValidity::Valid {
+                keep.push(quote);
+                continue;
+            }
+
+            let issues: Vec<&str> = check.issues.iter().map(|i| i.as_str()).collect();
+            tracing::warn!(
+                market_id = %quote.market_id,
+                validity = check.validity.as_str(),
+                issues = ?issues,
+                "Quote failed validation"
+            );
+
+            if check.validity == QuoteValidity::Suspicious {
+                keep.push(quote.clone());
+            }
+            quarantined.push(QuarantinedQuote {
+                quote,
+                validity: check.validity,
+                issues: check.issues,
+            });
+        }
+
+        (keep, quarantined)
+    }
+
+    /// Trade persistence task - saves new fills and prunes each market to
+    /// its window
+    async fn trade_persistence_task(
+        pool: PgPool,
+        config: TradesConfig,
+        trade_rx: SharedReceiver<Vec<Trade>>,
+        drain: ShutdownDrain,
+        clock: SharedClock,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut trade_rx = trade_rx.lock().await;
+        loop {
+            tokio::select! {
+                Some(batch) = trade_rx.recv() => {
+                    Self::persist_trades(&pool, &config, &batch, clock.as_ref(), &metrics).await;
                 }
                 _ = cancellation.cancelled() => {
-                    tracing::info!("Quote persistence task cancelled");
+                    let mut channel = drain.start("trades", &mut trade_rx, Vec::len);
+                    while let Some(batch) = channel.next().await {
+                        channel
+                            .persist(batch.len(), Self::persist_trades(&pool, &config, &batch, clock.as_ref(), &metrics))
+                            .await;
+                    }
+                    channel.finish();
+                    tracing::info!("Trade persistence task cancelled");
                     return;
                 }
             }
         }
     }
 
+    /// Save a batch of fills and prune; returns whether the fills were
+    /// written
+    async fn persist_trades(
+        pool: &PgPool,
+        config: &TradesConfig,
+        batch: &[Trade],
+        clock: &dyn Clock,
+        metrics: &IngestMetrics,
+    ) -> bool {
+        let mut cycle = metrics.loops.cycle("trade_persistence");
+        let saved = match trades::insert_trades_batch(pool, batch).await {
+            Ok(inserted) => {
+                tracing::info!(inserted, "Persisted trades");
+                metrics.loops.record_rows("trades", inserted as usize);
+                true
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to save trades");
+                cycle.fail();
+                false
+            }
+        };
+
+        let cutoff = clock.now() - chrono::Duration::hours(config.retention_hours as i64);
+        match trades::prune_trades(pool, cutoff, config.max_per_market as i64).await {
+            Ok(0) => {}
+            Ok(deleted) => tracing::debug!(deleted, "Pruned trades"),
+            Err(e) => tracing::error!(error = %e, "Failed to prune trades"),
+        }
+
+        saved
+    }
+
     /// Rule persistence task - saves rules to database
     async fn rule_persistence_task(
         pool: PgPool,
-        mut rule_rx: mpsc::Receiver<RuleSnapshot>,
+        rule_rx: SharedReceiver<RuleSnapshot>,
+        drain: ShutdownDrain,
+        metrics: IngestMetrics,
         cancellation: CancellationToken,
     ) {
+        let mut rule_rx = rule_rx.lock().await;
         loop {
             tokio::select! {
                 Some(rule) = rule_rx.recv() => {
-                    if let Err(e) = rules::upsert_rule(&pool, &rule).await {
-                        tracing::error!(
-                            market_id = %rule.market_id,
-                            error = %e,
-                            "Failed to save rule"
+                    Self::persist_rule(&pool, &rule, &metrics).await;
+                }
+                _ = cancellation.cancelled() => {
+                    let mut channel = drain.start("rules", &mut rule_rx, |_| 1);
+                    while let Some(rule) = channel.next().await {
+                        channel.persist(1, Self::persist_rule(&pool, &rule, &metrics)).await;
+                    }
+                    channel.finish();
+                    tracing::info!("Rule persistence task cancelled");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Save one rule snapshot; returns whether it was written
+    async fn persist_rule(pool: &PgPool, rule: &RuleSnapshot, metrics: &IngestMetrics) -> bool {
+        let mut cycle = metrics.loops.cycle("rule_persistence");
+        match rules::upsert_rule(pool, rule).await {
+            Ok(_) => {
+                tracing::info!(market_id = %rule.market_id, "Persisted rule");
+                metrics.loops.record_rows("rules_latest", 1);
+                Self::notify_changed(pool, std::slice::from_ref(&rule.market_id)).await;
+                true
+            }
+            Err(e) => {
+                cycle.fail();
+                tracing::error!(
+                    market_id = %rule.market_id,
+                    error = %e,
+                    "Failed to save rule"
+                );
+                false
+            }
+        }
+    }
+
+    /// Outcome persistence task - saves outcomes and their token ids
+    async fn outcome_persistence_task(
+        pool: PgPool,
+        outcome_rx: SharedReceiver<Vec<Outcome>>,
+        drain: ShutdownDrain,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut outcome_rx = outcome_rx.lock().await;
+        loop {
+            tokio::select! {
+                Some(outcomes) = outcome_rx.recv() => {
+                    Self::persist_outcomes(&pool, &outcomes, &metrics).await;
+                }
+                _ = cancellation.cancelled() => {
+                    let mut channel = drain.start("outcomes", &mut outcome_rx, Vec::len);
+                    while let Some(outcomes) = channel.next().await {
+                        channel
+                            .persist(outcomes.len(), Self::persist_outcomes(&pool, &outcomes, &metrics))
+                            .await;
+                    }
+                    channel.finish();
+                    tracing::info!("Outcome persistence task cancelled");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Save outcomes for one market; returns whether they were written
+    async fn persist_outcomes(
+        pool: &PgPool,
+        outcomes: &[Outcome],
+        metrics: &IngestMetrics,
+    ) -> bool {
+        let mut cycle = metrics.loops.cycle("outcome_persistence");
+        match markets::upsert_outcomes(pool, outcomes).await {
+            Ok(_) => {
+                tracing::debug!(count = outcomes.len(), "Persisted outcomes");
+                metrics.loops.record_rows("market_outcomes", outcomes.len());
+                true
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to save outcomes");
+                cycle.fail();
+                false
+            }
+        }
+    }
+
+    /// Status persistence task - applies reconciled status changes
+    async fn status_persistence_task(
+        pool: PgPool,
+        status_rx: SharedReceiver<StatusUpdate>,
+        drain: ShutdownDrain,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut status_rx = status_rx.lock().await;
+        loop {
+            tokio::select! {
+                Some(update) = status_rx.recv() => {
+                    Self::persist_status(&pool, &update, &metrics).await;
+                }
+                _ = cancellation.cancelled() => {
+                    let mut channel = drain.start("statuses", &mut status_rx, |_| 1);
+                    while let Some(update) = channel.next().await {
+                        channel.persist(1, Self::persist_status(&pool, &update, &metrics)).await;
+                    }
+                    channel.finish();
+                    tracing::info!("Status persistence task cancelled");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Apply one reconciled status change; returns whether it was handled
+    async fn persist_status(pool: &PgPool, update: &StatusUpdate, metrics: &IngestMetrics) -> bool {
+        let mut cycle = metrics.loops.cycle("status_persistence");
+        match markets::update_market_status(
+            pool,
+            &update.market_id,
+            update.status,
+            RECONCILIATION_SOURCE,
+            Some(&update.reason),
+            update.observed_at,
+        )
+        .await
+        {
+            Ok(_) => {
+                metrics.loops.record_rows("market_status_history", 1);
+                true
+            }
+            // Recorded for review by the storage layer
+            Err(markets::MarketError::IllegalTransition { .. }) => true,
+            Err(e) => {
+                cycle.fail();
+                tracing::error!(
+                    market_id = %update.market_id,
+                    error = %e,
+                    "Failed to save market status"
+                );
+                false
+            }
+        }
+    }
+
+    /// Ticker for `task` whose first tick is due one cadence after the
+    /// checkpointed last success, rather than immediately
+    ///
+    /// A task with an interrupted pass (a cursor) or no checkpoint starts
+    /// at once.
+    async fn resume_ticker(
+        pool: &PgPool,
+        task: &str,
+        cadence: Duration,
+        clock: &dyn Clock,
+    ) -> Interval {
+        let delay = match ingest_state::get_task_state(pool, task).await {
+            Ok(Some(state)) if state.cursor.is_none() => state
+                .last_success_at
+                .map(|last| {
+                    let elapsed = (clock.now() - last).to_std().unwrap_or_default();
+                    cadence.saturating_sub(elapsed)
+                })
+                .unwrap_or_default(),
+            Ok(_) => Duration::ZERO,
+            Err(e) => {
+                tracing::warn!(task, error = %e, "Failed to read task checkpoint");
+                Duration::ZERO
+            }
+        };
+
+        if !delay.is_zero() {
+            tracing::info!(
+                task,
+                delay_sec = delay.as_secs(),
+                "Resuming schedule from checkpoint"
+            );
+        }
+        interval_at(Instant::now() + delay, cadence)
+    }
+
+    /// Record a completed cycle of `task`
+    async fn checkpoint(pool: &PgPool, task: &str, at: DateTime<Utc>) {
+        if let Err(e) = ingest_state::record_task_success(pool, task, at).await {
+            tracing::warn!(task, error = %e, "Failed to checkpoint task");
+        }
+    }
+
+    /// Record per-market fetch times
+    async fn record_fetches(
+        pool: &PgPool,
+        fetch: MarketFetch,
+        market_ids: &[String],
+        at: DateTime<Utc>,
+    ) {
+        if let Err(e) = ingest_state::record_market_fetches(pool, fetch, market_ids, at).await {
+            tracing::warn!(%fetch, error = %e, "Failed to record market fetch times");
+        }
+    }
+
+    /// Whether writes sourced from `endpoint` are paused by schema drift
+    fn paused(schema: &Option<Arc<SchemaTracker>>, config: &IngestConfig, endpoint: &str) -> bool {
+        config.schema_drift.pause_on_critical
+            && schema.as_ref().is_some_and(|s| s.is_degraded(endpoint))
+    }
+
+    /// Channel depth task - samples persistence channel depths into the
+    /// `pm_ingest_channel_depth` gauge
+    async fn channel_depth_task(
+        probes: Arc<Vec<DepthProbe>>,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut ticker = interval(Duration::from_secs(1));
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    for (channel, probe) in probes.iter() {
+                        if let Some(depth) = probe() {
+                            metrics
+                                .channel_depth
+                                .with_label_values(&[*channel])
+                                .set(depth as i64);
+                        }
+                    }
+                }
+                _ = cancellation.cancelled() => return,
+            }
+        }
+    }
+
+    /// Schema drift task - periodically persists drift seen by the tracker
+    async fn schema_drift_task(
+        pool: PgPool,
+        tracker: Arc<SchemaTracker>,
+        cadence: Duration,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut ticker = interval(cadence);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    Self::flush_schema_drift(&pool, &tracker, &metrics).await;
+                }
+                _ = cancellation.cancelled() => {
+                    Self::flush_schema_drift(&pool, &tracker, &metrics).await;
+                    tracing::info!("Schema drift task cancelled");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Persist drift changed since the last flush
+    async fn flush_schema_drift(pool: &PgPool, tracker: &SchemaTracker, metrics: &IngestMetrics) {
+        let updates = tracker.take_updates();
+        if updates.is_empty() {
+            return;
+        }
+
+        let mut cycle = metrics.loops.cycle("schema_drift");
+        match schema_drift::upsert_drift_batch(pool, &updates).await {
+            Ok(()) => {
+                tracing::debug!(count = updates.len(), "Persisted schema drift");
+                metrics.loops.record_rows("schema_drift", updates.len());
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to save schema drift");
+                cycle.fail();
+            }
+        }
+    }
+
+    /// Payload archive task - stores captured payloads and enforces the
+    /// archive's size and age bounds
+    async fn payload_archive_task(
+        pool: PgPool,
+        config: PayloadArchiveConfig,
+        payload_rx: SharedReceiver<RawPayload>,
+        drain: ShutdownDrain,
+        clock: SharedClock,
+        metrics: IngestMetrics,
+        cancellation: CancellationToken,
+    ) {
+        let mut payload_rx = payload_rx.lock().await;
+        let mut ticker = interval(Duration::from_secs(config.prune_cadence_sec));
+        let mut batch = Vec::new();
+        let batch_size = 50;
+
+        loop {
+            tokio::select! {
+                Some(payload) = payload_rx.recv() => {
+                    if payload.body.len() > config.max_payload_bytes {
+                        tracing::debug!(
+                            url = %payload.request_url,
+                            size = payload.body.len(),
+                            "Skipping oversized payload"
                         );
                     } else {
-                        tracing::info!(market_id = %rule.market_id, "Persisted rule");
+                        batch.push(payload);
+                    }
+
+                    if batch.len() >= batch_size || payload_rx.is_empty() {
+                        Self::flush_payloads(&pool, &mut batch, &metrics).await;
+                    }
+                }
+                _ = ticker.tick() => {
+                    let mut cycle = metrics.loops.cycle("payload_prune");
+                    let cutoff =
+                        clock.now() - chrono::Duration::hours(config.retention_hours as i64);
+
+                    match payloads::prune_payloads(&pool, cutoff, config.max_total_bytes).await {
+                        Ok(0) => {}
+                        Ok(count) => tracing::info!(count, "Pruned archived payloads"),
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to prune archived payloads");
+                            cycle.fail();
+                        }
                     }
                 }
                 _ = cancellation.cancelled() => {
-                    tracing::info!("Rule persistence task cancelled");
+                    // Oversized payloads are skipped rather than counted
+                    let mut channel = drain.start("payloads", &mut payload_rx, |_| 1);
+                    loop {
+                        let next = channel.next().await;
+                        let done = next.is_none();
+                        batch.extend(next.filter(|p| p.body.len() <= config.max_payload_bytes));
+
+                        if !batch.is_empty()
+                            && (done || batch.len() >= batch_size || channel.is_empty())
+                        {
+                            channel
+                                .persist(batch.len(), Self::flush_payloads(&pool, &mut batch, &metrics))
+                                .await;
+                            batch.clear();
+                        }
+                        if done {
+                            break;
+                        }
+                    }
+                    channel.finish();
+                    tracing::info!("Payload archive task cancelled");
                     return;
                 }
             }
         }
     }
 
-    /// Flush market batch to database
-    async fn flush_markets(pool: &PgPool, batch: &mut Vec<Market>) {
+    /// Flush payload batch to database; returns whether it was written
+    async fn flush_payloads(
+        pool: &PgPool,
+        batch: &mut Vec<RawPayload>,
+        metrics: &IngestMetrics,
+    ) -> bool {
         if batch.is_empty() {
-            return;
+            return true;
         }
 
-        match markets::upsert_markets_batch(pool, batch).await {
+        let mut cycle = metrics.loops.cycle("payload_archive");
+        let saved = match payloads::insert_payloads_batch(pool, batch).await {
+            Ok(stored) => {
+                tracing::debug!(count = batch.len(), stored, "Archived payloads");
+                metrics.loops.record_rows("raw_payloads", stored as usize);
+                true
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to archive payloads");
+                cycle.fail();
+                false
+            }
+        };
+
+        batch.clear();
+        saved
+    }
+
+    /// Flush market batch to database, marking it seen at `now`; returns
+    /// whether it was written
+    ///
+    /// Markets stored for the first time are queued for an immediate rule
+    /// and outcome fetch.
+    async fn flush_markets(
+        pool: &PgPool,
+        batch: &mut Vec<Market>,
+        rule_queue: &FetchQueue,
+        now: DateTime<Utc>,
+        metrics: &IngestMetrics,
+    ) -> bool {
+        if batch.is_empty() {
+            return true;
+        }
+        let mut cycle = metrics.loops.cycle("market_persistence");
+
+        let ids: Vec<String> = batch.iter().map(|m| m.market_id.clone()).collect();
+        let known: HashSet<String> = match markets::existing_market_ids(pool, &ids).await {
+            Ok(known) => known.into_iter().collect(),
+            Err(e) => {
+                // The periodic refresh picks these up instead
+                tracing::warn!(error = %e, "Failed to check for new markets");
+                ids.iter().cloned().collect()
+            }
+        };
+
+        let saved = match markets::upsert_markets_batch(pool, batch, now).await {
             Ok(()) => {
-                tracing::info!(count = batch.len(), "Persisted markets");
+                let new: Vec<&String> = ids.iter().filter(|id| !known.contains(*id)).collect();
+                tracing::info!(count = batch.len(), new = new.len(), "Persisted markets");
+                metrics.loops.record_rows("markets", batch.len());
+
+                for market_id in new {
+                    if !rule_queue.push(market_id, FetchPriority::New) {
+                        tracing::warn!(market_id = %market_id, "Rule queue full, deferring to refresh");
+                    }
+                }
+                true
             }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to save markets");
+                cycle.fail();
+                false
             }
-        }
+        };
 
         batch.clear();
+        saved
     }
 }
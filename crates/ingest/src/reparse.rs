@@ -0,0 +1,292 @@
+//! Rebuild market, quote and rule rows from archived venue payloads
+//!
+//! Used after a parser fix: archived bodies are run through the current
+//! parsers and the newest result per market is written back. Rows already
+//! stored from a newer fetch are left alone.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use pm_domain::{Market, Quote, QuoteLimits, QuoteValidity, RuleSnapshot};
+use pm_storage::{
+    markets,
+    payloads::{self, PayloadFilter, StoredPayload},
+    quotes, rules,
+};
+use sqlx::PgPool;
+
+use crate::client::{
+    build_quote, parse_book, parse_markets_page, parse_rule, TopOfBook, ENDPOINT_BOOK,
+    ENDPOINT_MARKET, ENDPOINT_MARKETS,
+};
+
+/// Payloads read per page
+const PAGE_SIZE: i64 = 500;
+
+/// Error type for re-parse runs
+#[derive(Debug, thiserror::Error)]
+pub enum ReparseError {
+    #[error("Payload archive error: {0}")]
+    Payloads(#[from] payloads::PayloadError),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+}
+
+pub type Result<T> = std::result::Result<T, ReparseError>;
+
+/// Which archived payloads to re-parse
+#[derive(Debug, Clone, Default)]
+pub struct ReparseOptions {
+    /// Only payloads fetched at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only payloads for this market
+    pub market_id: Option<String>,
+
+    /// Parse and count, but write nothing
+    pub dry_run: bool,
+
+    /// Quote sanity limits; rebuilt quotes outside them are not written
+    pub quote_limits: QuoteLimits,
+}
+
+/// Outcome of a re-parse run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReparseSummary {
+    pub payloads: usize,
+    pub parse_errors: usize,
+    pub markets: usize,
+    pub quotes: usize,
+    pub rules: usize,
+    /// Results skipped because a newer row is already stored
+    pub skipped_stale: usize,
+    /// Quotes and rules skipped because their market is not stored
+    pub skipped_unknown: usize,
+    /// Rebuilt quotes that failed validation
+    pub skipped_invalid: usize,
+}
+
+/// YES and NO top of book from one quote poll
+type BookPair = (Option<TopOfBook>, Option<TopOfBook>);
+
+/// Newest parse results per market
+#[derive(Default)]
+struct Rebuilt {
+    markets: HashMap<String, (DateTime<Utc>, Market)>,
+    rules: HashMap<String, RuleSnapshot>,
+    /// Books keyed by market and fetch time; YES and NO books from one
+    /// quote poll share a fetch time
+    books: HashMap<(String, DateTime<Utc>), BookPair>,
+}
+
+impl Rebuilt {
+    /// Parse one payload, keeping its result if it is the newest so far
+    fn add(&mut self, stored: &StoredPayload, market_filter: Option<&str>) -> bool {
+        let payload = &stored.payload;
+
+        let parsed = match (payload.endpoint.as_str(), payload.market_id.as_deref()) {
+            (ENDPOINT_MARKETS, _) => parse_markets_page(&payload.body).map(|page| {
+                for market in page {
+                    if market_filter.is_some_and(|id| id != market.market_id) {
+                        continue;
+                    }
+                    match self.markets.get(&market.market_id) {
+                        Some((seen, _)) if *seen > payload.fetched_at => {}
+                        _ => {
+                            self.markets
+                                .insert(market.market_id.clone(), (payload.fetched_at, market));
+                        }
+                    }
+                }
+            }),
+            (ENDPOINT_MARKET, Some(market_id)) => {
+                parse_rule(market_id, &payload.body, payload.fetched_at).map(|rule| {
+                    match self.rules.get(market_id) {
+                        Some(seen) if seen.as_of > rule.as_of => {}
+                        _ => {
+                            self.rules.insert(market_id.to_string(), rule);
+                        }
+                    }
+                })
+            }
+            (ENDPOINT_BOOK, Some(market_id)) => parse_book(&payload.body).map(|book| {
+                let sides = self
+                    .books
+                    .entry((market_id.to_string(), payload.fetched_at))
+                    .or_default();
+                match payload.outcome.as_deref() {
+                    Some("YES") => sides.0 = Some(book),
+                    Some("NO") => sides.1 = Some(book),
+                    _ => {}
+                }
+            }),
+            _ => {
+                tracing::debug!(
+                    id = stored.id,
+                    endpoint = %payload.endpoint,
+                    "Skipping payload from unknown endpoint"
+                );
+                return true;
+            }
+        };
+
+        match parsed {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(
+                    id = stored.id,
+                    endpoint = %payload.endpoint,
+                    error = %e,
+                    "Failed to parse archived payload"
+                );
+                false
+            }
+        }
+    }
+
+    /// Newest valid quote per market built from the collected books, and
+    /// the number of invalid quotes passed over
+    fn quotes(&self, limits: &QuoteLimits) -> (Vec<Quote>, usize) {
+        let mut newest: HashMap<&str, Quote> = HashMap::new();
+        let mut invalid = 0;
+
+        for ((market_id, as_of), (yes, no)) in &self.books {
+            let Some(quote) = build_quote(market_id, *as_of, *yes, *no) else {
+                continue;
+            };
+            if quote.validate(limits).validity == QuoteValidity::Invalid {
+                invalid += 1;
+                continue;
+            }
+            match newest.get(market_id.as_str()) {
+                Some(seen) if seen.as_of > quote.as_of => {}
+                _ => {
+                    newest.insert(market_id, quote);
+                }
+            }
+        }
+
+        (newest.into_values().collect(), invalid)
+    }
+}
+
+/// Re-parse archived payloads and write the rebuilt rows
+pub async fn reparse(pool: &PgPool, options: &ReparseOptions) -> Result<ReparseSummary> {
+    let filter = PayloadFilter {
+        market_id: options.market_id.clone(),
+        endpoint: None,
+        since: options.since,
+    };
+
+    // Discovery pages are not keyed by market, so a market filter is
+    // applied to their parsed contents instead
+    let page_filter = PayloadFilter {
+        market_id: None,
+        endpoint: Some(ENDPOINT_MARKETS.to_string()),
+        since: options.since,
+    };
+
+    let mut summary = ReparseSummary::default();
+    let mut rebuilt = Rebuilt::default();
+
+    let mut filters = vec![filter];
+    if options.market_id.is_some() {
+        filters.push(page_filter);
+    }
+
+    for filter in &filters {
+        let mut after_id = 0;
+        loop {
+            let page = payloads::list_payloads(pool, filter, after_id, PAGE_SIZE).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for stored in &page {
+                summary.payloads += 1;
+                if !rebuilt.add(stored, options.market_id.as_deref()) {
+                    summary.parse_errors += 1;
+                }
+            }
+        }
+    }
+
+    let (rebuilt_quotes, invalid_quotes) = rebuilt.quotes(&options.quote_limits);
+    let rebuilt_markets: Vec<Market> = rebuilt.markets.into_values().map(|(_, m)| m).collect();
+    let rebuilt_rules: Vec<RuleSnapshot> = rebuilt.rules.into_values().collect();
+
+    // Keep only results newer than what is already stored
+    let quote_ids: Vec<String> = rebuilt_quotes.iter().map(|q| q.market_id.clone()).collect();
+    let stored_quotes: HashMap<String, DateTime<Utc>> =
+        quotes::get_quotes_latest_batch(pool, &quote_ids)
+            .await
+            .map_err(|e| ReparseError::Storage(e.to_string()))?
+            .into_iter()
+            .map(|q| (q.market_id, q.as_of))
+            .collect();
+
+    let rule_ids: Vec<String> = rebuilt_rules.iter().map(|r| r.market_id.clone()).collect();
+    let stored_rules: HashMap<String, DateTime<Utc>> = rules::get_rules_batch(pool, &rule_ids)
+        .await
+        .map_err(|e| ReparseError::Storage(e.to_string()))?
+        .into_iter()
+        .map(|r| (r.market_id, r.as_of))
+        .collect();
+
+    // Quotes and rules need a market row, stored or rebuilt
+    let mut ids: Vec<String> = quote_ids;
+    ids.extend(rule_ids);
+    let mut known: HashSet<String> = markets::existing_market_ids(pool, &ids)
+        .await
+        .map_err(|e| ReparseError::Storage(e.to_string()))?
+        .into_iter()
+        .collect();
+    known.extend(rebuilt_markets.iter().map(|m| m.market_id.clone()));
+
+    let (new_quotes, stale_quotes): (Vec<Quote>, Vec<Quote>) = rebuilt_quotes
+        .into_iter()
+        .filter(|q| known.contains(&q.market_id))
+        .partition(|q| {
+            stored_quotes
+                .get(&q.market_id)
+                .is_none_or(|as_of| *as_of <= q.as_of)
+        });
+    let (new_rules, stale_rules): (Vec<RuleSnapshot>, Vec<RuleSnapshot>) = rebuilt_rules
+        .into_iter()
+        .filter(|r| known.contains(&r.market_id))
+        .partition(|r| {
+            stored_rules
+                .get(&r.market_id)
+                .is_none_or(|as_of| *as_of <= r.as_of)
+        });
+
+    summary.markets = rebuilt_markets.len();
+    summary.quotes = new_quotes.len();
+    summary.rules = new_rules.len();
+    summary.skipped_stale = stale_quotes.len() + stale_rules.len();
+    summary.skipped_invalid = invalid_quotes;
+    summary.skipped_unknown = ids.len() - summary.quotes - summary.rules - summary.skipped_stale;
+
+    if options.dry_run {
+        return Ok(summary);
+    }
+
+    // Markets first: quotes and rules reference them
+    markets::upsert_markets_batch(pool, &rebuilt_markets, Utc::now())
+        .await
+        .map_err(|e| ReparseError::Storage(e.to_string()))?;
+
+    quotes::upsert_quotes_latest_batch(pool, &new_quotes)
+        .await
+        .map_err(|e| ReparseError::Storage(e.to_string()))?;
+
+    for rule in &new_rules {
+        rules::upsert_rule(pool, rule)
+            .await
+            .map_err(|e| ReparseError::Storage(e.to_string()))?;
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,494 @@
+//! Record-and-replay venue clients for deterministic, offline ingest tests
+//!
+//! `RecordingVenueClient` wraps any `VenueClient` and appends every call and
+//! its result to a fixture directory. `ReplayVenueClient` serves those
+//! fixtures back through the same trait, so full ingest cycles can run
+//! without network access.
+//!
+//! Each distinct request is stored in its own file, named after the method
+//! and a hash of the request arguments. A file holds the responses in the
+//! order they were recorded; replay walks through them and keeps returning
+//! the last one once exhausted.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use pm_domain::{Market, MarketStatus, Outcome, Quote, RuleSnapshot, Trade};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::client::{ClientError, Result, VenueClient};
+
+/// A recorded request and every response observed for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    request: Value,
+    responses: Vec<RecordedResponse>,
+}
+
+/// A single recorded response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    outcome: RecordedOutcome,
+}
+
+/// Result of a recorded call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedOutcome {
+    Ok(Value),
+    Err(RecordedError),
+}
+
+/// Client error in a form that survives serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+enum RecordedError {
+    MarketNotFound(String),
+    Other(String),
+}
+
+impl From<&ClientError> for RecordedError {
+    fn from(e: &ClientError) -> Self {
+        match e {
+            ClientError::MarketNotFound(id) => Self::MarketNotFound(id.clone()),
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<RecordedError> for ClientError {
+    fn from(e: RecordedError) -> Self {
+        match e {
+            RecordedError::MarketNotFound(id) => ClientError::MarketNotFound(id),
+            RecordedError::Other(message) => ClientError::InvalidResponse(message),
+        }
+    }
+}
+
+/// File name for a request: `<method>-<first 16 hex chars of sha256>.json`
+fn fixture_file_name(method: &str, request: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(request.to_string().as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    format!("{}-{}.json", method, &hash[..16])
+}
+
+/// Venue client decorator that records every call to a fixture directory
+///
+/// Recording failures are logged and never affect the wrapped call.
+pub struct RecordingVenueClient<C: VenueClient> {
+    inner: C,
+    dir: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl<C: VenueClient> RecordingVenueClient<C> {
+    /// Wrap a client, recording into `dir` (created if missing)
+    pub fn new(inner: C, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ClientError::Fixture(format!("Cannot create {}: {}", dir.display(), e)))?;
+
+        Ok(Self {
+            inner,
+            dir,
+            write_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Fixture directory being recorded into
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Append a result to the fixture file for this request
+    async fn record<T: Serialize>(&self, method: &str, request: Value, result: &Result<T>) {
+        let outcome = match result {
+            Ok(value) => match serde_json::to_value(value) {
+                Ok(value) => RecordedOutcome::Ok(value),
+                Err(e) => {
+                    tracing::warn!(method, error = %e, "Failed to serialize venue response");
+                    return;
+                }
+            },
+            Err(e) => RecordedOutcome::Err(e.into()),
+        };
+
+        let _guard = self.write_lock.lock().await;
+        let path = self.dir.join(fixture_file_name(method, &request));
+
+        let mut fixture = match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<Fixture>(&bytes) {
+                Ok(fixture) => fixture,
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Corrupt fixture file");
+                    return;
+                }
+            },
+            Err(_) => Fixture {
+                method: method.to_string(),
+                request,
+                responses: Vec::new(),
+            },
+        };
+
+        fixture.responses.push(RecordedResponse {
+            recorded_at: Utc::now(),
+            outcome,
+        });
+
+        let written = serde_json::to_vec_pretty(&fixture)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| std::fs::write(&path, bytes).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to write fixture");
+        }
+    }
+}
+
+#[async_trait]
+impl<C: VenueClient> VenueClient for RecordingVenueClient<C> {
+    async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>> {
+        let result = self.inner.discover_markets(limit, offset).await;
+        self.record(
+            "discover_markets",
+            json!({ "limit": limit, "offset": offset }),
+            &result,
+        )
+        .await;
+        result
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        let result = self.inner.get_quotes(outcomes).await;
+        self.record("get_quotes", json!({ "outcomes": outcomes }), &result)
+            .await;
+        result
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        let result = self.inner.get_rules(market_id).await;
+        self.record("get_rules", json!({ "market_id": market_id }), &result)
+            .await;
+        result
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        let result = self.inner.get_market_status(market_id).await;
+        self.record(
+            "get_market_status",
+            json!({ "market_id": market_id }),
+            &result,
+        )
+        .await;
+        result
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        let result = self.inner.get_outcomes(market_id).await;
+        self.record("get_outcomes", json!({ "market_id": market_id }), &result)
+            .await;
+        result
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        let result = self.inner.get_trades(market_id, limit).await;
+        self.record(
+            "get_trades",
+            json!({ "market_id": market_id, "limit": limit }),
+            &result,
+        )
+        .await;
+        result
+    }
+}
+
+/// Recorded responses for one request, with a replay cursor
+#[derive(Debug)]
+struct ReplayEntry {
+    responses: Vec<RecordedResponse>,
+    next: usize,
+}
+
+/// Venue client that serves responses from a recorded fixture directory
+///
+/// Requests without a fixture fail with `ClientError::Fixture`, except
+/// discovery pages past the recorded ones, which replay as empty so
+/// pagination terminates.
+pub struct ReplayVenueClient {
+    entries: Mutex<HashMap<String, ReplayEntry>>,
+    recorded_from: Option<DateTime<Utc>>,
+    shift: Duration,
+}
+
+impl ReplayVenueClient {
+    /// Load every fixture in `dir`
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let read_dir = std::fs::read_dir(dir)
+            .map_err(|e| ClientError::Fixture(format!("Cannot read {}: {}", dir.display(), e)))?;
+
+        let mut entries = HashMap::new();
+        let mut recorded_from: Option<DateTime<Utc>> = None;
+
+        for entry in read_dir {
+            let path = entry
+                .map_err(|e| ClientError::Fixture(e.to_string()))?
+                .path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let bytes = std::fs::read(&path).map_err(|e| {
+                ClientError::Fixture(format!("Cannot read {}: {}", path.display(), e))
+            })?;
+            let fixture: Fixture = serde_json::from_slice(&bytes).map_err(|e| {
+                ClientError::Fixture(format!("Invalid fixture {}: {}", path.display(), e))
+            })?;
+
+            for response in &fixture.responses {
+                recorded_from = Some(match recorded_from {
+                    Some(t) => t.min(response.recorded_at),
+                    None => response.recorded_at,
+                });
+            }
+
+            entries.insert(
+                fixture_file_name(&fixture.method, &fixture.request),
+                ReplayEntry {
+                    responses: fixture.responses,
+                    next: 0,
+                },
+            );
+        }
+
+        Ok(Self {
+            entries: Mutex::new(entries),
+            recorded_from,
+            shift: Duration::zero(),
+        })
+    }
+
+    /// Shift all replayed timestamps so the recording appears to have
+    /// started at `start`
+    ///
+    /// Keeps close times and quote ages realistic relative to the wall
+    /// clock regardless of when the fixtures were captured.
+    pub fn rebased_to(mut self, start: DateTime<Utc>) -> Self {
+        if let Some(recorded_from) = self.recorded_from {
+            self.shift = start - recorded_from;
+        }
+        self
+    }
+
+    /// Number of distinct recorded requests
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    /// Whether the fixture directory had no recordings
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Next recorded outcome for a request, if any
+    fn next_outcome(&self, method: &str, request: &Value) -> Option<RecordedOutcome> {
+        let mut entries = self.entries.lock().ok()?;
+        let entry = entries.get_mut(&fixture_file_name(method, request))?;
+        let response = entry
+            .responses
+            .get(entry.next)
+            .or_else(|| entry.responses.last())?;
+        entry.next = (entry.next + 1).min(entry.responses.len());
+        Some(response.outcome.clone())
+    }
+
+    /// Replay a request, deserializing the recorded value
+    fn replay<T: DeserializeOwned>(&self, method: &str, request: Value) -> Result<T> {
+        match self.next_outcome(method, &request) {
+            Some(RecordedOutcome::Ok(value)) => Ok(serde_json::from_value(value)?),
+            Some(RecordedOutcome::Err(e)) => Err(e.into()),
+            None => Err(ClientError::Fixture(format!(
+                "No recording for {} {}",
+                method, request
+            ))),
+        }
+    }
+
+    fn shift_time(&self, t: DateTime<Utc>) -> DateTime<Utc> {
+        t + self.shift
+    }
+}
+
+#[async_trait]
+impl VenueClient for ReplayVenueClient {
+    async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>> {
+        let request = json!({ "limit": limit, "offset": offset });
+        let markets: Vec<Market> = match self.replay("discover_markets", request) {
+            Err(ClientError::Fixture(_)) if offset > 0 => return Ok(Vec::new()),
+            other => other?,
+        };
+
+        Ok(markets
+            .into_iter()
+            .map(|mut m| {
+                m.open_time = m.open_time.map(|t| self.shift_time(t));
+                m.close_time = m.close_time.map(|t| self.shift_time(t));
+                m.resolved_time = m.resolved_time.map(|t| self.shift_time(t));
+                m
+            })
+            .collect())
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        let quotes: Vec<Quote> = self.replay("get_quotes", json!({ "outcomes": outcomes }))?;
+
+        Ok(quotes
+            .into_iter()
+            .map(|mut q| {
+                q.as_of = self.shift_time(q.as_of);
+                q
+            })
+            .collect())
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        let mut rule: RuleSnapshot = self.replay("get_rules", json!({ "market_id": market_id }))?;
+        rule.as_of = self.shift_time(rule.as_of);
+        Ok(rule)
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        self.replay("get_market_status", json!({ "market_id": market_id }))
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        self.replay("get_outcomes", json!({ "market_id": market_id }))
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        let request = json!({ "market_id": market_id, "limit": limit });
+        let trades: Vec<Trade> = self.replay("get_trades", request)?;
+
+        Ok(trades
+            .into_iter()
+            .map(|mut t| {
+                t.traded_at = self.shift_time(t.traded_at);
+                t
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Venue stub returning one market and failing status lookups
+    struct StubClient;
+
+    fn stub_market(now: DateTime<Utc>) -> Market {
+        Market {
+            market_id: "polymarket:0x1".to_string(),
+            venue: "polymarket".to_string(),
+            venue_market_id: "0x1".to_string(),
+            title: "Stub market".to_string(),
+            slug: None,
+            category: None,
+            status: MarketStatus::Active,
+            open_time: None,
+            close_time: Some(now + Duration::days(2)),
+            resolved_time: None,
+            url: None,
+            metadata: Default::default(),
+            outcomes: Vec::new(),
+        }
+    }
+
+    #[async_trait]
+    impl VenueClient for StubClient {
+        async fn discover_markets(&self, _limit: usize, offset: usize) -> Result<Vec<Market>> {
+            if offset > 0 {
+                return Ok(Vec::new());
+            }
+            Ok(vec![stub_market(Utc::now())])
+        }
+
+        async fn get_quotes(&self, _outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+            Err(ClientError::InvalidResponse(market_id.to_string()))
+        }
+
+        async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+            Err(ClientError::MarketNotFound(market_id.to_string()))
+        }
+
+        async fn get_outcomes(&self, _market_id: &str) -> Result<Vec<Outcome>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_trades(&self, _market_id: &str, _limit: usize) -> Result<Vec<Trade>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingVenueClient::new(StubClient, dir.path()).unwrap();
+
+        let recorded = recorder.discover_markets(10, 0).await.unwrap();
+        recorder.discover_markets(10, 0).await.unwrap();
+        assert!(recorder.get_market_status("polymarket:0x1").await.is_err());
+
+        let replay = ReplayVenueClient::open(dir.path()).unwrap();
+        assert_eq!(replay.len(), 2);
+
+        let replayed = replay.discover_markets(10, 0).await.unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].market_id, recorded[0].market_id);
+        assert_eq!(replayed[0].close_time, recorded[0].close_time);
+
+        // Unrecorded discovery pages end pagination
+        assert!(replay.discover_markets(10, 10).await.unwrap().is_empty());
+
+        assert!(matches!(
+            replay.get_market_status("polymarket:0x1").await,
+            Err(ClientError::MarketNotFound(_))
+        ));
+        assert!(matches!(
+            replay.get_outcomes("polymarket:0x2").await,
+            Err(ClientError::Fixture(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replay_rebases_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingVenueClient::new(StubClient, dir.path()).unwrap();
+        let recorded = recorder.discover_markets(10, 0).await.unwrap();
+
+        let start = Utc::now() + Duration::days(30);
+        let replay = ReplayVenueClient::open(dir.path())
+            .unwrap()
+            .rebased_to(start);
+        let replayed = replay.discover_markets(10, 0).await.unwrap();
+
+        let shift = replayed[0].close_time.unwrap() - recorded[0].close_time.unwrap();
+        assert!(shift >= Duration::days(29) && shift <= Duration::days(31));
+    }
+}
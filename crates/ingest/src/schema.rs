@@ -0,0 +1,616 @@
+//! Venue response schema-drift detection
+//!
+//! Response bodies are compared field by field against a recorded baseline
+//! per endpoint (`schemas/polymarket.json`). Fields the baseline does not
+//! know, required fields that disappear and fields whose JSON type changes
+//! are tracked as drift. Drift on a critical field puts its endpoint in
+//! degraded mode until a response without it arrives.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Mutex,
+};
+
+use pm_domain::{DriftKind, SchemaDrift, SharedClock, SystemClock};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Recorded Polymarket baseline
+const POLYMARKET_BASELINE: &str = include_str!("../schemas/polymarket.json");
+
+/// Field path used for the response body itself
+const ROOT_FIELD: &str = "$";
+
+/// JSON value type, as named in baselines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Bool,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Bool => "bool",
+            JsonType::Number => "number",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+}
+
+/// Baseline for one field
+#[derive(Debug, Clone, Deserialize)]
+struct FieldSpec {
+    /// Allowed JSON types
+    types: Vec<JsonType>,
+    /// Always present (and not null) in responses
+    #[serde(default)]
+    required: bool,
+    /// Ingest relies on the field; drift degrades the endpoint
+    #[serde(default)]
+    critical: bool,
+    /// For objects: children not listed are not reported as unexpected
+    #[serde(default)]
+    open: bool,
+}
+
+impl FieldSpec {
+    fn expected(&self) -> String {
+        self.types
+            .iter()
+            .map(JsonType::as_str)
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+}
+
+/// Baseline for one endpoint
+#[derive(Debug, Clone, Deserialize)]
+struct EndpointBaseline {
+    /// Body type: `array` of records or a single `object`
+    root: JsonType,
+    /// Fields by path; `[]` marks array elements
+    fields: BTreeMap<String, FieldSpec>,
+    /// Top-level fields accepted with any type and not inspected
+    #[serde(default)]
+    known: HashSet<String>,
+    /// Top-level fields not listed are not reported as unexpected
+    #[serde(default)]
+    open: bool,
+}
+
+/// Recorded response schemas for a venue
+#[derive(Debug, Clone, Deserialize)]
+pub struct Baseline {
+    venue: String,
+    endpoints: HashMap<String, EndpointBaseline>,
+}
+
+impl Baseline {
+    /// The recorded Polymarket baseline
+    pub fn polymarket() -> Self {
+        serde_json::from_str(POLYMARKET_BASELINE).expect("embedded baseline is valid")
+    }
+
+    /// Parse a baseline from JSON
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Drift found in a single response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    field: String,
+    kind: DriftKind,
+    expected: Option<String>,
+    observed: Option<String>,
+    critical: bool,
+}
+
+/// What a walk over one body saw
+#[derive(Default)]
+struct Observed {
+    findings: Vec<Finding>,
+    /// Paths seen with a non-null value
+    present: HashSet<String>,
+    /// Object paths walked
+    containers: HashSet<String>,
+}
+
+impl Observed {
+    fn push(&mut self, finding: Finding) {
+        if !self.findings.contains(&finding) {
+            self.findings.push(finding);
+        }
+    }
+}
+
+impl EndpointBaseline {
+    /// Compare one response body against the baseline
+    fn check(&self, body: &Value) -> Vec<Finding> {
+        let mut observed = Observed::default();
+
+        let root = JsonType::of(body);
+        if root != self.root {
+            observed.push(Finding {
+                field: ROOT_FIELD.to_string(),
+                kind: DriftKind::TypeChanged,
+                expected: Some(self.root.as_str().to_string()),
+                observed: Some(root.as_str().to_string()),
+                critical: true,
+            });
+            return observed.findings;
+        }
+
+        match body {
+            Value::Array(records) => {
+                for record in records {
+                    match record {
+                        Value::Object(map) => self.walk(map, "", self.open, &mut observed),
+                        other => observed.push(Finding {
+                            field: format!("{}[]", ROOT_FIELD),
+                            kind: DriftKind::TypeChanged,
+                            expected: Some(JsonType::Object.as_str().to_string()),
+                            observed: Some(JsonType::of(other).as_str().to_string()),
+                            critical: true,
+                        }),
+                    }
+                }
+            }
+            Value::Object(map) => self.walk(map, "", self.open, &mut observed),
+            _ => {}
+        }
+
+        // Required fields count as missing only when absent from every
+        // record that could have carried them
+        for (path, spec) in &self.fields {
+            if spec.required
+                && observed.containers.contains(parent_path(path))
+                && !observed.present.contains(path)
+            {
+                observed.push(Finding {
+                    field: path.clone(),
+                    kind: DriftKind::Missing,
+                    expected: Some(spec.expected()),
+                    observed: None,
+                    critical: spec.critical,
+                });
+            }
+        }
+
+        observed.findings
+    }
+
+    /// Walk an object's fields at `prefix`
+    fn walk(&self, map: &Map<String, Value>, prefix: &str, open: bool, observed: &mut Observed) {
+        observed.containers.insert(prefix.to_string());
+
+        for (key, value) in map {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+
+            match self.fields.get(&path) {
+                Some(spec) => self.check_field(&path, spec, value, observed),
+                None if open || (prefix.is_empty() && self.known.contains(key)) => {}
+                None => observed.push(Finding {
+                    field: path,
+                    kind: DriftKind::Unexpected,
+                    expected: None,
+                    observed: Some(JsonType::of(value).as_str().to_string()),
+                    critical: false,
+                }),
+            }
+        }
+    }
+
+    /// Check a baseline field's value and descend into it
+    fn check_field(&self, path: &str, spec: &FieldSpec, value: &Value, observed: &mut Observed) {
+        // A required field nulled out is treated as gone
+        if value.is_null() && spec.required {
+            return;
+        }
+        observed.present.insert(path.to_string());
+
+        let actual = JsonType::of(value);
+        if !spec.types.contains(&actual) {
+            observed.push(Finding {
+                field: path.to_string(),
+                kind: DriftKind::TypeChanged,
+                expected: Some(spec.expected()),
+                observed: Some(actual.as_str().to_string()),
+                critical: spec.critical,
+            });
+            return;
+        }
+
+        match value {
+            Value::Object(map) => self.walk(map, path, spec.open, observed),
+            Value::Array(items) => {
+                let item_path = format!("{}[]", path);
+                if let Some(item_spec) = self.fields.get(&item_path) {
+                    for item in items {
+                        self.check_field(&item_path, item_spec, item, observed);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Path of the object containing a field (`""` for top-level fields)
+fn parent_path(path: &str) -> &str {
+    path.rfind('.').map(|i| &path[..i]).unwrap_or("")
+}
+
+/// Tracked drift and how much of it has not been persisted yet
+#[derive(Debug, Clone)]
+struct Entry {
+    drift: SchemaDrift,
+    unflushed: i64,
+    dirty: bool,
+}
+
+/// Drift tracked per endpoint
+#[derive(Default)]
+struct TrackerState {
+    entries: HashMap<(String, String, DriftKind), Entry>,
+    /// Drift shown by the latest response from each endpoint
+    active: HashMap<String, HashSet<(String, DriftKind)>>,
+}
+
+/// Tracks schema drift of venue responses against a baseline
+pub struct SchemaTracker {
+    baseline: Baseline,
+    state: Mutex<TrackerState>,
+    clock: SharedClock,
+}
+
+impl SchemaTracker {
+    /// Track drift against a baseline
+    pub fn new(baseline: Baseline) -> Self {
+        Self {
+            baseline,
+            state: Mutex::new(TrackerState::default()),
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// Timestamp drift with `clock` instead of the wall clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Track drift against the recorded Polymarket baseline
+    pub fn polymarket() -> Self {
+        Self::new(Baseline::polymarket())
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, TrackerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Compare a response body from `endpoint` against the baseline
+    ///
+    /// Bodies that are not JSON and endpoints without a baseline are
+    /// ignored; parse failures are reported by the client itself.
+    pub fn observe(&self, endpoint: &str, body: &[u8]) {
+        let Some(baseline) = self.baseline.endpoints.get(endpoint) else {
+            return;
+        };
+        let Ok(value) = serde_json::from_slice::<Value>(body) else {
+            return;
+        };
+
+        let findings = baseline.check(&value);
+        let now = self.clock.now();
+        let was_degraded = self.is_degraded(endpoint);
+
+        let mut state = self.state();
+        let mut active = HashSet::new();
+
+        for finding in findings {
+            let key = (endpoint.to_string(), finding.field.clone(), finding.kind);
+            active.insert((finding.field.clone(), finding.kind));
+
+            let entry = state.entries.entry(key).or_insert_with(|| {
+                tracing::warn!(
+                    endpoint,
+                    field = %finding.field,
+                    kind = %finding.kind,
+                    critical = finding.critical,
+                    "Venue response schema drift"
+                );
+                Entry {
+                    drift: SchemaDrift {
+                        venue: self.baseline.venue.clone(),
+                        endpoint: endpoint.to_string(),
+                        field: finding.field.clone(),
+                        kind: finding.kind,
+                        expected: finding.expected.clone(),
+                        observed: None,
+                        critical: finding.critical,
+                        active: true,
+                        occurrences: 0,
+                        first_seen_at: now,
+                        last_seen_at: now,
+                    },
+                    unflushed: 0,
+                    dirty: true,
+                }
+            });
+
+            entry.drift.observed = finding.observed;
+            entry.drift.active = true;
+            entry.drift.occurrences += 1;
+            entry.drift.last_seen_at = now;
+            entry.unflushed += 1;
+            entry.dirty = true;
+        }
+
+        // Drift the latest response no longer shows is inactive
+        let previous = state.active.insert(endpoint.to_string(), active.clone());
+        for (field, kind) in previous.unwrap_or_default().difference(&active) {
+            if let Some(entry) =
+                state
+                    .entries
+                    .get_mut(&(endpoint.to_string(), field.clone(), *kind))
+            {
+                entry.drift.active = false;
+                entry.dirty = true;
+            }
+        }
+        drop(state);
+
+        match (was_degraded, self.is_degraded(endpoint)) {
+            (false, true) => tracing::error!(endpoint, "Critical schema drift, endpoint degraded"),
+            (true, false) => tracing::info!(endpoint, "Schema drift cleared, endpoint recovered"),
+            _ => {}
+        }
+    }
+
+    /// Whether the latest response from `endpoint` lost or retyped a
+    /// critical field
+    pub fn is_degraded(&self, endpoint: &str) -> bool {
+        self.state()
+            .entries
+            .values()
+            .any(|e| e.drift.endpoint == endpoint && e.drift.degrades())
+    }
+
+    /// Endpoints currently in degraded mode
+    pub fn degraded_endpoints(&self) -> Vec<String> {
+        let mut endpoints: Vec<String> = self
+            .state()
+            .entries
+            .values()
+            .filter(|e| e.drift.degrades())
+            .map(|e| e.drift.endpoint.clone())
+            .collect();
+        endpoints.sort();
+        endpoints.dedup();
+        endpoints
+    }
+
+    /// All drift tracked since startup
+    pub fn snapshot(&self) -> Vec<SchemaDrift> {
+        self.state()
+            .entries
+            .values()
+            .map(|e| e.drift.clone())
+            .collect()
+    }
+
+    /// Drift changed since the last call, with `occurrences` counting only
+    /// observations since then
+    pub fn take_updates(&self) -> Vec<SchemaDrift> {
+        self.state()
+            .entries
+            .values_mut()
+            .filter(|e| e.dirty)
+            .map(|e| {
+                let mut drift = e.drift.clone();
+                drift.occurrences = e.unflushed;
+                e.unflushed = 0;
+                e.dirty = false;
+                drift
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> SchemaTracker {
+        SchemaTracker::polymarket()
+    }
+
+    fn market(extra: Value) -> Value {
+        let mut market = serde_json::json!({
+            "conditionId": "0xabc",
+            "question": "Will it rain?",
+            "slug": "will-it-rain",
+            "closed": false,
+            "endDate": "2026-03-01T00:00:00Z",
+            "events": [{"id": 7, "title": "Weather", "ticker": "rain"}]
+        });
+        if let (Value::Object(fields), Value::Object(extra)) = (&mut market, extra) {
+            fields.extend(extra);
+        }
+        market
+    }
+
+    fn body(value: Value) -> Vec<u8> {
+        serde_json::to_vec(&value).unwrap()
+    }
+
+    fn kinds(tracker: &SchemaTracker) -> Vec<(String, DriftKind)> {
+        let mut kinds: Vec<_> = tracker
+            .snapshot()
+            .into_iter()
+            .filter(|d| d.active)
+            .map(|d| (d.field, d.kind))
+            .collect();
+        kinds.sort_by(|a, b| a.0.cmp(&b.0));
+        kinds
+    }
+
+    #[test]
+    fn test_baseline_parses() {
+        let baseline = Baseline::polymarket();
+        assert_eq!(baseline.venue, "polymarket");
+        assert!(baseline.endpoints["gamma_markets"].fields["endDate"].critical);
+    }
+
+    #[test]
+    fn test_matching_body_has_no_drift() {
+        let tracker = tracker();
+        tracker.observe(
+            "gamma_markets",
+            &body(serde_json::json!([market(
+                serde_json::json!({"volumeNum": 12})
+            )])),
+        );
+        assert!(tracker.snapshot().is_empty());
+        assert!(!tracker.is_degraded("gamma_markets"));
+    }
+
+    #[test]
+    fn test_unexpected_and_type_changed_fields() {
+        let tracker = tracker();
+        tracker.observe(
+            "gamma_markets",
+            &body(serde_json::json!([market(
+                serde_json::json!({"closed": "false", "brandNew": 1})
+            )])),
+        );
+
+        assert_eq!(
+            kinds(&tracker),
+            vec![
+                ("brandNew".to_string(), DriftKind::Unexpected),
+                ("closed".to_string(), DriftKind::TypeChanged),
+            ]
+        );
+        // Retyping a critical field degrades the endpoint
+        assert!(tracker.is_degraded("gamma_markets"));
+    }
+
+    #[test]
+    fn test_missing_only_when_absent_from_every_record() {
+        let tracker = tracker();
+        let mut without_end = market(serde_json::json!({}));
+        without_end.as_object_mut().unwrap().remove("endDate");
+
+        tracker.observe(
+            "gamma_markets",
+            &body(serde_json::json!([
+                without_end.clone(),
+                market(serde_json::json!({}))
+            ])),
+        );
+        assert!(tracker.snapshot().is_empty());
+
+        tracker.observe("gamma_markets", &body(serde_json::json!([without_end])));
+        assert_eq!(
+            kinds(&tracker),
+            vec![("endDate".to_string(), DriftKind::Missing)]
+        );
+        assert!(tracker.is_degraded("gamma_markets"));
+        assert_eq!(tracker.degraded_endpoints(), vec!["gamma_markets"]);
+
+        // Recovery once the field is back
+        tracker.observe(
+            "gamma_markets",
+            &body(serde_json::json!([market(serde_json::json!({}))])),
+        );
+        assert!(!tracker.is_degraded("gamma_markets"));
+        assert!(kinds(&tracker).is_empty());
+        assert_eq!(tracker.snapshot()[0].occurrences, 1);
+    }
+
+    #[test]
+    fn test_nested_fields_and_nulls() {
+        let tracker = tracker();
+        tracker.observe(
+            "clob_book",
+            &body(serde_json::json!({
+                "bids": [{"price": null, "size": "1"}],
+                "asks": [{"price": "0.5", "size": "1", "side": "sell"}]
+            })),
+        );
+
+        assert_eq!(
+            kinds(&tracker),
+            vec![
+                ("asks[].side".to_string(), DriftKind::Unexpected),
+                ("bids[].price".to_string(), DriftKind::Missing),
+            ]
+        );
+        assert!(tracker.is_degraded("clob_book"));
+        assert!(!tracker.is_degraded("gamma_markets"));
+    }
+
+    #[test]
+    fn test_root_type_change_and_ignored_bodies() {
+        let tracker = tracker();
+        tracker.observe("gamma_markets", br#"{"error": "moved"}"#);
+        tracker.observe("gamma_markets", b"not json");
+        tracker.observe("unknown_endpoint", b"{}");
+
+        assert_eq!(
+            kinds(&tracker),
+            vec![("$".to_string(), DriftKind::TypeChanged)]
+        );
+        assert!(tracker.is_degraded("gamma_markets"));
+    }
+
+    #[test]
+    fn test_take_updates_counts_since_last_flush() {
+        let tracker = tracker();
+        let drifted = body(serde_json::json!([market(
+            serde_json::json!({"brandNew": 1})
+        )]));
+
+        tracker.observe("gamma_markets", &drifted);
+        tracker.observe("gamma_markets", &drifted);
+        let updates = tracker.take_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].occurrences, 2);
+        assert!(tracker.take_updates().is_empty());
+
+        tracker.observe(
+            "gamma_markets",
+            &body(serde_json::json!([market(serde_json::json!({}))])),
+        );
+        let updates = tracker.take_updates();
+        assert_eq!(updates[0].occurrences, 0);
+        assert!(!updates[0].active);
+    }
+}
@@ -0,0 +1,231 @@
+//! Graceful shutdown of the ingest persistence stage
+//!
+//! On shutdown the orchestrator stops every producer first, then lets each
+//! persistence task drain what is still queued in its channel into the
+//! database. A deadline bounds the drain; items still queued when it passes,
+//! or whose write fails or runs past it, are counted as dropped.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::mpsc,
+    time::{timeout_at, Instant},
+};
+
+/// Shutdown behaviour for the persistence stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownConfig {
+    /// Time allowed for draining queued items once producers have stopped
+    /// (milliseconds)
+    pub drain_timeout_ms: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout_ms: 10_000,
+        }
+    }
+}
+
+/// Drain outcome summed over every channel
+#[derive(Debug, Default)]
+pub struct DrainTotals {
+    persisted: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl DrainTotals {
+    /// Items written to the database while draining
+    pub fn persisted(&self) -> u64 {
+        self.persisted.load(Ordering::Relaxed)
+    }
+
+    /// Items left queued or failed to write while draining
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Shared by the persistence tasks to drain their channels on shutdown
+#[derive(Debug, Clone)]
+pub struct ShutdownDrain {
+    timeout: Duration,
+    totals: Arc<DrainTotals>,
+}
+
+impl ShutdownDrain {
+    /// Create a drain allowing `config.drain_timeout_ms` per channel
+    pub fn new(config: &ShutdownConfig) -> Self {
+        Self {
+            timeout: Duration::from_millis(config.drain_timeout_ms),
+            totals: Arc::new(DrainTotals::default()),
+        }
+    }
+
+    /// Totals over every channel drained so far
+    pub fn totals(&self) -> &DrainTotals {
+        &self.totals
+    }
+
+    /// Close `rx` and start draining it; `count` gives the number of items
+    /// in one message
+    pub fn start<'a, T>(
+        &self,
+        channel: &'static str,
+        rx: &'a mut mpsc::Receiver<T>,
+        count: fn(&T) -> usize,
+    ) -> Drain<'a, T> {
+        rx.close();
+        Drain {
+            channel,
+            rx,
+            count,
+            deadline: Instant::now() + self.timeout,
+            persisted: 0,
+            dropped: 0,
+            totals: Arc::clone(&self.totals),
+        }
+    }
+}
+
+/// Drain of one closed channel, bounded by a deadline
+pub struct Drain<'a, T> {
+    channel: &'static str,
+    rx: &'a mut mpsc::Receiver<T>,
+    count: fn(&T) -> usize,
+    deadline: Instant,
+    persisted: u64,
+    dropped: u64,
+    totals: Arc<DrainTotals>,
+}
+
+impl<T> Drain<'_, T> {
+    /// Next queued message, or `None` once the channel is empty or the
+    /// deadline has passed
+    pub async fn next(&mut self) -> Option<T> {
+        // `timeout_at` still polls once after the deadline
+        if Instant::now() >= self.deadline {
+            return None;
+        }
+        timeout_at(self.deadline, self.rx.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Whether nothing is left queued
+    pub fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+
+    /// Number of items in `message`
+    pub fn count(&self, message: &T) -> usize {
+        (self.count)(message)
+    }
+
+    /// Run a write of `items` items, which reports whether it succeeded,
+    /// and count them as persisted or dropped
+    pub async fn persist<F>(&mut self, items: usize, write: F)
+    where
+        F: Future<Output = bool>,
+    {
+        match timeout_at(self.deadline, write).await {
+            Ok(true) => self.persisted += items as u64,
+            _ => self.dropped += items as u64,
+        }
+    }
+
+    /// Count what is still queued as dropped, log the outcome, and add it
+    /// to the totals
+    pub fn finish(self) -> (u64, u64) {
+        let mut dropped = self.dropped;
+        while let Ok(message) = self.rx.try_recv() {
+            dropped += (self.count)(&message) as u64;
+        }
+
+        self.totals
+            .persisted
+            .fetch_add(self.persisted, Ordering::Relaxed);
+        self.totals.dropped.fetch_add(dropped, Ordering::Relaxed);
+
+        if dropped > 0 {
+            tracing::warn!(
+                channel = self.channel,
+                persisted = self.persisted,
+                dropped,
+                "Drained channel with items dropped"
+            );
+        } else {
+            tracing::info!(
+                channel = self.channel,
+                persisted = self.persisted,
+                dropped,
+                "Drained channel"
+            );
+        }
+
+        (self.persisted, dropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_persists_queued_items() {
+        let drain = ShutdownDrain::new(&ShutdownConfig::default());
+        let (tx, mut rx) = mpsc::channel::<Vec<u32>>(8);
+        tx.send(vec![1, 2]).await.unwrap();
+        tx.send(vec![3]).await.unwrap();
+
+        let mut channel = drain.start("numbers", &mut rx, Vec::len);
+        assert!(tx.send(vec![4]).await.is_err(), "channel should be closed");
+
+        while let Some(batch) = channel.next().await {
+            let items = channel.count(&batch);
+            channel.persist(items, async { true }).await;
+        }
+
+        assert_eq!(channel.finish(), (3, 0));
+        assert_eq!(drain.totals().persisted(), 3);
+        assert_eq!(drain.totals().dropped(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_counts_failed_and_late_items_as_dropped() {
+        let drain = ShutdownDrain::new(&ShutdownConfig {
+            drain_timeout_ms: 50,
+        });
+        let (tx, mut rx) = mpsc::channel::<u32>(8);
+        for i in 0..4 {
+            tx.send(i).await.unwrap();
+        }
+
+        let mut channel = drain.start("numbers", &mut rx, |_| 1);
+
+        // A failed write, then one that outlives the deadline
+        channel.next().await.unwrap();
+        channel.persist(1, async { false }).await;
+        channel.next().await.unwrap();
+        channel
+            .persist(1, async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                true
+            })
+            .await;
+
+        assert!(channel.next().await.is_none());
+        assert_eq!(channel.finish(), (0, 4));
+        assert_eq!(drain.totals().dropped(), 4);
+    }
+}
@@ -0,0 +1,383 @@
+//! Restart supervision for long-running ingest tasks
+//!
+//! Each task is registered with a factory that builds a fresh run of it.
+//! When a run returns or panics before shutdown, the supervisor starts a new
+//! one after an exponential backoff; a restart still waiting out its backoff
+//! when shutdown begins starts at once, so the task can run its own shutdown
+//! (such as draining its channel). A task that exits more than
+//! `max_restarts` times within `restart_window_sec` is treated as broken:
+//! every task is cancelled and `run` returns an error so the process can
+//! exit and be restarted by its service manager.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use prometheus::IntCounterVec;
+use serde::{Deserialize, Serialize};
+use tokio::task::{Id, JoinSet};
+use tokio_util::sync::CancellationToken;
+
+/// Error type for supervision
+#[derive(Debug, thiserror::Error)]
+pub enum SupervisorError {
+    #[error("Task {task} exited {restarts} times within {window_sec}s")]
+    RestartLimit {
+        task: String,
+        restarts: usize,
+        window_sec: u64,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, SupervisorError>;
+
+/// Restart policy for supervised tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorConfig {
+    /// Delay before the first restart (milliseconds)
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound on the restart delay (milliseconds)
+    pub max_backoff_ms: u64,
+
+    /// Restarts of one task allowed within the window before giving up
+    pub max_restarts: usize,
+
+    /// Window over which restarts are counted (seconds)
+    pub restart_window_sec: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            max_restarts: 5,
+            restart_window_sec: 300,
+        }
+    }
+}
+
+type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type TaskFactory = Box<dyn Fn() -> TaskFuture + Send + Sync>;
+
+/// A registered task and its recent restarts
+struct Supervised {
+    name: String,
+    factory: TaskFactory,
+    restarts: VecDeque<Instant>,
+}
+
+/// Runs tasks, restarting them with backoff when they exit early
+pub struct Supervisor {
+    config: SupervisorConfig,
+    cancellation: CancellationToken,
+    restarts_total: Option<IntCounterVec>,
+    tasks: Vec<Supervised>,
+}
+
+impl Supervisor {
+    /// Create a supervisor that stops restarting once `cancellation` fires
+    pub fn new(config: SupervisorConfig, cancellation: CancellationToken) -> Self {
+        Self {
+            config,
+            cancellation,
+            restarts_total: None,
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Count restarts in `counter`, labelled by task name
+    pub fn with_restart_counter(mut self, counter: IntCounterVec) -> Self {
+        self.restarts_total = Some(counter);
+        self
+    }
+
+    /// Register a task; `factory` is called for every (re)start
+    pub fn add<F, Fut>(&mut self, name: &str, factory: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.push(Supervised {
+            name: name.to_string(),
+            factory: Box::new(move || Box::pin(factory())),
+            restarts: VecDeque::new(),
+        });
+    }
+
+    /// Run every task until cancellation, then wait for them to finish
+    ///
+    /// Returns an error, after cancelling all tasks, if one exceeds its
+    /// restart limit.
+    pub async fn run(mut self) -> Result<()> {
+        let mut set = JoinSet::new();
+        let mut running: HashMap<Id, usize> = HashMap::new();
+
+        for (idx, task) in self.tasks.iter().enumerate() {
+            let handle = set.spawn((task.factory)());
+            running.insert(handle.id(), idx);
+        }
+
+        let mut failure = None;
+
+        loop {
+            let joined = tokio::select! {
+                joined = set.join_next_with_id() => joined,
+                _ = self.cancellation.cancelled() => break,
+            };
+            let Some(joined) = joined else {
+                break;
+            };
+
+            let id = match &joined {
+                Ok((id, ())) => *id,
+                Err(e) => e.id(),
+            };
+            let Some(idx) = running.remove(&id) else {
+                continue;
+            };
+
+            if self.cancellation.is_cancelled() {
+                continue;
+            }
+
+            let task = &mut self.tasks[idx];
+            match joined {
+                Ok(_) => tracing::error!(task = %task.name, "Task exited unexpectedly"),
+                Err(e) => tracing::error!(task = %task.name, error = %e, "Task panicked"),
+            }
+
+            let now = Instant::now();
+            let window = Duration::from_secs(self.config.restart_window_sec);
+            while task
+                .restarts
+                .front()
+                .is_some_and(|t| now.duration_since(*t) > window)
+            {
+                task.restarts.pop_front();
+            }
+
+            if task.restarts.len() >= self.config.max_restarts {
+                tracing::error!(
+                    task = %task.name,
+                    restarts = task.restarts.len(),
+                    "Task restart limit reached, shutting down"
+                );
+                failure = Some(SupervisorError::RestartLimit {
+                    task: task.name.clone(),
+                    restarts: task.restarts.len(),
+                    window_sec: self.config.restart_window_sec,
+                });
+                self.cancellation.cancel();
+                break;
+            }
+
+            task.restarts.push_back(now);
+            if let Some(counter) = &self.restarts_total {
+                counter.with_label_values(&[task.name.as_str()]).inc();
+            }
+
+            let delay = backoff(&self.config, task.restarts.len());
+            tracing::warn!(
+                task = %task.name,
+                attempt = task.restarts.len(),
+                delay_ms = delay.as_millis() as u64,
+                "Restarting task"
+            );
+
+            // The delay runs inside the new task so other tasks stay
+            // supervised meanwhile; shutdown cuts it short
+            let run = (task.factory)();
+            let cancellation = self.cancellation.clone();
+            let handle = set.spawn(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = cancellation.cancelled() => {}
+                }
+                run.await
+            });
+            running.insert(handle.id(), idx);
+        }
+
+        // Tasks watch the same token and return once it fires
+        while let Some(joined) = set.join_next().await {
+            if let Err(e) = joined {
+                tracing::warn!(error = %e, "Task failed during shutdown");
+            }
+        }
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Delay before the `attempt`-th restart within the window (1-based)
+fn backoff(config: &SupervisorConfig, attempt: usize) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16) as u32;
+    let delay = config.initial_backoff_ms.saturating_mul(1 << exponent);
+    Duration::from_millis(delay.min(config.max_backoff_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use prometheus::{opts, IntCounterVec};
+    use tokio::sync::{mpsc, Mutex};
+
+    use super::*;
+    use crate::shutdown::{ShutdownConfig, ShutdownDrain};
+
+    fn fast() -> SupervisorConfig {
+        SupervisorConfig {
+            initial_backoff_ms: 10,
+            max_backoff_ms: 20,
+            max_restarts: 3,
+            restart_window_sec: 60,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = SupervisorConfig::default();
+        assert_eq!(backoff(&config, 1), Duration::from_millis(500));
+        assert_eq!(backoff(&config, 2), Duration::from_millis(1000));
+        assert_eq!(backoff(&config, 3), Duration::from_millis(2000));
+        assert_eq!(backoff(&config, 50), Duration::from_millis(30_000));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_is_restarted() {
+        let cancellation = CancellationToken::new();
+        let counter =
+            IntCounterVec::new(opts!("test_task_restarts_total", "restarts"), &["task"]).unwrap();
+        let mut supervisor =
+            Supervisor::new(fast(), cancellation.clone()).with_restart_counter(counter.clone());
+
+        // Panics on its first run, then runs until cancelled
+        let starts = Arc::new(AtomicUsize::new(0));
+        supervisor.add("flaky", {
+            let starts = Arc::clone(&starts);
+            let cancellation = cancellation.clone();
+            move || {
+                let run = starts.fetch_add(1, Ordering::SeqCst);
+                let cancellation = cancellation.clone();
+                async move {
+                    if run == 0 {
+                        panic!("first run fails");
+                    }
+                    cancellation.cancelled().await;
+                }
+            }
+        });
+
+        let handle = tokio::spawn(supervisor.run());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        cancellation.cancel();
+
+        assert!(handle.await.unwrap().is_ok());
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        assert_eq!(counter.with_label_values(&["flaky"]).get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restart_limit_escalates() {
+        let cancellation = CancellationToken::new();
+        let mut supervisor = Supervisor::new(fast(), cancellation.clone());
+
+        supervisor.add("broken", || async {});
+
+        let other_stopped = Arc::new(AtomicUsize::new(0));
+        supervisor.add("healthy", {
+            let cancellation = cancellation.clone();
+            let other_stopped = Arc::clone(&other_stopped);
+            move || {
+                let cancellation = cancellation.clone();
+                let other_stopped = Arc::clone(&other_stopped);
+                async move {
+                    cancellation.cancelled().await;
+                    other_stopped.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        let result = tokio::time::timeout(Duration::from_secs(5), supervisor.run())
+            .await
+            .unwrap();
+        match result {
+            Err(SupervisorError::RestartLimit { task, restarts, .. }) => {
+                assert_eq!(task, "broken");
+                assert_eq!(restarts, 3);
+            }
+            other => panic!("expected restart limit, got {:?}", other),
+        }
+        assert!(cancellation.is_cancelled());
+        assert_eq!(other_stopped.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_during_backoff_still_drains() {
+        let cancellation = CancellationToken::new();
+        let config = SupervisorConfig {
+            initial_backoff_ms: 60_000,
+            ..fast()
+        };
+        let mut supervisor = Supervisor::new(config, cancellation.clone());
+
+        let (tx, rx) = mpsc::channel::<u32>(8);
+        let rx = Arc::new(Mutex::new(rx));
+        let drain = ShutdownDrain::new(&ShutdownConfig::default());
+
+        // Panics on its first run, leaving three items queued; the restart
+        // drains them on shutdown
+        let starts = Arc::new(AtomicUsize::new(0));
+        supervisor.add("persistence", {
+            let starts = Arc::clone(&starts);
+            let cancellation = cancellation.clone();
+            let drain = drain.clone();
+            move || {
+                let run = starts.fetch_add(1, Ordering::SeqCst);
+                let cancellation = cancellation.clone();
+                let drain = drain.clone();
+                let rx = Arc::clone(&rx);
+                async move {
+                    if run == 0 {
+                        panic!("first run fails");
+                    }
+                    cancellation.cancelled().await;
+                    let mut rx = rx.lock().await;
+                    let mut channel = drain.start("numbers", &mut rx, |_| 1);
+                    while channel.next().await.is_some() {
+                        channel.persist(1, async { true }).await;
+                    }
+                    channel.finish();
+                }
+            }
+        });
+        for i in 0..3 {
+            tx.send(i).await.unwrap();
+        }
+
+        let handle = tokio::spawn(supervisor.run());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cancellation.cancel();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(result.is_ok());
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        assert_eq!(drain.totals().persisted(), 3);
+        assert_eq!(drain.totals().dropped(), 0);
+    }
+}
@@ -0,0 +1,150 @@
+//! Ingest resilience under injected venue faults
+
+mod common;
+
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use common::{fast_config, replay, run_until};
+use pm_api::{handlers, ApiConfig, AppState, Metrics};
+use pm_ingest::{
+    chaos::MethodChaos, ChaosConfig, ChaosVenueClient, IngestConfig, IngestOrchestrator,
+};
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::{markets, quotes};
+use sqlx::PgPool;
+
+const MARKET_IDS: [&str; 2] = ["polymarket:0xa1", "polymarket:0xb2"];
+
+fn market_ids() -> Vec<String> {
+    MARKET_IDS.iter().map(|id| id.to_string()).collect()
+}
+
+/// Every method fails, slowly or outright
+fn all_failing() -> ChaosConfig {
+    let method = MethodChaos {
+        max_latency_ms: 100,
+        timeout_rate: 0.3,
+        timeout_ms: 500,
+        error_rate: 0.4,
+        truncated_json_rate: 0.3,
+        ..MethodChaos::default()
+    };
+
+    ChaosConfig {
+        enabled: true,
+        seed: Some(42),
+        discover_markets: method.clone(),
+        get_quotes: method.clone(),
+        get_rules: method.clone(),
+        get_market_status: method.clone(),
+        get_outcomes: method.clone(),
+        get_trades: method,
+    }
+}
+
+/// Ingest the fixtures cleanly until both markets have quotes
+async fn ingest_cleanly(pool: &PgPool) {
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), fast_config());
+    let ready = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                quotes::get_quotes_latest_batch(&pool, &market_ids())
+                    .await
+                    .map(|q| q.len() == MARKET_IDS.len())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(ready, "clean ingest did not complete");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn bounded_channels_do_not_block_shutdown_under_chaos(pool: PgPool) {
+    // Discovery only suffers latency and empty pages so markets keep flowing
+    // into single-slot channels while everything downstream misbehaves
+    let chaos = ChaosConfig {
+        discover_markets: MethodChaos {
+            max_latency_ms: 200,
+            empty_rate: 0.3,
+            ..MethodChaos::default()
+        },
+        ..all_failing()
+    };
+    let config = IngestConfig {
+        max_channel_size: 1,
+        ..fast_config()
+    };
+
+    let client = ChaosVenueClient::new(replay("two_markets"), chaos);
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), config);
+
+    let discovered = run_until(
+        orchestrator,
+        Duration::from_secs(10),
+        Duration::from_secs(3),
+        || {
+            let pool = pool.clone();
+            async move {
+                markets::list_markets(&pool, None, None, 10, 0)
+                    .await
+                    .map(|m| m.len() == MARKET_IDS.len())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(discovered, "markets were not persisted under chaos");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn scoring_and_api_serve_last_known_data_during_venue_faults(pool: PgPool) {
+    ingest_cleanly(&pool).await;
+
+    let scoring = ScoringOrchestrator::new(pool.clone(), ScoringConfig::default());
+    scoring.run_scoring_cycle().await.unwrap();
+
+    let before = quotes::get_quotes_latest_batch(&pool, &market_ids())
+        .await
+        .unwrap();
+
+    // The venue misbehaves for a few cycles
+    let client = ChaosVenueClient::new(replay("two_markets"), all_failing());
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), fast_config());
+    run_until(
+        orchestrator,
+        Duration::from_secs(3),
+        Duration::from_secs(3),
+        || async { false },
+    )
+    .await;
+
+    // Last good quotes are untouched and scoring still succeeds on them
+    let after = quotes::get_quotes_latest_batch(&pool, &market_ids())
+        .await
+        .unwrap();
+    assert_eq!(after.len(), before.len());
+    for (a, b) in after.iter().zip(&before) {
+        assert_eq!(a.as_of, b.as_of);
+        assert_eq!(a.no_bid, b.no_bid);
+    }
+    scoring.run_scoring_cycle().await.unwrap();
+
+    // The API keeps serving opportunities and market detail
+    let state = AppState::new(pool.clone(), ApiConfig::default(), Metrics::default());
+    let query = serde_json::from_value(serde_json::json!({})).unwrap();
+    let opportunities = handlers::opportunities_handler(State(state.clone()), Query(query))
+        .await
+        .unwrap();
+    assert!(opportunities.0.total > 0);
+
+    let detail = handlers::market_handler(State(state), Path(MARKET_IDS[0].to_string()))
+        .await
+        .unwrap();
+    assert!(detail.0.quote.is_some());
+}
@@ -0,0 +1,84 @@
+//! Helpers shared by ingest integration tests
+
+#![allow(dead_code)]
+
+use std::{future::Future, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use pm_ingest::{IngestConfig, IngestOrchestrator, ReplayVenueClient, VenueClient};
+use pm_storage::{quotes, rules};
+use sqlx::PgPool;
+
+/// Path to a recorded fixture directory
+pub fn fixture_dir(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Replay client for a fixture directory, rebased to now
+pub fn replay(name: &str) -> ReplayVenueClient {
+    ReplayVenueClient::open(fixture_dir(name))
+        .unwrap()
+        .rebased_to(Utc::now())
+}
+
+/// Cadences short enough for several cycles within a test
+pub fn fast_config() -> IngestConfig {
+    IngestConfig {
+        quotes_cadence_sec: 1,
+        discovery_cadence_sec: 1,
+        rules_refresh_cadence_sec: 1,
+        reconcile_cadence_sec: 1,
+        ..IngestConfig::default()
+    }
+}
+
+/// Run the orchestrator until `done` holds or `timeout` passes, then shut
+/// it down
+///
+/// Panics if shutdown itself takes longer than `shutdown_within`.
+pub async fn run_until<C, F, Fut>(
+    orchestrator: IngestOrchestrator<C>,
+    timeout: Duration,
+    shutdown_within: Duration,
+    done: F,
+) -> bool
+where
+    C: VenueClient + 'static,
+    F: Fn() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let cancel = orchestrator.cancellation_token();
+    let handle = tokio::spawn(async move { orchestrator.run().await });
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut finished = false;
+    while tokio::time::Instant::now() < deadline {
+        if done().await {
+            finished = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    cancel.cancel();
+    tokio::time::timeout(shutdown_within, handle)
+        .await
+        .expect("orchestrator did not shut down in time")
+        .unwrap()
+        .unwrap();
+    finished
+}
+
+/// Whether a quote and a rule snapshot are stored for every market in
+/// `market_ids`, so that scoring can consider them
+pub async fn scoring_inputs_stored(pool: &PgPool, market_ids: &[String]) -> bool {
+    let all = |n: usize| n == market_ids.len();
+    quotes::get_quotes_latest_batch(pool, market_ids)
+        .await
+        .is_ok_and(|q| all(q.len()))
+        && rules::get_rules_batch(pool, market_ids)
+            .await
+            .is_ok_and(|r| all(r.len()))
+}
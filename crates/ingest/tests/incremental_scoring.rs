@@ -0,0 +1,60 @@
+//! Scoring markets as ingest reports them changed, between full sweeps
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{fast_config, replay, run_until};
+use pm_ingest::IngestOrchestrator;
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::scores;
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn changed_markets_are_scored_before_the_next_sweep(pool: PgPool) {
+    // The first sweep runs before anything is ingested; the next is an
+    // hour away
+    let mut config = ScoringConfig {
+        cadence_sec: 3600,
+        ..ScoringConfig::default()
+    };
+    config.leader.enabled = false;
+    config.incremental.debounce_ms = 200;
+    let scoring = Arc::new(ScoringOrchestrator::new(pool.clone(), config));
+    let scoring_run = tokio::spawn({
+        let scoring = Arc::clone(&scoring);
+        async move { scoring.run().await }
+    });
+
+    let ingest = IngestOrchestrator::new(replay("two_markets"), pool.clone(), fast_config());
+    let scored = run_until(
+        ingest,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                scores::get_score(&pool, "polymarket:0xa1").await.is_ok()
+                    && scores::get_score(&pool, "polymarket:0xb2").await.is_ok()
+            }
+        },
+    )
+    .await;
+    assert!(scored, "changed markets were not re-scored");
+
+    let cycles = &scoring.metrics().loops.cycles_total;
+    assert_eq!(cycles.with_label_values(&["scoring", "ok"]).get(), 1);
+    assert!(
+        cycles
+            .with_label_values(&["incremental_scoring", "ok"])
+            .get()
+            > 0
+    );
+
+    scoring.cancellation_token().cancel();
+    tokio::time::timeout(Duration::from_secs(5), scoring_run)
+        .await
+        .expect("scoring did not shut down")
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,270 @@
+//! Ingest checkpoints and resumed schedules
+
+mod common;
+
+use std::{collections::HashSet, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{fast_config, replay, run_until};
+use pm_domain::{Market, MarketStatus, Outcome, PriceSource, Quote, RuleSnapshot, Trade};
+use pm_ingest::{
+    client::{ClientError, Result},
+    IngestConfig, IngestOrchestrator, VenueClient,
+};
+use pm_storage::{ingest_state, markets, quotes};
+use sqlx::PgPool;
+
+/// Venue that quotes any outcome with a token id and knows nothing else
+struct QuotingClient;
+
+#[async_trait]
+impl VenueClient for QuotingClient {
+    async fn discover_markets(&self, _limit: usize, _offset: usize) -> Result<Vec<Market>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        let quoted: HashSet<&str> = outcomes
+            .iter()
+            .filter(|o| o.token_id.is_some())
+            .map(|o| o.market_id.as_str())
+            .collect();
+        Ok(quoted
+            .into_iter()
+            .map(|market_id| Quote {
+                market_id: market_id.to_string(),
+                as_of: Utc::now(),
+                yes_bid: Some(0.03),
+                yes_ask: Some(0.05),
+                no_bid: Some(0.95),
+                no_ask: Some(0.97),
+                spread_yes: Some(0.02),
+                spread_no: Some(0.02),
+                mid_yes: Some(0.04),
+                mid_no: Some(0.96),
+                yes_source: PriceSource::Observed,
+                no_source: PriceSource::Observed,
+                quote_source: "polymarket".to_string(),
+            })
+            .collect())
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        Err(ClientError::MarketNotFound(market_id.to_string()))
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        Err(ClientError::MarketNotFound(market_id.to_string()))
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        Err(ClientError::MarketNotFound(market_id.to_string()))
+    }
+
+    async fn get_trades(&self, _market_id: &str, _limit: usize) -> Result<Vec<Trade>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Active market closing at `close_time`, listing outcomes with
+/// `token_ids` if given
+fn market(id: &str, close_time: DateTime<Utc>, token_ids: Option<[&str; 2]>) -> Market {
+    let market_id = format!("polymarket:{id}");
+    let outcomes = token_ids
+        .map(|tokens| {
+            ["YES", "NO"]
+                .into_iter()
+                .zip(tokens)
+                .map(|(outcome, token)| Outcome {
+                    market_id: market_id.clone(),
+                    outcome: outcome.to_string(),
+                    token_id: Some(token.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Market {
+        market_id,
+        venue: "polymarket".to_string(),
+        venue_market_id: id.to_string(),
+        title: format!("Market {id}"),
+        slug: None,
+        category: None,
+        status: MarketStatus::Active,
+        open_time: None,
+        close_time: Some(close_time),
+        resolved_time: None,
+        url: None,
+        metadata: Default::default(),
+        outcomes,
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replay_records_task_and_market_checkpoints(pool: PgPool) {
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), fast_config());
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                let ids = ["polymarket:0xa1".to_string()];
+                ingest_state::get_market_states(&pool, &ids)
+                    .await
+                    .ok()
+                    .and_then(|s| s.into_iter().next())
+                    .is_some_and(|s| {
+                        s.last_quote_at.is_some()
+                            && s.last_rule_at.is_some()
+                            && s.last_outcome_at.is_some()
+                    })
+            }
+        },
+    )
+    .await;
+    assert!(finished, "market fetch times were not recorded");
+
+    for task in ["discovery", "quote_polling", "rule_extraction"] {
+        let state = ingest_state::get_task_state(&pool, task)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("no checkpoint for {task}"));
+        assert!(state.last_success_at.is_some(), "{task}");
+    }
+
+    // A completed discovery pass leaves nothing to resume
+    let discovery = ingest_state::get_task_state(&pool, "discovery")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(discovery.cursor, None);
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn restart_waits_out_the_remaining_cadence(pool: PgPool) {
+    // Quotes were polled just before the restart; the next poll is an hour
+    // away, while every other task has no checkpoint and starts at once
+    ingest_state::record_task_success(&pool, "quote_polling", Utc::now())
+        .await
+        .unwrap();
+    let mut config = fast_config();
+    config.quotes_cadence_sec = 3600;
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), config);
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                markets::get_outcomes(&pool, "polymarket:0xa1")
+                    .await
+                    .map(|o| !o.is_empty())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(finished, "markets were not discovered");
+
+    let ids = vec!["polymarket:0xa1".to_string(), "polymarket:0xb2".to_string()];
+    let latest = quotes::get_quotes_latest_batch(&pool, &ids).await.unwrap();
+    assert!(
+        latest.is_empty(),
+        "quotes polled before the cadence elapsed"
+    );
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn markets_without_quotes_do_not_starve_the_poll(pool: PgPool) {
+    // More tokenless markets than one poll takes, all closing before the
+    // only market the venue can quote
+    let now = Utc::now();
+    let mut seeded: Vec<Market> = (0..3)
+        .map(|i| market(&format!("0xt{i}"), now + chrono::Duration::days(2), None))
+        .collect();
+    seeded.push(market(
+        "0xq",
+        now + chrono::Duration::days(5),
+        Some(["101", "102"]),
+    ));
+    markets::upsert_markets_batch(&pool, &seeded, now)
+        .await
+        .unwrap();
+
+    let config = IngestConfig {
+        max_quotes_per_fetch: 2,
+        ..fast_config()
+    };
+    let orchestrator = IngestOrchestrator::new(QuotingClient, pool.clone(), config);
+
+    let quoted = vec!["polymarket:0xq".to_string()];
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let quoted = quoted.clone();
+            async move {
+                quotes::get_quotes_latest_batch(&pool, &quoted)
+                    .await
+                    .is_ok_and(|q| !q.is_empty())
+            }
+        },
+    )
+    .await;
+    assert!(finished, "quotable market was never polled");
+
+    let ids: Vec<String> = seeded.iter().map(|m| m.market_id.clone()).collect();
+    let states = ingest_state::get_market_states(&pool, &ids).await.unwrap();
+    assert!(states
+        .iter()
+        .any(|s| s.market_id == "polymarket:0xt0" && s.last_quote_at.is_some()));
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn trade_polling_rotates_through_markets(pool: PgPool) {
+    // Twice as many markets as one poll takes, none with recent fills
+    let now = Utc::now();
+    let seeded: Vec<Market> = (0..4)
+        .map(|i| market(&format!("0xt{i}"), now + chrono::Duration::days(2), None))
+        .collect();
+    markets::upsert_markets_batch(&pool, &seeded, now)
+        .await
+        .unwrap();
+
+    let mut config = IngestConfig {
+        max_quotes_per_fetch: 2,
+        ..fast_config()
+    };
+    config.trades.cadence_sec = 1;
+    let orchestrator = IngestOrchestrator::new(QuotingClient, pool.clone(), config);
+
+    let ids: Vec<String> = seeded.iter().map(|m| m.market_id.clone()).collect();
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move {
+                ingest_state::get_market_states(&pool, &ids)
+                    .await
+                    .is_ok_and(|states| {
+                        states.len() == ids.len()
+                            && states.iter().all(|s| s.last_trade_at.is_some())
+                    })
+            }
+        },
+    )
+    .await;
+    assert!(finished, "trade polling did not reach every market");
+}
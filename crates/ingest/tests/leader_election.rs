@@ -0,0 +1,279 @@
+//! Leader election between ingest and scoring replicas sharing one database
+//!
+//! Advisory locks are scoped to the database, so tests do not contend with
+//! each other.
+
+mod common;
+
+use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::extract::State;
+use common::{fast_config, replay};
+use pm_api::{handlers, ApiConfig, AppState, Metrics};
+use pm_ingest::{orchestrator::OrchestratorError, IngestOrchestrator, ReplayVenueClient};
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::{
+    leader::{self, LeaderConfig, LeaderElection, LeaderRole},
+    markets,
+};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+fn fast_leader() -> LeaderConfig {
+    LeaderConfig {
+        enabled: true,
+        retry_interval_ms: 100,
+        check_interval_ms: 100,
+    }
+}
+
+fn ingest_replica(pool: &PgPool) -> Arc<IngestOrchestrator<ReplayVenueClient>> {
+    let mut config = fast_config();
+    config.leader = fast_leader();
+    Arc::new(IngestOrchestrator::new(
+        replay("two_markets"),
+        pool.clone(),
+        config,
+    ))
+}
+
+/// Poll `check` until it holds or five seconds pass
+async fn eventually<F, Fut>(check: F) -> bool
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    for _ in 0..50 {
+        if check().await {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+/// Terminate the session holding `role`'s lock, as if its replica died
+async fn kill_leader_session(pool: &PgPool, role: LeaderRole) {
+    let leaders = leader::list_leaders(pool).await.unwrap();
+    let holder = leaders.iter().find(|l| l.role == role).unwrap();
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(holder.pid)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Forward connections to `target` until `frozen` fires, then stop
+/// forwarding while keeping both ends open, like a half-open connection
+async fn freezable_proxy(target: SocketAddr, frozen: CancellationToken) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((client, _)) = listener.accept().await {
+            let frozen = frozen.clone();
+            tokio::spawn(async move {
+                let server = TcpStream::connect(target).await.unwrap();
+                let (mut client_rx, mut client_tx) = client.into_split();
+                let (mut server_rx, mut server_tx) = server.into_split();
+                tokio::select! {
+                    _ = tokio::io::copy(&mut client_rx, &mut server_tx) => {}
+                    _ = tokio::io::copy(&mut server_rx, &mut client_tx) => {}
+                    _ = frozen.cancelled() => std::future::pending::<()>().await,
+                }
+            });
+        }
+    });
+    addr
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn ingest_standby_takes_over_when_leader_session_drops(pool: PgPool) {
+    let first = ingest_replica(&pool);
+    let first_run = tokio::spawn({
+        let first = Arc::clone(&first);
+        async move { first.run().await }
+    });
+    assert!(eventually(|| async { first.is_leader() }).await);
+    assert_eq!(first.metrics().leader.get(), 1);
+
+    let second = ingest_replica(&pool);
+    let second_run = tokio::spawn({
+        let second = Arc::clone(&second);
+        async move { second.run().await }
+    });
+
+    // The leader ingests; the standby waits without touching the venue
+    let pool_ref = &pool;
+    assert!(
+        eventually(|| async move {
+            markets::get_outcomes(pool_ref, "polymarket:0xa1")
+                .await
+                .map(|o| !o.is_empty())
+                .unwrap_or(false)
+        })
+        .await
+    );
+    assert!(!second.is_leader());
+    assert_eq!(second.metrics().leader.get(), 0);
+
+    let leaders = leader::list_leaders(&pool).await.unwrap();
+    assert_eq!(leaders.len(), 1);
+    assert_eq!(leaders[0].role, LeaderRole::Ingest);
+    assert!(leaders[0]
+        .instance
+        .as_deref()
+        .is_some_and(|i| i.starts_with("pm-ingest@")));
+
+    kill_leader_session(&pool, LeaderRole::Ingest).await;
+
+    let lost = tokio::time::timeout(Duration::from_secs(10), first_run)
+        .await
+        .expect("old leader did not stop")
+        .unwrap();
+    assert!(matches!(lost, Err(OrchestratorError::LeadershipLost)));
+    assert!(!first.is_leader());
+
+    assert!(eventually(|| async { second.is_leader() }).await);
+    assert_eq!(second.metrics().leader.get(), 1);
+
+    second.cancellation_token().cancel();
+    tokio::time::timeout(Duration::from_secs(10), second_run)
+        .await
+        .expect("new leader did not shut down")
+        .unwrap()
+        .unwrap();
+
+    // The lock goes once the server notices the closed session
+    assert!(
+        eventually(|| async move {
+            leader::list_leaders(pool_ref)
+                .await
+                .is_ok_and(|leaders| leaders.is_empty())
+        })
+        .await
+    );
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn scoring_leadership_is_reported_in_health_and_metrics(pool: PgPool) {
+    let config = ScoringConfig {
+        leader: fast_leader(),
+        ..ScoringConfig::default()
+    };
+    let first = Arc::new(ScoringOrchestrator::new(pool.clone(), config.clone()));
+    let second = Arc::new(ScoringOrchestrator::new(pool.clone(), config));
+
+    let runs: Vec<_> = [&first, &second]
+        .into_iter()
+        .map(|replica| {
+            let replica = Arc::clone(replica);
+            tokio::spawn(async move { replica.run().await })
+        })
+        .collect();
+
+    assert!(eventually(|| async { first.is_leader() || second.is_leader() }).await);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(first.is_leader() != second.is_leader());
+
+    let state = AppState::new(pool.clone(), ApiConfig::default(), Metrics::default());
+    let (_, health) = handlers::health_handler(State(state.clone())).await;
+    assert!(health.0["leaders"]["ingest"].is_null());
+    assert_eq!(health.0["leaders"]["scoring"]["role"], "scoring");
+
+    let metrics = handlers::metrics_handler(State(state)).await.unwrap();
+    assert!(metrics.contains(r#"pm_leader_elected{role="scoring"} 1"#));
+    assert!(metrics.contains(r#"pm_leader_elected{role="ingest"} 0"#));
+
+    // Stopping the leader hands over to the standby
+    let (leader, standby) = if first.is_leader() {
+        (&first, &second)
+    } else {
+        (&second, &first)
+    };
+    leader.cancellation_token().cancel();
+    assert!(eventually(|| async { standby.is_leader() }).await);
+
+    standby.cancellation_token().cancel();
+    for run in runs {
+        tokio::time::timeout(Duration::from_secs(10), run)
+            .await
+            .expect("scoring replica did not shut down")
+            .unwrap()
+            .unwrap();
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn unanswered_liveness_check_counts_as_lost(pool: PgPool) {
+    let options = pool.connect_options();
+    let target = format!("{}:{}", options.get_host(), options.get_port())
+        .parse()
+        .unwrap();
+    let frozen = CancellationToken::new();
+    let proxy = freezable_proxy(target, frozen.clone()).await;
+    let proxied = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with((*options).clone().host("127.0.0.1").port(proxy.port()))
+        .await
+        .unwrap();
+
+    let election = LeaderElection::new(proxied, LeaderRole::Ingest, "test", fast_leader());
+    let mut leadership = election
+        .acquire(&CancellationToken::new())
+        .await
+        .expect("no leadership");
+    assert!(election.is_leader());
+
+    // The server still holds the session, but nothing gets through
+    frozen.cancel();
+    tokio::time::timeout(Duration::from_secs(5), leadership.lost())
+        .await
+        .expect("liveness check hung on a half-open connection");
+    assert!(!election.is_leader());
+
+    // Free the lock so the test database can be dropped
+    kill_leader_session(&pool, LeaderRole::Ingest).await;
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replicas_without_election_report_as_leader(pool: PgPool) {
+    let mut ingest_config = fast_config();
+    ingest_config.leader.enabled = false;
+    let ingest = Arc::new(IngestOrchestrator::new(
+        replay("two_markets"),
+        pool.clone(),
+        ingest_config,
+    ));
+    let mut scoring_config = ScoringConfig::default();
+    scoring_config.leader.enabled = false;
+    let scoring = Arc::new(ScoringOrchestrator::new(pool.clone(), scoring_config));
+
+    let ingest_run = tokio::spawn({
+        let ingest = Arc::clone(&ingest);
+        async move { ingest.run().await }
+    });
+    let scoring_run = tokio::spawn({
+        let scoring = Arc::clone(&scoring);
+        async move { scoring.run().await }
+    });
+
+    // Both services mean the same thing by the gauge: this replica runs
+    assert!(eventually(|| async { ingest.metrics().leader.get() == 1 }).await);
+    assert!(eventually(|| async { scoring.metrics().leader.get() == 1 }).await);
+    assert!(ingest.is_leader() && scoring.is_leader());
+
+    ingest.cancellation_token().cancel();
+    scoring.cancellation_token().cancel();
+    tokio::time::timeout(Duration::from_secs(10), ingest_run)
+        .await
+        .expect("ingest did not shut down")
+        .unwrap()
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(10), scoring_run)
+        .await
+        .expect("scoring did not shut down")
+        .unwrap()
+        .unwrap();
+}
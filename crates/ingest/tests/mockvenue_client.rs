@@ -0,0 +1,87 @@
+//! `PolymarketClient` against the local mock venue
+
+use std::{path::PathBuf, sync::Arc};
+
+use pm_domain::{MarketStatus, PriceSource};
+use pm_ingest::{
+    client::ClientError, config::RetryConfig, PolymarketClient, VenueClient, VenueConfig,
+};
+use pm_mockvenue::{router, MockVenue, Scenario};
+
+/// Serve a scenario on an ephemeral port and return its base URL
+async fn serve(scenario: Scenario) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(MockVenue::new(scenario)));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    format!("http://{}", addr)
+}
+
+fn client(base_url: &str) -> PolymarketClient {
+    let venue = VenueConfig {
+        gamma_base_url: base_url.to_string(),
+        clob_base_url: base_url.to_string(),
+        data_base_url: base_url.to_string(),
+    };
+    let retry = RetryConfig {
+        max_attempts: 1,
+        ..RetryConfig::default()
+    };
+    PolymarketClient::with_venue(&venue, retry)
+}
+
+fn endgame_scenario() -> Scenario {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../mockvenue/scenarios/endgame.json");
+    Scenario::load(path).unwrap()
+}
+
+#[tokio::test]
+async fn discovers_markets_and_quotes_from_mock_venue() {
+    let client = client(&serve(endgame_scenario()).await);
+
+    let markets = client.discover_markets(10, 0).await.unwrap();
+    assert_eq!(markets.len(), 3);
+    assert_eq!(markets[0].market_id, "polymarket:0xmock01");
+    assert_eq!(markets[0].metadata.event_id.as_deref(), Some("7001"));
+    assert_eq!(markets[0].metadata.liquidity, Some(88000.0));
+
+    let outcomes = client.get_outcomes("polymarket:0xmock01").await.unwrap();
+    assert_eq!(outcomes.len(), 2);
+
+    let quotes = client.get_quotes(&outcomes).await.unwrap();
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0].no_source, PriceSource::Observed);
+    assert!(quotes[0].no_bid.unwrap() < quotes[0].no_ask.unwrap());
+
+    let rule = client.get_rules("polymarket:0xmock01").await.unwrap();
+    assert!(rule.rule_text.contains("electoral commission"));
+
+    assert_eq!(
+        client
+            .get_market_status("polymarket:0xmock01")
+            .await
+            .unwrap(),
+        MarketStatus::Active
+    );
+    assert!(matches!(
+        client.get_market_status("polymarket:0xmissing").await,
+        Err(ClientError::MarketNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn surfaces_scripted_outage() {
+    let mut scenario = endgame_scenario();
+    scenario.faults = serde_json::from_value(serde_json::json!([
+        {"kind": "outage", "from_step": 0, "to_step": 1000, "endpoints": ["markets"]}
+    ]))
+    .unwrap();
+    let client = client(&serve(scenario).await);
+
+    assert!(matches!(
+        client.discover_markets(10, 0).await,
+        Err(ClientError::Http(_))
+    ));
+    assert!(client.get_outcomes("polymarket:0xmock02").await.is_ok());
+}
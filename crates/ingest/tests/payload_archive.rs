@@ -0,0 +1,224 @@
+//! Raw payload archiving against the mock venue, and re-parsing the archive
+
+mod common;
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use common::{fast_config, run_until};
+use pm_domain::{PriceSource, RawPayload};
+use pm_ingest::{
+    client::{ENDPOINT_BOOK, ENDPOINT_MARKET, ENDPOINT_MARKETS},
+    config::RetryConfig,
+    IngestOrchestrator, PolymarketClient, ReparseOptions, VenueConfig,
+};
+use pm_mockvenue::{router, MockVenue, Scenario};
+use pm_storage::{
+    markets,
+    payloads::{self, PayloadFilter},
+    quotes, rules,
+};
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+
+/// Serve the endgame scenario on an ephemeral port and return its base URL
+async fn serve_endgame() -> String {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../mockvenue/scenarios/endgame.json");
+    let scenario = Scenario::load(path).unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(MockVenue::new(scenario)));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    format!("http://{}", addr)
+}
+
+fn payload(endpoint: &str, fetched_at: chrono::DateTime<Utc>, body: &[u8]) -> RawPayload {
+    RawPayload {
+        venue: "polymarket".to_string(),
+        market_id: None,
+        endpoint: endpoint.to_string(),
+        request_url: "http://localhost/markets".to_string(),
+        outcome: None,
+        fetched_at,
+        http_status: 200,
+        body: body.to_vec(),
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn archived_payloads_rebuild_rows_after_reparse(pool: PgPool) {
+    let base_url = serve_endgame().await;
+    let mut config = fast_config();
+    config.venue = VenueConfig {
+        gamma_base_url: base_url.clone(),
+        clob_base_url: base_url.clone(),
+        data_base_url: base_url,
+    };
+    config.retry = RetryConfig {
+        max_attempts: 1,
+        ..RetryConfig::default()
+    };
+    config.payloads.enabled = true;
+
+    let (tx, rx) = mpsc::channel(config.payloads.channel_size);
+    let client =
+        PolymarketClient::with_venue(&config.venue, config.retry.clone()).with_payload_capture(tx);
+    let orchestrator =
+        IngestOrchestrator::new(client, pool.clone(), config).with_payload_archive(rx);
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(20),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                let filter = PayloadFilter {
+                    endpoint: Some(ENDPOINT_BOOK.to_string()),
+                    ..PayloadFilter::default()
+                };
+                payloads::list_payloads(&pool, &filter, 0, 1)
+                    .await
+                    .map(|p| !p.is_empty())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(finished, "no order book payloads were archived");
+
+    let archived = payloads::list_payloads(&pool, &PayloadFilter::default(), 0, 1000)
+        .await
+        .unwrap();
+    for endpoint in [ENDPOINT_MARKETS, ENDPOINT_MARKET, ENDPOINT_BOOK] {
+        assert!(
+            archived.iter().any(|p| p.payload.endpoint == endpoint),
+            "missing {} payloads",
+            endpoint
+        );
+    }
+    let book = archived
+        .iter()
+        .find(|p| p.payload.endpoint == ENDPOINT_BOOK)
+        .unwrap();
+    assert!(book.payload.market_id.is_some());
+    assert!(book.payload.outcome.is_some());
+    assert!(serde_json::from_slice::<serde_json::Value>(&book.payload.body).is_ok());
+
+    // Drop the parsed rows, as if they had been written by a broken parser
+    sqlx::query("DELETE FROM quotes_latest")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM rules_latest")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let dry_run = pm_ingest::reparse(
+        &pool,
+        &ReparseOptions {
+            dry_run: true,
+            ..ReparseOptions::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(dry_run.parse_errors, 0);
+    assert!(dry_run.quotes > 0);
+    assert!(quotes::get_quote_latest(&pool, "polymarket:0xmock01")
+        .await
+        .is_err());
+
+    let summary = pm_ingest::reparse(&pool, &ReparseOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(summary, dry_run);
+    assert_eq!(summary.markets, 3);
+
+    let quote = quotes::get_quote_latest(&pool, "polymarket:0xmock01")
+        .await
+        .unwrap();
+    assert_eq!(quote.no_source, PriceSource::Observed);
+    let rule = rules::get_rule(&pool, "polymarket:0xmock01").await.unwrap();
+    assert!(rule.rule_text.contains("electoral commission"));
+
+    // Scoped to one market, only its rows are rebuilt
+    let scoped = pm_ingest::reparse(
+        &pool,
+        &ReparseOptions {
+            market_id: Some("polymarket:0xmock02".to_string()),
+            ..ReparseOptions::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(scoped.markets, 1);
+    assert!(scoped.rules <= 1);
+    assert!(markets::get_market(&pool, "polymarket:0xmock02")
+        .await
+        .is_ok());
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn reparse_skips_rows_for_unknown_markets_and_bad_bodies(pool: PgPool) {
+    let now = Utc::now();
+    let mut book = payload(
+        ENDPOINT_BOOK,
+        now,
+        br#"{"bids":[],"asks":[{"price":"0.5","size":"1"}]}"#,
+    );
+    book.market_id = Some("polymarket:0xunknown".to_string());
+    book.outcome = Some("YES".to_string());
+
+    payloads::insert_payloads_batch(
+        &pool,
+        &[book, payload(ENDPOINT_MARKETS, now, b"[{\"conditionId\":")],
+    )
+    .await
+    .unwrap();
+
+    let summary = pm_ingest::reparse(&pool, &ReparseOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(summary.payloads, 2);
+    assert_eq!(summary.parse_errors, 1);
+    assert_eq!(summary.quotes, 0);
+    assert_eq!(summary.skipped_unknown, 1);
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn prune_enforces_age_and_size_bounds(pool: PgPool) {
+    let now = Utc::now();
+    let body = br#"[{"conditionId":"0xabc"}]"#;
+    let batch = vec![
+        payload(ENDPOINT_MARKETS, now - chrono::Duration::days(10), body),
+        payload(ENDPOINT_MARKETS, now - chrono::Duration::hours(2), body),
+        payload(ENDPOINT_MARKETS, now - chrono::Duration::hours(1), body),
+        payload(ENDPOINT_MARKETS, now, body),
+    ];
+    let stored = payloads::insert_payloads_batch(&pool, &batch)
+        .await
+        .unwrap();
+    let each = stored / batch.len() as i64;
+
+    // Age bound removes the ten-day-old payload, size bound the two oldest
+    // of the rest
+    let deleted = payloads::prune_payloads(&pool, now - chrono::Duration::days(3), each)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 3);
+
+    let left = payloads::list_payloads(&pool, &PayloadFilter::default(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(left.len(), 1);
+    assert_eq!(left[0].payload.fetched_at.timestamp(), now.timestamp());
+    assert_eq!(left[0].payload.body, body);
+    assert_eq!(
+        payloads::total_stored_size(&pool).await.unwrap(),
+        left[0].stored_size as i64
+    );
+}
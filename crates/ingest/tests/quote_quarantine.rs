@@ -0,0 +1,113 @@
+//! Quote validation and quarantine during ingest
+
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use common::{fast_config, replay, run_until};
+use pm_domain::{
+    Market, MarketStatus, Outcome, Quote, QuoteIssue, QuoteValidity, RuleSnapshot, Trade,
+};
+use pm_ingest::{client::Result, IngestOrchestrator, ReplayVenueClient, VenueClient};
+use pm_storage::quotes;
+use sqlx::PgPool;
+
+/// Replayed venue whose books turn crossed once `crossed` is set
+struct CrossingClient {
+    inner: ReplayVenueClient,
+    crossed: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl VenueClient for CrossingClient {
+    async fn discover_markets(&self, limit: usize, offset: usize) -> Result<Vec<Market>> {
+        self.inner.discover_markets(limit, offset).await
+    }
+
+    async fn get_quotes(&self, outcomes: &[Outcome]) -> Result<Vec<Quote>> {
+        let mut quotes = self.inner.get_quotes(outcomes).await?;
+        if self.crossed.load(Ordering::SeqCst) {
+            for quote in &mut quotes {
+                quote.yes_bid = quote.yes_ask.map(|ask| ask + 0.1);
+            }
+        }
+        Ok(quotes)
+    }
+
+    async fn get_rules(&self, market_id: &str) -> Result<RuleSnapshot> {
+        self.inner.get_rules(market_id).await
+    }
+
+    async fn get_market_status(&self, market_id: &str) -> Result<MarketStatus> {
+        self.inner.get_market_status(market_id).await
+    }
+
+    async fn get_outcomes(&self, market_id: &str) -> Result<Vec<Outcome>> {
+        self.inner.get_outcomes(market_id).await
+    }
+
+    async fn get_trades(&self, market_id: &str, limit: usize) -> Result<Vec<Trade>> {
+        self.inner.get_trades(market_id, limit).await
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn crossed_books_are_quarantined_and_last_good_quote_kept(pool: PgPool) {
+    let crossed = Arc::new(AtomicBool::new(false));
+    let client = CrossingClient {
+        inner: replay("two_markets"),
+        crossed: Arc::clone(&crossed),
+    };
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), fast_config());
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(20),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let crossed = Arc::clone(&crossed);
+            async move {
+                // Cross the books once a good quote is stored
+                if !crossed.load(Ordering::SeqCst) {
+                    if quotes::get_quote_latest(&pool, "polymarket:0xa1")
+                        .await
+                        .is_ok()
+                    {
+                        crossed.store(true, Ordering::SeqCst);
+                    }
+                    return false;
+                }
+                quotes::list_quarantined(&pool, Some("polymarket:0xa1"), 1)
+                    .await
+                    .map(|q| !q.is_empty())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(finished, "no crossed quote was quarantined");
+
+    let quarantined = quotes::list_quarantined(&pool, Some("polymarket:0xa1"), 10)
+        .await
+        .unwrap();
+    let entry = &quarantined[0];
+    assert_eq!(entry.validity, QuoteValidity::Invalid);
+    assert!(entry.issues.contains(&QuoteIssue::CrossedBook));
+    assert!(entry.quote.yes_bid > entry.quote.yes_ask);
+
+    // The last good quote is still the one served to scoring
+    let latest = quotes::get_quote_latest(&pool, "polymarket:0xa1")
+        .await
+        .unwrap();
+    assert_eq!(latest.no_bid, Some(0.955));
+    assert!(latest.yes_bid <= latest.yes_ask);
+    assert!(latest.as_of < entry.quote.as_of);
+}
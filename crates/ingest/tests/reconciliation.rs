@@ -0,0 +1,97 @@
+//! Reconciliation of markets that discovery stops returning
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use common::{fast_config, fixture_dir, run_until};
+use pm_domain::{Clock, MarketStatus, SharedClock, SimulatedClock};
+use pm_ingest::{IngestConfig, IngestOrchestrator, ReplayVenueClient, VenueClient};
+use pm_storage::{ingest_state, markets};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn confirmed_markets_are_marked_seen(pool: PgPool) {
+    let start = Utc::now();
+    let clock = Arc::new(SimulatedClock::new(start));
+    let shared: SharedClock = clock.clone();
+
+    // Stored markets last seen two hours ago, past the one-hour unseen
+    // limit, with discovery checkpointed so it does not refresh them first
+    let stale = ReplayVenueClient::open(fixture_dir("two_markets"))
+        .unwrap()
+        .rebased_to(start)
+        .discover_markets(IngestConfig::default().max_markets_per_discovery, 0)
+        .await
+        .unwrap();
+    markets::upsert_markets_batch(&pool, &stale, start - chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    ingest_state::record_task_success(&pool, "discovery", start)
+        .await
+        .unwrap();
+
+    let client = ReplayVenueClient::open(fixture_dir("two_markets"))
+        .unwrap()
+        .rebased_to(start);
+    let config = IngestConfig {
+        discovery_cadence_sec: 3600,
+        ..fast_config()
+    };
+    let orchestrator =
+        IngestOrchestrator::new(client, pool.clone(), config).with_clock(Arc::clone(&shared));
+
+    let ids = ["polymarket:0xa1", "polymarket:0xb2"];
+    let seen_before = start - chrono::Duration::hours(1);
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                let mut stored = 0;
+                for id in ids {
+                    stored += markets::get_market(&pool, id).await.is_ok() as usize;
+                }
+                stored == ids.len()
+                    && markets::list_unseen_markets(&pool, seen_before, 10)
+                        .await
+                        .is_ok_and(|m| m.is_empty())
+            }
+        },
+    )
+    .await;
+    assert!(
+        finished,
+        "markets the venue reports active were not marked seen"
+    );
+
+    // Halted markets stay in the re-check set until they close or resolve
+    markets::update_market_status(
+        &pool,
+        "polymarket:0xb2",
+        MarketStatus::Halted,
+        "test",
+        None,
+        clock.now(),
+    )
+    .await
+    .unwrap();
+    let history = markets::get_status_history(&pool, "polymarket:0xb2", 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        history[0].changed_at.timestamp_micros(),
+        clock.now().timestamp_micros()
+    );
+
+    let unseen =
+        markets::list_unseen_markets(&pool, clock.now() + chrono::Duration::seconds(1), 10)
+            .await
+            .unwrap();
+    assert!(unseen
+        .iter()
+        .any(|m| m.market_id == "polymarket:0xb2" && m.status == MarketStatus::Halted));
+}
@@ -0,0 +1,81 @@
+//! Offline ingest cycles replayed from recorded venue fixtures
+
+mod common;
+
+use std::time::Duration;
+
+use chrono::Utc;
+use common::{fast_config, replay, run_until};
+use pm_domain::{MarketStatus, PriceSource};
+use pm_ingest::{IngestOrchestrator, ReplayVenueClient};
+use pm_storage::{markets, quotes, rules};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replayed_cycle_persists_markets_rules_outcomes_and_quotes(pool: PgPool) {
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), fast_config());
+
+    let ids = vec!["polymarket:0xa1".to_string(), "polymarket:0xb2".to_string()];
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move {
+                quotes::get_quotes_latest_batch(&pool, &ids)
+                    .await
+                    .map(|q| q.len() == 2)
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(finished, "ingest did not persist quotes for both markets");
+
+    let market = markets::get_market(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(market.venue_market_id, "0xa1");
+    assert_eq!(market.status, MarketStatus::Active);
+    assert_eq!(market.metadata.event_id.as_deref(), Some("9001"));
+    assert_eq!(market.metadata.tags, vec!["Economics", "Fed"]);
+    assert!(market.metadata.neg_risk);
+    assert!(market.close_time.unwrap() > Utc::now());
+
+    let outcomes = markets::get_outcomes_batch(&pool, &ids).await.unwrap();
+    assert_eq!(outcomes.len(), 4);
+    assert!(outcomes.iter().all(|o| o.token_id.is_some()));
+
+    let rule = rules::get_rule(&pool, "polymarket:0xb2").await.unwrap();
+    assert_eq!(rule.risk_flags.len(), 1);
+    assert_eq!(rule.risk_flags[0].code, "SUBJECTIVE_RESOLUTION");
+
+    let quote = quotes::get_quote_latest(&pool, "polymarket:0xa1")
+        .await
+        .unwrap();
+    assert_eq!(quote.no_bid, Some(0.955));
+    assert_eq!(quote.no_source, PriceSource::Observed);
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replay_without_fixtures_persists_nothing(pool: PgPool) {
+    let dir = tempfile::tempdir().unwrap();
+    let client = ReplayVenueClient::open(dir.path()).unwrap();
+    assert!(client.is_empty());
+
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), fast_config());
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(3),
+        Duration::from_secs(5),
+        || async { false },
+    )
+    .await;
+    assert!(!finished);
+
+    let listed = markets::list_markets(&pool, None, None, 10, 0)
+        .await
+        .unwrap();
+    assert!(listed.is_empty());
+}
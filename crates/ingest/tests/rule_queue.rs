@@ -0,0 +1,50 @@
+//! Event-driven rule fetches for newly discovered markets
+
+mod common;
+
+use std::time::Duration;
+
+use chrono::Utc;
+use common::{fast_config, replay, run_until};
+use pm_ingest::IngestOrchestrator;
+use pm_storage::{ingest_state, markets};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn new_markets_get_rules_without_waiting_for_the_refresh(pool: PgPool) {
+    // The periodic rule refresh just ran and is not due for an hour
+    ingest_state::record_task_success(&pool, "rule_extraction", Utc::now())
+        .await
+        .unwrap();
+    let mut config = fast_config();
+    config.rules_refresh_cadence_sec = 3600;
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), config);
+
+    let ids = vec!["polymarket:0xa1".to_string(), "polymarket:0xb2".to_string()];
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move {
+                ingest_state::get_market_states(&pool, &ids)
+                    .await
+                    .map(|states| {
+                        states.len() == 2
+                            && states
+                                .iter()
+                                .all(|s| s.last_rule_at.is_some() && s.last_outcome_at.is_some())
+                    })
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(finished, "new markets were not queued for a rule fetch");
+
+    for id in &ids {
+        assert!(!markets::get_outcomes(&pool, id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Schema-drift detection and degraded mode against the mock venue
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use axum::extract::{Query, State};
+use common::{fast_config, run_until};
+use pm_api::{handlers, ApiConfig, AppState, Metrics};
+use pm_domain::DriftKind;
+use pm_ingest::{
+    client::ENDPOINT_MARKET, config::RetryConfig, IngestConfig, IngestOrchestrator,
+    PolymarketClient, SchemaTracker, VenueConfig,
+};
+use pm_mockvenue::{router, MockVenue, Scenario};
+use pm_storage::{markets, rules, schema_drift};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+/// One-market scenario with extra gamma fields merged into the market
+fn scenario(extra: Value) -> Scenario {
+    let mut market = json!({
+        "condition_id": "0xdrift",
+        "question": "Will the schema hold?",
+        "closes_in_sec": 259200,
+        "description": "Resolves per the official announcement.",
+        "yes_token_id": "1001",
+        "no_token_id": "1002",
+        "price_path": [{"step": 0, "yes_mid": 0.05}],
+        "spread": 0.01
+    });
+    if let (Value::Object(fields), Value::Object(extra)) = (&mut market, extra) {
+        fields.extend(extra);
+    }
+
+    serde_json::from_value(json!({"step_ms": 60000, "markets": [market]})).unwrap()
+}
+
+/// Serve a scenario and return an ingest config pointed at it
+async fn serve(scenario: Scenario) -> IngestConfig {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(MockVenue::new(scenario)));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut config = fast_config();
+    config.venue = VenueConfig {
+        gamma_base_url: format!("http://{}", addr),
+        clob_base_url: format!("http://{}", addr),
+        data_base_url: format!("http://{}", addr),
+    };
+    config.retry = RetryConfig {
+        max_attempts: 1,
+        ..RetryConfig::default()
+    };
+    config.schema_drift.flush_cadence_sec = 1;
+    config
+}
+
+fn orchestrator(
+    config: IngestConfig,
+    pool: &PgPool,
+) -> (IngestOrchestrator<PolymarketClient>, Arc<SchemaTracker>) {
+    let tracker = Arc::new(SchemaTracker::polymarket());
+    let client = PolymarketClient::with_venue(&config.venue, config.retry.clone())
+        .with_schema_tracking(Arc::clone(&tracker));
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), config)
+        .with_schema_tracker(Arc::clone(&tracker));
+    (orchestrator, tracker)
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn missing_critical_field_degrades_discovery(pool: PgPool) {
+    let config = serve(scenario(json!({"endDate": null, "promoBanner": "new"}))).await;
+    let (orchestrator, tracker) = orchestrator(config, &pool);
+
+    let recorded = run_until(
+        orchestrator,
+        Duration::from_secs(10),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                schema_drift::list_drift(&pool, true)
+                    .await
+                    .map(|d| d.iter().any(|d| d.field == "endDate"))
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(recorded, "endDate drift was not persisted");
+    assert_eq!(tracker.degraded_endpoints(), vec!["gamma_markets"]);
+
+    // Degraded discovery keeps markets without a close time out of storage
+    let listed = markets::list_markets(&pool, None, None, 10, 0)
+        .await
+        .unwrap();
+    assert!(listed.is_empty());
+
+    let drift = schema_drift::list_drift(&pool, false).await.unwrap();
+    let end_date = drift.iter().find(|d| d.field == "endDate").unwrap();
+    assert_eq!(end_date.kind, DriftKind::Missing);
+    assert!(end_date.critical && end_date.active);
+    assert!(end_date.occurrences >= 1);
+    assert!(drift
+        .iter()
+        .any(|d| d.field == "promoBanner" && d.kind == DriftKind::Unexpected && !d.critical));
+
+    let state = AppState::new(pool.clone(), ApiConfig::default(), Metrics::default());
+    let report = handlers::schema_drift_handler(
+        State(state.clone()),
+        Query(handlers::ingest::SchemaDriftQuery { active: Some(true) }),
+    )
+    .await
+    .unwrap();
+    assert!(report.0.degraded);
+    assert_eq!(report.0.degraded_endpoints, vec!["gamma_markets"]);
+
+    let metrics = handlers::metrics_handler(State(state)).await.unwrap();
+    assert!(metrics.contains("pm_ingest_degraded_endpoints 1"));
+    assert!(metrics
+        .contains(r#"pm_ingest_schema_drift_fields{endpoint="gamma_markets",kind="missing"} 1"#));
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn baseline_responses_ingest_without_drift(pool: PgPool) {
+    let config = serve(scenario(json!({}))).await;
+    let (orchestrator, tracker) = orchestrator(config, &pool);
+
+    let discovered = run_until(
+        orchestrator,
+        Duration::from_secs(10),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move {
+                markets::get_outcomes(&pool, "polymarket:0xdrift")
+                    .await
+                    .map(|o| !o.is_empty())
+                    .unwrap_or(false)
+            }
+        },
+    )
+    .await;
+    assert!(discovered, "market was not ingested");
+
+    assert!(tracker.snapshot().is_empty(), "{:?}", tracker.snapshot());
+    assert!(schema_drift::list_drift(&pool, false)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn degraded_detail_endpoint_recovers_on_next_response(pool: PgPool) {
+    let config = serve(scenario(json!({}))).await;
+    let (orchestrator, tracker) = orchestrator(config, &pool);
+
+    // An earlier detail body lost its rule text
+    tracker.observe(
+        ENDPOINT_MARKET,
+        br#"{"outcomes": "[]", "clobTokenIds": "[]"}"#,
+    );
+    assert!(tracker.is_degraded(ENDPOINT_MARKET));
+
+    let extracted = run_until(
+        orchestrator,
+        Duration::from_secs(10),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            async move { rules::get_rule(&pool, "polymarket:0xdrift").await.is_ok() }
+        },
+    )
+    .await;
+    assert!(
+        extracted,
+        "rules were not extracted after the drift cleared"
+    );
+    assert!(!tracker.is_degraded(ENDPOINT_MARKET));
+}
@@ -0,0 +1,74 @@
+//! Ingest and scoring driven by a simulated clock
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use common::{fast_config, fixture_dir, run_until, scoring_inputs_stored};
+use pm_domain::{Clock, IneligibleReason, SharedClock, SimulatedClock};
+use pm_ingest::{IngestOrchestrator, ReplayVenueClient};
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::{eligibility, scores};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replay_in_the_past_is_ingested_and_scored_on_simulated_time(pool: PgPool) {
+    // Both fixture markets close within a week of the recording, so on the
+    // wall clock they would already be outside every eligibility window
+    let start = Utc::now() - chrono::Duration::days(20);
+    let clock = Arc::new(SimulatedClock::new(start));
+    let shared: SharedClock = clock.clone();
+
+    let client = ReplayVenueClient::open(fixture_dir("two_markets"))
+        .unwrap()
+        .rebased_to(start);
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), fast_config())
+        .with_clock(Arc::clone(&shared));
+
+    let ids = vec!["polymarket:0xa1".to_string(), "polymarket:0xb2".to_string()];
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move { scoring_inputs_stored(&pool, &ids).await }
+        },
+    )
+    .await;
+    assert!(finished, "quotes were not polled on simulated time");
+
+    let scoring = ScoringOrchestrator::new(pool.clone(), ScoringConfig::default())
+        .with_clock(Arc::clone(&shared));
+
+    // One minute after the replayed quotes, a third of the staleness limit
+    clock.advance(chrono::Duration::minutes(1));
+    scoring.run_scoring_cycle().await.unwrap();
+    let fresh = scores::get_score(&pool, "polymarket:0xa1").await.unwrap();
+    assert!(
+        (55..=65).contains(&fresh.staleness_sec),
+        "{}",
+        fresh.staleness_sec
+    );
+    assert!((fresh.staleness_penalty - fresh.staleness_sec as f64 / 180.0).abs() < 1e-3);
+
+    // Half an hour on, the same quotes are past the staleness limit and
+    // the market is no longer scored
+    clock.advance(chrono::Duration::minutes(30));
+    scoring.run_scoring_cycle().await.unwrap();
+    let check = eligibility::get_check(&pool, "polymarket:0xa1")
+        .await
+        .unwrap();
+    assert_eq!(check.reason, Some(IneligibleReason::StaleQuote));
+    let unchanged = scores::get_score(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(unchanged.staleness_sec, fresh.staleness_sec);
+
+    // Within the final hour the market leaves the scoring window
+    clock.set(start + chrono::Duration::days(3) - chrono::Duration::minutes(30));
+    scoring.run_scoring_cycle().await.unwrap();
+    let unchanged = scores::get_score(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(unchanged.staleness_sec, fresh.staleness_sec);
+    assert!(clock.now() < Utc::now());
+}
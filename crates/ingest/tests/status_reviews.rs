@@ -0,0 +1,58 @@
+//! Status changes that discovery keeps proposing but the state machine
+//! does not apply
+
+mod common;
+
+use chrono::Utc;
+use common::fixture_dir;
+use pm_domain::MarketStatus;
+use pm_ingest::{IngestConfig, ReplayVenueClient, VenueClient};
+use pm_storage::markets;
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn repeated_rejected_transition_is_reviewed_once(pool: PgPool) {
+    let now = Utc::now();
+    let mut discovered = ReplayVenueClient::open(fixture_dir("two_markets"))
+        .unwrap()
+        .rebased_to(now)
+        .discover_markets(IngestConfig::default().max_markets_per_discovery, 0)
+        .await
+        .unwrap();
+    markets::upsert_markets_batch(&pool, &discovered, now)
+        .await
+        .unwrap();
+    markets::update_market_status(
+        &pool,
+        "polymarket:0xa1",
+        MarketStatus::Resolved,
+        "test",
+        None,
+        now,
+    )
+    .await
+    .unwrap();
+
+    // The venue still lists the resolved market, now as closed
+    for market in &mut discovered {
+        if market.market_id == "polymarket:0xa1" {
+            market.status = MarketStatus::Closed;
+        }
+    }
+    for pass in 1..=2 {
+        markets::upsert_markets_batch(&pool, &discovered, now + chrono::Duration::minutes(pass))
+            .await
+            .unwrap();
+    }
+
+    let reviews: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM market_status_reviews WHERE market_id = $1")
+            .bind("polymarket:0xa1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(reviews, 1);
+
+    let market = markets::get_market(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(market.status, MarketStatus::Resolved);
+}
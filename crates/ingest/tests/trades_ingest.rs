@@ -0,0 +1,97 @@
+//! Trade print ingestion against the mock venue, and the scoring features
+//! derived from it
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{fast_config, run_until};
+use pm_domain::TradeSide;
+use pm_ingest::{config::RetryConfig, IngestOrchestrator, PolymarketClient, VenueConfig};
+use pm_mockvenue::{router, MockVenue, Scenario};
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::{quotes, scores, trades};
+use serde_json::json;
+use sqlx::PgPool;
+
+const MARKET_ID: &str = "polymarket:0xtrades";
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn trades_are_windowed_and_feed_scoring(pool: PgPool) {
+    // One fill per 200ms step
+    let scenario: Scenario = serde_json::from_value(json!({
+        "step_ms": 200,
+        "markets": [{
+            "condition_id": "0xtrades",
+            "question": "Will the fills keep coming?",
+            "closes_in_sec": 259200,
+            "description": "Resolves per the official announcement.",
+            "yes_token_id": "2001",
+            "no_token_id": "2002",
+            "price_path": [{"step": 0, "yes_mid": 0.04}],
+            "spread": 0.02,
+            "trade_size": 50
+        }]
+    }))
+    .unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = router(Arc::new(MockVenue::new(scenario)));
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut config = fast_config();
+    config.venue = VenueConfig {
+        gamma_base_url: format!("http://{}", addr),
+        clob_base_url: format!("http://{}", addr),
+        data_base_url: format!("http://{}", addr),
+    };
+    config.retry = RetryConfig {
+        max_attempts: 1,
+        ..RetryConfig::default()
+    };
+    config.trades.cadence_sec = 1;
+    config.trades.max_per_market = 5;
+
+    let client = PolymarketClient::with_venue(&config.venue, config.retry.clone());
+    let orchestrator = IngestOrchestrator::new(client, pool.clone(), config);
+
+    // Wait until more fills have printed than the window keeps
+    let ids = vec![MARKET_ID.to_string()];
+    let filled = run_until(
+        orchestrator,
+        Duration::from_secs(20),
+        Duration::from_secs(5),
+        || {
+            let pool = pool.clone();
+            let ids = ids.clone();
+            async move {
+                let quoted = quotes::get_quote_latest(&pool, MARKET_ID).await.is_ok();
+                let stored = trades::get_trades_batch(&pool, &ids)
+                    .await
+                    .map(|t| t.len())
+                    .unwrap_or(0);
+                quoted && stored == 5
+            }
+        },
+    )
+    .await;
+    assert!(filled, "trades were not ingested");
+
+    let stored = trades::get_trades_batch(&pool, &ids).await.unwrap();
+    assert_eq!(stored.len(), 5);
+    assert!(stored.windows(2).all(|w| w[0].traded_at >= w[1].traded_at));
+    assert!(stored.iter().all(|t| t.outcome == "NO" && t.size == 50.0));
+    assert!(stored.iter().any(|t| t.side == TradeSide::Buy));
+
+    let scoring = ScoringOrchestrator::new(pool.clone(), ScoringConfig::default());
+    scoring.run_scoring_cycle().await.unwrap();
+
+    let score = scores::get_score(&pool, MARKET_ID).await.unwrap();
+    let breakdown = &score.score_breakdown;
+    assert!(breakdown["last_trade_no_price"].as_f64().unwrap() > 0.9);
+    assert!(breakdown["last_trade_vs_mid"].as_f64().unwrap().abs() <= 0.011);
+    assert!(breakdown["notional_1h"].as_f64().unwrap() > 0.0);
+    assert_eq!(breakdown["trades_1h"].as_u64(), Some(5));
+    assert!(breakdown["time_since_last_trade_sec"].as_i64().unwrap() < 60);
+}
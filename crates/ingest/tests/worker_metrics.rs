@@ -0,0 +1,72 @@
+//! Loop and venue metrics served by the ingest metrics listener
+
+mod common;
+
+use std::time::Duration;
+
+use common::{fast_config, replay, run_until};
+use pm_ingest::{IngestMetrics, IngestOrchestrator};
+use pm_metrics::{MetricsServer, WorkerHealth};
+use sqlx::PgPool;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn replay_cycles_are_served_on_metrics_endpoint(pool: PgPool) {
+    let metrics = IngestMetrics::default();
+    let orchestrator = IngestOrchestrator::new(replay("two_markets"), pool.clone(), fast_config())
+        .with_metrics(metrics.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let shutdown = CancellationToken::new();
+    tokio::spawn(
+        MetricsServer::new("pm-ingest", metrics.registry()).serve(listener, shutdown.clone()),
+    );
+
+    let finished = run_until(
+        orchestrator,
+        Duration::from_secs(15),
+        Duration::from_secs(5),
+        || {
+            let metrics = metrics.clone();
+            async move {
+                metrics
+                    .loops
+                    .rows_upserted_total
+                    .with_label_values(&["quotes_latest"])
+                    .get()
+                    > 0
+            }
+        },
+    )
+    .await;
+    assert!(finished, "replay never upserted quotes");
+
+    let body = reqwest::get(format!("{base}/metrics"))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    for series in [
+        r#"pm_ingest_cycles_total{result="ok",task="discovery"}"#,
+        r#"pm_ingest_cycles_total{result="ok",task="quote_polling"}"#,
+        r#"pm_ingest_rows_upserted_total{table="markets"}"#,
+        r#"pm_ingest_venue_requests_total{method="discover_markets",result="ok"}"#,
+        r#"pm_ingest_venue_request_duration_seconds_count{method="get_quotes"}"#,
+    ] {
+        assert!(body.contains(series), "missing {series} in:\n{body}");
+    }
+
+    let health: WorkerHealth = reqwest::get(format!("{base}/healthz"))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(health.service, "pm-ingest");
+    assert_eq!(health.status, "ok");
+
+    shutdown.cancel();
+}
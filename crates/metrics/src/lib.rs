@@ -0,0 +1,25 @@
+//! PM Endgame Sweep - Shared worker metrics
+//!
+//! Metrics common to the `pm-ingest` and `pm-score` workers, and the small
+//! HTTP listener each of them runs to serve `/metrics` and `/healthz`.
+
+pub mod loops;
+pub mod server;
+
+pub use loops::{CycleTimer, LoopMetrics};
+use prometheus::{Encoder, Registry, TextEncoder};
+pub use server::{MetricsServer, MetricsServerConfig, WorkerHealth};
+
+/// Buckets for loop and request durations (seconds)
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Encode a registry in Prometheus text format
+pub fn render(registry: &Registry) -> Result<String, prometheus::Error> {
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    encoder.encode(&registry.gather(), &mut buffer)?;
+    String::from_utf8(buffer)
+        .map_err(|e| prometheus::Error::Msg(format!("Failed to encode metrics: {}", e)))
+}
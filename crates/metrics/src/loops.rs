@@ -0,0 +1,165 @@
+//! Metrics for periodic worker loops
+
+use std::time::Instant;
+
+use prometheus::{histogram_opts, opts, HistogramVec, IntCounterVec, Registry};
+
+use crate::DURATION_BUCKETS;
+
+/// Cycle, row and skip counts shared by every worker loop
+///
+/// Metric names are prefixed with the service, e.g.
+/// `pm_ingest_cycle_duration_seconds{task="discovery"}`.
+#[derive(Clone)]
+pub struct LoopMetrics {
+    /// Duration of one loop cycle, by task
+    pub cycle_duration_seconds: HistogramVec,
+
+    /// Completed loop cycles, by task and result (`ok` or `error`)
+    pub cycles_total: IntCounterVec,
+
+    /// Rows written to the database, by table
+    pub rows_upserted_total: IntCounterVec,
+
+    /// Markets left out of a cycle, by reason
+    pub markets_skipped_total: IntCounterVec,
+}
+
+impl LoopMetrics {
+    /// Create the metrics as `<prefix>_*` and register them into `registry`
+    pub fn new(registry: &Registry, prefix: &str) -> Result<Self, prometheus::Error> {
+        let cycle_duration_seconds = HistogramVec::new(
+            histogram_opts!(
+                format!("{prefix}_cycle_duration_seconds"),
+                "Duration of one worker loop cycle",
+                DURATION_BUCKETS.to_vec()
+            ),
+            &["task"],
+        )?;
+        registry.register(Box::new(cycle_duration_seconds.clone()))?;
+
+        let cycles_total = IntCounterVec::new(
+            opts!(
+                format!("{prefix}_cycles_total"),
+                "Worker loop cycles by result"
+            ),
+            &["task", "result"],
+        )?;
+        registry.register(Box::new(cycles_total.clone()))?;
+
+        let rows_upserted_total = IntCounterVec::new(
+            opts!(
+                format!("{prefix}_rows_upserted_total"),
+                "Rows inserted or updated by table"
+            ),
+            &["table"],
+        )?;
+        registry.register(Box::new(rows_upserted_total.clone()))?;
+
+        let markets_skipped_total = IntCounterVec::new(
+            opts!(
+                format!("{prefix}_markets_skipped_total"),
+                "Markets left out of a cycle by reason"
+            ),
+            &["reason"],
+        )?;
+        registry.register(Box::new(markets_skipped_total.clone()))?;
+
+        Ok(Self {
+            cycle_duration_seconds,
+            cycles_total,
+            rows_upserted_total,
+            markets_skipped_total,
+        })
+    }
+
+    /// Start timing one cycle of `task`; recorded when the timer drops
+    pub fn cycle(&self, task: &'static str) -> CycleTimer {
+        CycleTimer {
+            metrics: self.clone(),
+            task,
+            started: Instant::now(),
+            failed: false,
+        }
+    }
+
+    /// Count rows written to `table`
+    pub fn record_rows(&self, table: &str, count: usize) {
+        self.rows_upserted_total
+            .with_label_values(&[table])
+            .inc_by(count as u64);
+    }
+
+    /// Count markets skipped for `reason`
+    pub fn record_skipped(&self, reason: &str, count: usize) {
+        if count > 0 {
+            self.markets_skipped_total
+                .with_label_values(&[reason])
+                .inc_by(count as u64);
+        }
+    }
+}
+
+/// Times one loop cycle
+///
+/// Dropping the timer records the cycle, as `error` if [`fail`] was
+/// called and `ok` otherwise, so early exits from a cycle are still counted.
+///
+/// [`fail`]: CycleTimer::fail
+pub struct CycleTimer {
+    metrics: LoopMetrics,
+    task: &'static str,
+    started: Instant,
+    failed: bool,
+}
+
+impl CycleTimer {
+    /// Record this cycle as failed
+    pub fn fail(&mut self) {
+        self.failed = true;
+    }
+}
+
+impl Drop for CycleTimer {
+    fn drop(&mut self) {
+        let result = if self.failed { "error" } else { "ok" };
+        self.metrics
+            .cycle_duration_seconds
+            .with_label_values(&[self.task])
+            .observe(self.started.elapsed().as_secs_f64());
+        self.metrics
+            .cycles_total
+            .with_label_values(&[self.task, result])
+            .inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_timer_records_result_on_drop() {
+        let registry = Registry::new();
+        let metrics = LoopMetrics::new(&registry, "pm_test").unwrap();
+
+        drop(metrics.cycle("poll"));
+        let mut failed = metrics.cycle("poll");
+        failed.fail();
+        drop(failed);
+
+        let ok = metrics.cycles_total.with_label_values(&["poll", "ok"]);
+        let error = metrics.cycles_total.with_label_values(&["poll", "error"]);
+        assert_eq!((ok.get(), error.get()), (1, 1));
+        assert_eq!(
+            metrics
+                .cycle_duration_seconds
+                .with_label_values(&["poll"])
+                .get_sample_count(),
+            2
+        );
+
+        metrics.record_skipped("stale", 0);
+        assert!(!crate::render(&registry).unwrap().contains("skipped_total{"));
+    }
+}
@@ -0,0 +1,188 @@
+//! `/metrics` and `/healthz` listener for worker services
+
+use std::{sync::Arc, time::Instant};
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+/// Metrics listener settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsServerConfig {
+    /// Serve `/metrics` and `/healthz`
+    pub enabled: bool,
+
+    /// HTTP server bind address
+    pub bind_addr: String,
+
+    /// HTTP server port
+    pub port: u16,
+}
+
+impl MetricsServerConfig {
+    /// Listener on `port` on every interface
+    pub fn on_port(port: u16) -> Self {
+        Self {
+            enabled: true,
+            bind_addr: "0.0.0.0".to_string(),
+            port,
+        }
+    }
+
+    /// `bind_addr:port`
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.bind_addr, self.port)
+    }
+}
+
+/// Body of `/healthz`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerHealth {
+    pub service: String,
+    /// `ok` while working, `standby` while another replica leads
+    pub status: String,
+    pub leader: bool,
+    pub uptime_sec: u64,
+}
+
+type LeaderProbe = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Serves one worker's registry and health
+#[derive(Clone)]
+pub struct MetricsServer {
+    service: String,
+    registry: Arc<Registry>,
+    leader: Option<LeaderProbe>,
+    started: Instant,
+}
+
+impl MetricsServer {
+    /// Serve `registry` for `service`
+    pub fn new(service: &str, registry: Arc<Registry>) -> Self {
+        Self {
+            service: service.to_string(),
+            registry,
+            leader: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Report leadership from `probe` in `/healthz`; without one the
+    /// worker is always reported as leading
+    pub fn with_leader(mut self, probe: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.leader = Some(Arc::new(probe));
+        self
+    }
+
+    /// Current health
+    pub fn health(&self) -> WorkerHealth {
+        let leader = self.leader.as_ref().is_none_or(|probe| probe());
+        WorkerHealth {
+            service: self.service.clone(),
+            status: if leader { "ok" } else { "standby" }.to_string(),
+            leader,
+            uptime_sec: self.started.elapsed().as_secs(),
+        }
+    }
+
+    /// Metrics in Prometheus text format
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        crate::render(&self.registry)
+    }
+
+    /// Routes for `/metrics` and `/healthz`
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/metrics", get(metrics_handler))
+            .route("/healthz", get(health_handler))
+            .with_state(self)
+    }
+
+    /// Serve on `listener` until `cancellation` fires
+    pub async fn serve(
+        self,
+        listener: TcpListener,
+        cancellation: CancellationToken,
+    ) -> std::io::Result<()> {
+        if let Ok(addr) = listener.local_addr() {
+            tracing::info!(service = %self.service, %addr, "Serving metrics");
+        }
+        axum::serve(listener, self.router())
+            .with_graceful_shutdown(async move { cancellation.cancelled().await })
+            .await
+    }
+
+    /// Bind `config` and serve in the background until `cancellation`
+    /// fires; does nothing if disabled
+    pub async fn spawn(
+        self,
+        config: &MetricsServerConfig,
+        cancellation: CancellationToken,
+    ) -> anyhow::Result<()> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let listener = TcpListener::bind(config.addr()).await?;
+        tokio::spawn(async move {
+            if let Err(e) = self.serve(listener, cancellation).await {
+                tracing::error!(error = %e, "Metrics server failed");
+            }
+        });
+        Ok(())
+    }
+}
+
+async fn metrics_handler(State(server): State<MetricsServer>) -> Result<String, StatusCode> {
+    server.render().map_err(|e| {
+        tracing::error!(error = %e, "Failed to render metrics");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+async fn health_handler(State(server): State<MetricsServer>) -> Json<WorkerHealth> {
+    Json(server.health())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use prometheus::IntCounter;
+
+    use super::*;
+
+    #[test]
+    fn test_health_reflects_leadership() {
+        let registry = Arc::new(Registry::new());
+        let leading = Arc::new(AtomicBool::new(false));
+        let server = MetricsServer::new("pm-test", Arc::clone(&registry)).with_leader({
+            let leading = Arc::clone(&leading);
+            move || leading.load(Ordering::SeqCst)
+        });
+
+        let standby = server.health();
+        assert_eq!(standby.status, "standby");
+        assert!(!standby.leader);
+
+        leading.store(true, Ordering::SeqCst);
+        assert_eq!(server.health().status, "ok");
+        assert_eq!(
+            MetricsServer::new("pm-test", registry).health().status,
+            "ok"
+        );
+    }
+
+    #[test]
+    fn test_render_includes_registered_metrics() {
+        let registry = Arc::new(Registry::new());
+        let counter = IntCounter::new("pm_test_total", "Test counter").unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        counter.inc_by(3);
+
+        let body = MetricsServer::new("pm-test", registry).render().unwrap();
+        assert!(body.contains("pm_test_total 3"));
+    }
+}
@@ -0,0 +1,10 @@
+//! PM Endgame Sweep - Mock venue
+//!
+//! Serves Polymarket-compatible gamma and CLOB endpoints from a scripted
+//! scenario, so the stack can run end to end without the live venue.
+
+pub mod scenario;
+pub mod server;
+
+pub use scenario::Scenario;
+pub use server::{router, MockVenue};
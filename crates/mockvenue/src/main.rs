@@ -0,0 +1,44 @@
+//! PM Endgame Sweep - Mock venue
+//!
+//! Serves Polymarket-compatible gamma and CLOB endpoints from a scripted
+//! scenario, so the stack can run end to end without the live venue.
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use pm_mockvenue::{router, MockVenue, Scenario};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "pm_mockvenue=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer().json())
+        .init();
+
+    let path = std::env::args()
+        .nth(1)
+        .context("usage: pm-mockvenue <scenario.json>")?;
+    let scenario = Scenario::load(&path).with_context(|| format!("loading {}", path))?;
+
+    let addr = std::env::var("MOCKVENUE_ADDR").unwrap_or_else(|_| "127.0.0.1:8090".to_string());
+
+    tracing::info!(
+        scenario = %path,
+        markets = scenario.markets.len(),
+        step_ms = scenario.step_ms,
+        "pm-mockvenue starting on {}",
+        addr
+    );
+
+    let app = router(Arc::new(MockVenue::new(scenario)));
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| anyhow::anyhow!("Server error: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,425 @@
+//! Scripted market scenarios
+//!
+//! A scenario advances in discrete steps (one every `step_ms`). Each market's
+//! YES mid price follows linear interpolation between keyframes, shifted by
+//! any jumps that have already happened. Rule edits, lifecycle changes and
+//! venue faults are all keyed by step.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Error type for scenario loading
+#[derive(Debug, thiserror::Error)]
+pub enum ScenarioError {
+    #[error("Failed to read scenario: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse scenario: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid scenario: {0}")]
+    Invalid(String),
+}
+
+pub type Result<T> = std::result::Result<T, ScenarioError>;
+
+/// A scripted set of markets and venue faults
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Wall-clock duration of one scenario step (milliseconds)
+    #[serde(default = "default_step_ms")]
+    pub step_ms: u64,
+
+    /// Markets served by the mock venue
+    pub markets: Vec<ScenarioMarket>,
+
+    /// Scripted venue faults
+    #[serde(default)]
+    pub faults: Vec<Fault>,
+}
+
+/// A single scripted market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioMarket {
+    /// Condition id, used as the venue market id
+    pub condition_id: String,
+
+    /// Market question
+    pub question: String,
+
+    /// URL slug (derived from the condition id when absent)
+    #[serde(default)]
+    pub slug: Option<String>,
+
+    /// Market category
+    #[serde(default)]
+    pub category: Option<String>,
+
+    /// Close time relative to server start (seconds)
+    pub closes_in_sec: i64,
+
+    /// Initial rule text
+    pub description: String,
+
+    /// Resolution source
+    #[serde(default)]
+    pub resolution_source: Option<String>,
+
+    /// CLOB token id of the YES outcome
+    pub yes_token_id: String,
+
+    /// CLOB token id of the NO outcome
+    pub no_token_id: String,
+
+    /// YES mid price keyframes; linear between them, flat outside
+    pub price_path: Vec<PricePoint>,
+
+    /// Bid/ask spread around the mid
+    #[serde(default = "default_spread")]
+    pub spread: f64,
+
+    /// Size of the NO fill printed each step while the market is open
+    #[serde(default = "default_trade_size")]
+    pub trade_size: f64,
+
+    /// Sudden mid price moves, applied from their step onwards
+    #[serde(default)]
+    pub jumps: Vec<Jump>,
+
+    /// Rule text replacements
+    #[serde(default)]
+    pub rule_edits: Vec<RuleEdit>,
+
+    /// Step at which the market stops trading
+    #[serde(default)]
+    pub closes_at_step: Option<u64>,
+
+    /// Step at which the market is resolved
+    #[serde(default)]
+    pub resolves_at_step: Option<u64>,
+
+    /// Step after which the venue no longer lists the market
+    #[serde(default)]
+    pub delisted_at_step: Option<u64>,
+
+    /// Extra gamma fields passed through verbatim (events, tags, volume,
+    /// liquidity, negRisk, ...)
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// YES mid price at a step
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub step: u64,
+    pub yes_mid: f64,
+}
+
+/// Sudden YES mid price change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Jump {
+    pub step: u64,
+    pub delta: f64,
+}
+
+/// Rule text replacement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleEdit {
+    pub step: u64,
+    pub description: String,
+}
+
+/// A venue fault active over an inclusive step range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fault {
+    pub from_step: u64,
+    pub to_step: u64,
+
+    /// Endpoints affected; all when empty
+    #[serde(default)]
+    pub endpoints: Vec<Endpoint>,
+
+    #[serde(flatten)]
+    pub kind: FaultKind,
+}
+
+/// What the venue returns while a fault is active
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FaultKind {
+    /// `429 Too Many Requests` with a `Retry-After` header
+    RateLimit {
+        #[serde(default = "default_retry_after_sec")]
+        retry_after_sec: u64,
+    },
+    /// Bare error status (`503` by default)
+    Outage {
+        #[serde(default = "default_outage_status")]
+        status: u16,
+    },
+}
+
+/// Mock venue endpoint classes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    /// `GET /markets`
+    Markets,
+    /// `GET /markets/{id}`
+    Market,
+    /// `GET /markets/{id}/book` and `GET /book`
+    Book,
+    /// `GET /trades`
+    Trades,
+}
+
+/// Lifecycle state of a market at a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    Open,
+    Closed,
+    Resolved,
+    Delisted,
+}
+
+/// Top of book for both outcomes at a step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Book {
+    pub yes_bid: f64,
+    pub yes_ask: f64,
+    pub no_bid: f64,
+    pub no_ask: f64,
+}
+
+fn default_step_ms() -> u64 {
+    1000
+}
+
+fn default_spread() -> f64 {
+    0.01
+}
+
+fn default_trade_size() -> f64 {
+    100.0
+}
+
+fn default_retry_after_sec() -> u64 {
+    1
+}
+
+fn default_outage_status() -> u16 {
+    503
+}
+
+/// Lowest and highest price a book level may take
+const MIN_PRICE: f64 = 0.001;
+const MAX_PRICE: f64 = 0.999;
+
+impl Scenario {
+    /// Load and validate a scenario from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let scenario: Scenario = serde_json::from_slice(&bytes)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Check the scenario is internally consistent
+    pub fn validate(&self) -> Result<()> {
+        if self.step_ms == 0 {
+            return Err(ScenarioError::Invalid(
+                "step_ms must be positive".to_string(),
+            ));
+        }
+
+        for market in &self.markets {
+            if market.price_path.is_empty() {
+                return Err(ScenarioError::Invalid(format!(
+                    "Market {} has an empty price path",
+                    market.condition_id
+                )));
+            }
+            if market.price_path.windows(2).any(|w| w[0].step >= w[1].step) {
+                return Err(ScenarioError::Invalid(format!(
+                    "Market {} price path steps must be increasing",
+                    market.condition_id
+                )));
+            }
+        }
+
+        for fault in &self.faults {
+            if fault.from_step > fault.to_step {
+                return Err(ScenarioError::Invalid(format!(
+                    "Fault range {}..={} is empty",
+                    fault.from_step, fault.to_step
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find a market by condition id
+    pub fn market(&self, condition_id: &str) -> Option<&ScenarioMarket> {
+        self.markets.iter().find(|m| m.condition_id == condition_id)
+    }
+
+    /// Find the market owning a CLOB token
+    pub fn market_for_token(&self, token_id: &str) -> Option<&ScenarioMarket> {
+        self.markets
+            .iter()
+            .find(|m| m.yes_token_id == token_id || m.no_token_id == token_id)
+    }
+
+    /// First fault active for an endpoint at a step
+    pub fn active_fault(&self, endpoint: Endpoint, step: u64) -> Option<&Fault> {
+        self.faults.iter().find(|f| {
+            (f.from_step..=f.to_step).contains(&step)
+                && (f.endpoints.is_empty() || f.endpoints.contains(&endpoint))
+        })
+    }
+}
+
+impl ScenarioMarket {
+    /// YES mid price at a step
+    pub fn yes_mid(&self, step: u64) -> f64 {
+        let path = &self.price_path;
+        let base = match path.iter().position(|p| p.step > step) {
+            Some(0) => path[0].yes_mid,
+            Some(i) => {
+                let (a, b) = (path[i - 1], path[i]);
+                let t = (step - a.step) as f64 / (b.step - a.step) as f64;
+                a.yes_mid + (b.yes_mid - a.yes_mid) * t
+            }
+            None => path[path.len() - 1].yes_mid,
+        };
+
+        let jumped: f64 = self
+            .jumps
+            .iter()
+            .filter(|j| j.step <= step)
+            .map(|j| j.delta)
+            .sum();
+
+        (base + jumped).clamp(MIN_PRICE, MAX_PRICE)
+    }
+
+    /// Top of book for both outcomes at a step
+    pub fn book(&self, step: u64) -> Book {
+        let mid = self.yes_mid(step);
+        let half = self.spread / 2.0;
+        let yes_bid = (mid - half).clamp(MIN_PRICE, MAX_PRICE);
+        let yes_ask = (mid + half).clamp(MIN_PRICE, MAX_PRICE);
+
+        Book {
+            yes_bid,
+            yes_ask,
+            no_bid: 1.0 - yes_ask,
+            no_ask: 1.0 - yes_bid,
+        }
+    }
+
+    /// Rule text in effect at a step
+    pub fn description(&self, step: u64) -> &str {
+        self.rule_edits
+            .iter()
+            .filter(|e| e.step <= step)
+            .max_by_key(|e| e.step)
+            .map(|e| e.description.as_str())
+            .unwrap_or(&self.description)
+    }
+
+    /// Lifecycle state at a step
+    pub fn lifecycle(&self, step: u64) -> Lifecycle {
+        let reached = |s: Option<u64>| s.is_some_and(|s| s <= step);
+
+        if reached(self.delisted_at_step) {
+            Lifecycle::Delisted
+        } else if reached(self.resolves_at_step) {
+            Lifecycle::Resolved
+        } else if reached(self.closes_at_step) {
+            Lifecycle::Closed
+        } else {
+            Lifecycle::Open
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> ScenarioMarket {
+        serde_json::from_value(serde_json::json!({
+            "condition_id": "0xm1",
+            "question": "Will it converge?",
+            "closes_in_sec": 86400,
+            "description": "Original rules",
+            "yes_token_id": "1",
+            "no_token_id": "2",
+            "price_path": [
+                {"step": 0, "yes_mid": 0.90},
+                {"step": 10, "yes_mid": 0.99}
+            ],
+            "jumps": [{"step": 5, "delta": -0.30}],
+            "rule_edits": [{"step": 7, "description": "Edited rules"}],
+            "closes_at_step": 20,
+            "resolves_at_step": 25,
+            "negRisk": true
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_price_path_interpolates_and_jumps() {
+        let m = market();
+        assert!((m.yes_mid(0) - 0.90).abs() < 1e-9);
+        assert!((m.yes_mid(4) - 0.936).abs() < 1e-9);
+        assert!((m.yes_mid(5) - 0.645).abs() < 1e-9);
+        assert!((m.yes_mid(50) - 0.69).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_book_is_complementary() {
+        let book = market().book(0);
+        assert!((book.yes_bid - 0.895).abs() < 1e-9);
+        assert!((book.no_bid - (1.0 - book.yes_ask)).abs() < 1e-9);
+        assert!((book.no_ask - (1.0 - book.yes_bid)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rule_edits_and_lifecycle() {
+        let m = market();
+        assert_eq!(m.description(6), "Original rules");
+        assert_eq!(m.description(7), "Edited rules");
+        assert_eq!(m.lifecycle(19), Lifecycle::Open);
+        assert_eq!(m.lifecycle(20), Lifecycle::Closed);
+        assert_eq!(m.lifecycle(30), Lifecycle::Resolved);
+        assert_eq!(m.extra.get("negRisk"), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_active_fault() {
+        let scenario: Scenario = serde_json::from_value(serde_json::json!({
+            "markets": [],
+            "faults": [
+                {"kind": "rate_limit", "from_step": 2, "to_step": 3, "endpoints": ["book"]},
+                {"kind": "outage", "from_step": 5, "to_step": 5}
+            ]
+        }))
+        .unwrap();
+
+        assert!(scenario.active_fault(Endpoint::Book, 1).is_none());
+        assert!(matches!(
+            scenario.active_fault(Endpoint::Book, 2).map(|f| &f.kind),
+            Some(FaultKind::RateLimit { retry_after_sec: 1 })
+        ));
+        assert!(scenario.active_fault(Endpoint::Markets, 2).is_none());
+        assert!(matches!(
+            scenario.active_fault(Endpoint::Market, 5).map(|f| &f.kind),
+            Some(FaultKind::Outage { status: 503 })
+        ));
+    }
+}
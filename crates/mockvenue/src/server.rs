@@ -0,0 +1,294 @@
+//! Polymarket-compatible HTTP endpoints backed by a scenario
+
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tower_http::trace::TraceLayer;
+
+use crate::scenario::{Endpoint, FaultKind, Lifecycle, Scenario, ScenarioMarket};
+
+/// Shared state: the scenario and the instant it started
+pub struct MockVenue {
+    scenario: Scenario,
+    started: Instant,
+    started_at: DateTime<Utc>,
+}
+
+impl MockVenue {
+    /// Start a scenario at step zero
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            started: Instant::now(),
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Current scenario step
+    pub fn step(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64 / self.scenario.step_ms
+    }
+
+    /// Gamma market object for a scenario market at a step
+    fn gamma_market(&self, market: &ScenarioMarket, step: u64) -> Value {
+        let lifecycle = market.lifecycle(step);
+        let open = lifecycle == Lifecycle::Open;
+        let end_date = self.started_at + Duration::seconds(market.closes_in_sec);
+
+        let mut value = json!({
+            "conditionId": market.condition_id,
+            "question": market.question,
+            "slug": market.slug.clone().unwrap_or_else(|| market.condition_id.clone()),
+            "category": market.category,
+            "startDate": self.started_at,
+            "endDate": end_date,
+            "description": market.description(step),
+            "resolutionSource": market.resolution_source,
+            // Gamma encodes both lists as JSON strings
+            "outcomes": "[\"Yes\", \"No\"]",
+            "clobTokenIds": serde_json::to_string(&[&market.yes_token_id, &market.no_token_id])
+                .unwrap_or_default(),
+            "active": true,
+            "closed": !open,
+            "archived": false,
+            "acceptingOrders": open,
+            "umaResolutionStatus": (lifecycle == Lifecycle::Resolved).then_some("resolved"),
+        });
+
+        if let Value::Object(fields) = &mut value {
+            for (key, extra) in &market.extra {
+                fields.insert(key.clone(), extra.clone());
+            }
+        }
+
+        value
+    }
+
+    /// CLOB book object for one outcome token at a step
+    fn clob_book(&self, market: &ScenarioMarket, token_id: &str, step: u64) -> Value {
+        let book = market.book(step);
+        let (bid, ask) = if token_id == market.yes_token_id {
+            (book.yes_bid, book.yes_ask)
+        } else {
+            (book.no_bid, book.no_ask)
+        };
+
+        // Two levels per side; the CLOB sends prices and sizes as strings
+        let level = |price: f64, size: u32| json!({ "price": format!("{:.3}", price), "size": size.to_string() });
+        let tick = 0.01;
+
+        json!({
+            "market": market.condition_id,
+            "asset_id": token_id,
+            "bids": [level((bid - tick).max(0.001), 500), level(bid, 200)],
+            "asks": [level((ask + tick).min(0.999), 500), level(ask, 200)],
+        })
+    }
+
+    /// Data API fills for a market, newest first: one NO fill per elapsed
+    /// open step, alternately lifting the ask and hitting the bid
+    fn data_trades(&self, market: &ScenarioMarket, step: u64, limit: usize) -> Vec<Value> {
+        (0..=step)
+            .rev()
+            .filter(|s| market.lifecycle(*s) == Lifecycle::Open)
+            .take(limit)
+            .map(|s| {
+                let book = market.book(s);
+                let (side, price) = if s % 2 == 0 {
+                    ("BUY", book.no_ask)
+                } else {
+                    ("SELL", book.no_bid)
+                };
+                let at =
+                    self.started_at + Duration::milliseconds((s * self.scenario.step_ms) as i64);
+                let tx_hash = format!(
+                    "0x{}{:08x}",
+                    market.condition_id.trim_start_matches("0x"),
+                    s
+                );
+
+                json!({
+                    "proxyWallet": "0x0000000000000000000000000000000000000000",
+                    "side": side,
+                    "asset": market.no_token_id,
+                    "conditionId": market.condition_id,
+                    "size": market.trade_size,
+                    "price": (price * 1000.0).round() / 1000.0,
+                    "timestamp": at.timestamp(),
+                    "outcome": "No",
+                    "outcomeIndex": 1,
+                    "transactionHash": tx_hash,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Build the mock venue router
+pub fn router(venue: Arc<MockVenue>) -> Router {
+    Router::new()
+        .route("/markets", get(markets_handler))
+        .route("/markets/{condition_id}", get(market_handler))
+        .route("/markets/{condition_id}/book", get(market_book_handler))
+        .route("/book", get(book_handler))
+        .route("/trades", get(trades_handler))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&venue),
+            fault_middleware,
+        ))
+        .layer(TraceLayer::new_for_http())
+        .with_state(venue)
+}
+
+/// Endpoint class for a request path
+fn endpoint_for(path: &str) -> Endpoint {
+    if path == "/book" || path.ends_with("/book") {
+        Endpoint::Book
+    } else if path == "/trades" {
+        Endpoint::Trades
+    } else if path == "/markets" {
+        Endpoint::Markets
+    } else {
+        Endpoint::Market
+    }
+}
+
+/// Answer with the scripted fault, if one is active for this request
+async fn fault_middleware(
+    State(venue): State<Arc<MockVenue>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let step = venue.step();
+    let endpoint = endpoint_for(request.uri().path());
+
+    match venue.scenario.active_fault(endpoint, step).map(|f| &f.kind) {
+        Some(FaultKind::RateLimit { retry_after_sec }) => {
+            tracing::info!(step, ?endpoint, "Injecting rate limit");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_sec.to_string())],
+                "rate limited",
+            )
+                .into_response()
+        }
+        Some(FaultKind::Outage { status }) => {
+            tracing::info!(step, ?endpoint, status, "Injecting outage");
+            StatusCode::from_u16(*status)
+                .unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
+                .into_response()
+        }
+        None => next.run(request).await,
+    }
+}
+
+/// Pagination parameters for `GET /markets`
+#[derive(Debug, Deserialize)]
+struct MarketsQuery {
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+async fn markets_handler(
+    State(venue): State<Arc<MockVenue>>,
+    Query(params): Query<MarketsQuery>,
+) -> Json<Vec<Value>> {
+    let step = venue.step();
+    let limit = params.limit.unwrap_or(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let markets = venue
+        .scenario
+        .markets
+        .iter()
+        .filter(|m| m.lifecycle(step) != Lifecycle::Delisted)
+        .skip(offset)
+        .take(limit)
+        .map(|m| venue.gamma_market(m, step))
+        .collect();
+
+    Json(markets)
+}
+
+/// Look up a listed market, or 404
+fn listed_market<'a>(
+    venue: &'a MockVenue,
+    condition_id: &str,
+    step: u64,
+) -> Result<&'a ScenarioMarket, StatusCode> {
+    venue
+        .scenario
+        .market(condition_id)
+        .filter(|m| m.lifecycle(step) != Lifecycle::Delisted)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn market_handler(
+    State(venue): State<Arc<MockVenue>>,
+    Path(condition_id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let step = venue.step();
+    let market = listed_market(&venue, &condition_id, step)?;
+    Ok(Json(venue.gamma_market(market, step)))
+}
+
+async fn market_book_handler(
+    State(venue): State<Arc<MockVenue>>,
+    Path(condition_id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let step = venue.step();
+    let market = listed_market(&venue, &condition_id, step)?;
+
+    Ok(Json(json!({
+        "yes": venue.clob_book(market, &market.yes_token_id, step),
+        "no": venue.clob_book(market, &market.no_token_id, step),
+    })))
+}
+
+/// Query parameters for the CLOB-style `GET /book`
+#[derive(Debug, Deserialize)]
+struct BookQuery {
+    token_id: String,
+}
+
+async fn book_handler(
+    State(venue): State<Arc<MockVenue>>,
+    Query(params): Query<BookQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let step = venue.step();
+    let market = venue
+        .scenario
+        .market_for_token(&params.token_id)
+        .filter(|m| m.lifecycle(step) == Lifecycle::Open)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(venue.clob_book(market, &params.token_id, step)))
+}
+
+/// Query parameters for the data API `GET /trades`
+#[derive(Debug, Deserialize)]
+struct TradesQuery {
+    market: String,
+    limit: Option<usize>,
+}
+
+async fn trades_handler(
+    State(venue): State<Arc<MockVenue>>,
+    Query(params): Query<TradesQuery>,
+) -> Result<Json<Vec<Value>>, StatusCode> {
+    let step = venue.step();
+    let market = listed_market(&venue, &params.market, step)?;
+    let limit = params.limit.unwrap_or(100);
+
+    Ok(Json(venue.data_trades(market, step, limit)))
+}
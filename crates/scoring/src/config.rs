@@ -1,24 +1,76 @@
 //! Scoring service configuration
 
+use pm_metrics::MetricsServerConfig;
+use pm_storage::leader::LeaderConfig;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for scoring service
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoringConfig {
-    /// How often to run scoring (seconds)
+    /// How often to run a full scoring sweep (seconds)
     pub cadence_sec: u64,
 
+    /// Re-scoring of changed markets between full sweeps
+    #[serde(default)]
+    pub incremental: IncrementalConfig,
+
     /// Scoring weights
     pub weights: ScoringWeights,
 
     /// Bounds for eligibility
     pub bounds: ScoringBounds,
 
+    /// Hard eligibility gates on quote freshness and rule snapshots
+    #[serde(default)]
+    pub gates: EligibilityGates,
+
+    /// Normalization of yield features for the overall score
+    #[serde(default)]
+    pub normalization: NormalizationConfig,
+
     /// Fee configuration (basis points)
     pub fee_bps: f64,
 
     /// Sizing configuration
     pub sizing: SizingConfig,
+
+    /// Optional venue activity inputs (volume, displayed liquidity)
+    #[serde(default)]
+    pub activity: ActivityConfig,
+
+    /// Leader election between scoring replicas
+    #[serde(default)]
+    pub leader: LeaderConfig,
+
+    /// Weighted-sum variants scored in shadow each cycle, into
+    /// `scores_shadow`
+    #[serde(default)]
+    pub shadow_models: Vec<ShadowModelConfig>,
+
+    /// `/metrics` and `/healthz` listener
+    #[serde(default = "default_metrics_server")]
+    pub metrics: MetricsServerConfig,
+}
+
+/// Re-scoring of markets whose quotes or rules changed
+///
+/// Ingest notifies changed market ids over Postgres `NOTIFY`; scoring
+/// collects them for `debounce_ms` and re-scores just those markets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalConfig {
+    /// Listen for change notifications from ingest
+    pub enabled: bool,
+    /// How long to collect changed markets before re-scoring them
+    pub debounce_ms: u64,
+}
+
+impl Default for IncrementalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            debounce_ms: 2000,
+        }
+    }
 }
 
 /// Weights for overall score computation
@@ -51,21 +103,151 @@ pub struct ScoringBounds {
     pub spread_target: f64,
 }
 
+/// Hard eligibility gates
+///
+/// The defaults are the SPEC gates: a quote within
+/// `bounds.quote_stale_max_sec` and an existing rule snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EligibilityGates {
+    /// Exclude markets whose quote is older than `bounds.quote_stale_max_sec`
+    /// instead of only penalizing staleness
+    pub require_fresh_quote: bool,
+    /// Handling of markets without a rule snapshot
+    pub missing_rules: MissingRulesPolicy,
+    /// Definition risk score assumed under the `default_risk` policy
+    pub missing_rules_risk: f64,
+}
+
+/// How markets without a rule snapshot are scored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingRulesPolicy {
+    /// Leave the market unscored
+    #[default]
+    Exclude,
+    /// Score it with `missing_rules_risk` as its definition risk
+    DefaultRisk,
+}
+
+/// Normalization of yield features into `[0, 1]` before weighting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationConfig {
+    /// How features are normalized
+    pub method: NormalizationMethod,
+    /// Min-max range for yield velocity
+    pub yield_velocity: FeatureRange,
+    /// Min-max range for net yield
+    pub net_yield: FeatureRange,
+    /// Z-scores at or beyond this many standard deviations normalize to
+    /// 0 or 1
+    pub z_clip: f64,
+}
+
+/// How yield features are normalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationMethod {
+    /// Min-max against the configured feature ranges
+    #[default]
+    MinMax,
+    /// Percentile rank among the cycle's eligible markets
+    Percentile,
+    /// Clipped z-score among the cycle's eligible markets
+    ZScore,
+}
+
+impl NormalizationMethod {
+    /// Whether features are normalized against the cycle's eligible set
+    /// rather than fixed ranges
+    pub fn is_universe_relative(self) -> bool {
+        !matches!(self, Self::MinMax)
+    }
+}
+
+/// Min-max bounds for one feature
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeatureRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Variant of the weighted-sum model scored in shadow
+///
+/// Unset fields keep the primary model's settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowModelConfig {
+    /// Model id tagging the variant's rows in `scores_shadow`
+    pub id: String,
+    /// Replacement scoring weights
+    #[serde(default)]
+    pub weights: Option<ScoringWeights>,
+    /// Replacement feature normalization
+    #[serde(default)]
+    pub normalization: Option<NormalizationConfig>,
+}
+
+impl ShadowModelConfig {
+    /// `base` with this variant's overrides applied
+    pub fn apply(&self, base: &ScoringConfig) -> ScoringConfig {
+        let mut config = base.clone();
+        if let Some(weights) = &self.weights {
+            config.weights = weights.clone();
+        }
+        if let Some(normalization) = &self.normalization {
+            config.normalization = normalization.clone();
+        }
+        config.shadow_models.clear();
+        config
+    }
+}
+
 /// Position sizing configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SizingConfig {
     /// Base position size as percentage of NAV
     pub base_position_pct: f64,
+    /// Withhold recommendations whose aggregate risk score exceeds this;
+    /// disabled when unset
+    #[serde(default)]
+    pub max_risk_score: Option<f64>,
+}
+
+/// Optional scoring inputs from venue-reported market activity
+///
+/// All inputs are disabled by default; markets without the metadata are
+/// never excluded by them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityConfig {
+    /// Minimum 24h traded volume (USD) for eligibility
+    pub min_volume_24h: Option<f64>,
+    /// Minimum displayed liquidity (USD) for eligibility
+    pub min_liquidity: Option<f64>,
+    /// Displayed liquidity (USD) at which the depth score saturates; when
+    /// set, depth is blended into the liquidity score
+    pub liquidity_target: Option<f64>,
+}
+
+fn default_metrics_server() -> MetricsServerConfig {
+    MetricsServerConfig::on_port(9102)
 }
 
 impl Default for ScoringConfig {
     fn default() -> Self {
         Self {
             cadence_sec: 120,
+            incremental: IncrementalConfig::default(),
             weights: ScoringWeights::default(),
             bounds: ScoringBounds::default(),
+            gates: EligibilityGates::default(),
+            normalization: NormalizationConfig::default(),
             fee_bps: 120.0, // 1.2%
             sizing: SizingConfig::default(),
+            activity: ActivityConfig::default(),
+            leader: LeaderConfig::default(),
+            shadow_models: Vec::new(),
+            metrics: default_metrics_server(),
         }
     }
 }
@@ -94,10 +276,61 @@ impl Default for ScoringBounds {
     }
 }
 
+impl Default for EligibilityGates {
+    fn default() -> Self {
+        Self {
+            require_fresh_quote: true,
+            missing_rules: MissingRulesPolicy::Exclude,
+            missing_rules_risk: 0.8,
+        }
+    }
+}
+
+impl Default for NormalizationConfig {
+    fn default() -> Self {
+        Self {
+            method: NormalizationMethod::MinMax,
+            yield_velocity: FeatureRange { min: 0.0, max: 1.0 },
+            net_yield: FeatureRange { min: 0.0, max: 0.5 },
+            z_clip: 3.0,
+        }
+    }
+}
+
 impl Default for SizingConfig {
     fn default() -> Self {
         Self {
             base_position_pct: 0.10, // 10% NAV max
+            max_risk_score: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_normalization_config_uses_defaults() {
+        let config: NormalizationConfig =
+            serde_json::from_value(serde_json::json!({"method": "percentile"})).unwrap();
+        let defaults = NormalizationConfig::default();
+
+        assert_eq!(config.method, NormalizationMethod::Percentile);
+        assert_eq!(config.z_clip, defaults.z_clip);
+        assert_eq!(config.net_yield.max, defaults.net_yield.max);
+    }
+
+    #[test]
+    fn test_partial_eligibility_gates_use_defaults() {
+        let gates: EligibilityGates =
+            serde_json::from_value(serde_json::json!({"missing_rules": "default_risk"})).unwrap();
+
+        assert_eq!(gates.missing_rules, MissingRulesPolicy::DefaultRisk);
+        assert!(gates.require_fresh_quote);
+        assert_eq!(
+            gates.missing_rules_risk,
+            EligibilityGates::default().missing_rules_risk
+        );
+    }
+}
@@ -2,50 +2,82 @@
 
 use std::collections::HashMap;
 
-use chrono::{DateTime, Utc};
-use pm_domain::{Market, Quote, Recommendation, RuleSnapshot, Score};
-use serde_json::json;
+use chrono::{DateTime, Duration, Utc};
+use pm_domain::{IneligibleReason, Market, Quote, Recommendation, RuleSnapshot, Score, TradeStats};
+use serde_json::{json, Value};
 
-use crate::config::ScoringConfig;
+use crate::{
+    config::{MissingRulesPolicy, NormalizationMethod, ScoringConfig},
+    model::{self, ScoredBatch, ScoringModel},
+    normalize,
+};
 
 /// Error type for scoring operations
 #[derive(Debug, thiserror::Error)]
 pub enum ScoringError {
-    #[error("Missing quote for market: {0}")]
-    MissingQuote(String),
-
-    #[error("Missing rule for market: {0}")]
-    MissingRule(String),
+    #[error("Market {market_id} is not eligible ({reason}): {detail}")]
+    Ineligible {
+        market_id: String,
+        reason: IneligibleReason,
+        detail: String,
+    },
+}
 
-    #[error("Invalid market state: {0}")]
-    InvalidMarket(String),
+impl ScoringError {
+    /// `market` failed the `reason` eligibility gate
+    pub fn ineligible(
+        market: &Market,
+        reason: IneligibleReason,
+        detail: impl Into<String>,
+    ) -> Self {
+        ScoringError::Ineligible {
+            market_id: market.market_id.clone(),
+            reason,
+            detail: detail.into(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, ScoringError>;
 
+/// Window for traded-notional and trade-count features
+pub const TRADE_WINDOW: Duration = Duration::hours(1);
+
+/// Id of the default weighted-sum model
+pub const DEFAULT_MODEL_ID: &str = "weighted_sum";
+
 /// Scoring engine for computing opportunity scores
+///
+/// The default [`ScoringModel`]: a weighted sum of normalized yield,
+/// liquidity and risk features.
 pub struct ScoringEngine {
+    id: String,
     config: ScoringConfig,
 }
 
 impl ScoringEngine {
     /// Create a new scoring engine
     pub fn new(config: ScoringConfig) -> Self {
-        Self { config }
+        Self {
+            id: DEFAULT_MODEL_ID.to_string(),
+            config,
+        }
     }
 
-    /// Compute score for a market
-    pub fn compute_score(
-        &self,
-        market: &Market,
-        quote: &Quote,
-        rule: Option<&RuleSnapshot>,
-        now: DateTime<Utc>,
-    ) -> Result<Score> {
+    /// Tag this engine's scores as model `id`, e.g. to run a variant of
+    /// the formula in shadow
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    /// Seconds until the market closes, or fail if it has no close time or
+    /// closes outside the scoring window
+    pub fn time_remaining(&self, market: &Market, now: DateTime<Utc>) -> Result<i64> {
         // Validate market has close_time
-        let close_time = market
-            .close_time
-            .ok_or_else(|| ScoringError::InvalidMarket(market.market_id.clone()))?;
+        let close_time = market.close_time.ok_or_else(|| {
+            ScoringError::ineligible(market, IneligibleReason::MissingCloseTime, "No close time")
+        })?;
 
         // Calculate time remaining
         let t_remaining_sec = (close_time - now).num_seconds();
@@ -54,23 +86,92 @@ impl ScoringEngine {
         if t_remaining_sec < self.config.bounds.min_t_remaining_sec
             || t_remaining_sec > self.config.bounds.max_t_remaining_sec
         {
-            return Err(ScoringError::InvalidMarket(format!(
-                "Time remaining {} outside bounds",
-                t_remaining_sec
-            )));
+            return Err(ScoringError::ineligible(
+                market,
+                IneligibleReason::OutsideWindow,
+                format!("Time remaining {}s outside bounds", t_remaining_sec),
+            ));
+        }
+
+        Ok(t_remaining_sec)
+    }
+
+    /// Compute score for a market
+    pub fn compute_score(
+        &self,
+        market: &Market,
+        quote: &Quote,
+        rule: Option<&RuleSnapshot>,
+        trades: Option<&TradeStats>,
+        now: DateTime<Utc>,
+    ) -> Result<Score> {
+        let t_remaining_sec = self.time_remaining(market, now)?;
+
+        // Optional activity floors, only applied when the venue reports them
+        let activity = &self.config.activity;
+        let metadata = &market.metadata;
+        if let (Some(min), Some(volume)) = (activity.min_volume_24h, metadata.volume_24h) {
+            if volume < min {
+                return Err(ScoringError::ineligible(
+                    market,
+                    IneligibleReason::LowVolume,
+                    format!("24h volume {} below minimum {}", volume, min),
+                ));
+            }
+        }
+        if let (Some(min), Some(liquidity)) = (activity.min_liquidity, metadata.liquidity) {
+            if liquidity < min {
+                return Err(ScoringError::ineligible(
+                    market,
+                    IneligibleReason::LowLiquidity,
+                    format!("Liquidity {} below minimum {}", liquidity, min),
+                ));
+            }
         }
 
         // Calculate staleness
+        let gates = &self.config.gates;
         let staleness_sec = (now - quote.as_of).num_seconds();
+        if gates.require_fresh_quote && staleness_sec > self.config.bounds.quote_stale_max_sec {
+            return Err(ScoringError::ineligible(
+                market,
+                IneligibleReason::StaleQuote,
+                format!(
+                    "Quote is {}s old, limit {}s",
+                    staleness_sec, self.config.bounds.quote_stale_max_sec
+                ),
+            ));
+        }
         let staleness_penalty = self.calculate_staleness_penalty(staleness_sec);
 
+        // Definition risk score; unknown rules are never treated as safe
+        let definition_risk_score = match (rule, gates.missing_rules) {
+            (Some(rule), _) => rule.definition_risk_score,
+            (None, MissingRulesPolicy::DefaultRisk) => gates.missing_rules_risk,
+            (None, MissingRulesPolicy::Exclude) => {
+                return Err(ScoringError::ineligible(
+                    market,
+                    IneligibleReason::MissingRule,
+                    "No rule snapshot",
+                ))
+            }
+        };
+
         // Calculate NO side pricing (we're selling volatility on low-prob outcomes)
-        let no_bid = quote
-            .no_bid
-            .ok_or_else(|| ScoringError::MissingQuote(market.market_id.clone()))?;
-        let no_ask = quote
-            .no_ask
-            .ok_or_else(|| ScoringError::MissingQuote(market.market_id.clone()))?;
+        let no_bid = quote.no_bid.ok_or_else(|| {
+            ScoringError::ineligible(
+                market,
+                IneligibleReason::MissingNoBid,
+                "Quote has no NO bid",
+            )
+        })?;
+        let no_ask = quote.no_ask.ok_or_else(|| {
+            ScoringError::ineligible(
+                market,
+                IneligibleReason::MissingNoAsk,
+                "Quote has no NO ask",
+            )
+        })?;
 
         // Gross yield = selling NO at bid price
         let entry_price = no_bid;
@@ -86,31 +187,56 @@ impl ScoringEngine {
         let yield_velocity = net_yield / t_days_clamped;
 
         // Liquidity score
-        let liquidity_score = self.calculate_liquidity_score(no_bid, no_ask, staleness_penalty);
-
-        // Definition risk score
-        let definition_risk_score = rule.map(|r| r.definition_risk_score).unwrap_or(0.0);
-
-        // Overall score (weighted combination)
+        let depth_score = self.calculate_depth_score(metadata.liquidity);
+        let liquidity_score =
+            self.calculate_liquidity_score(no_bid, no_ask, staleness_penalty, depth_score);
+
+        // Overall score (weighted combination) from min-max normalized
+        // yields; universe-relative methods are applied across the batch
+        let normalization = &self.config.normalization;
+        let norm_velocity = normalize::min_max(yield_velocity, &normalization.yield_velocity);
+        let norm_net_yield = normalize::min_max(net_yield, &normalization.net_yield);
         let overall_score = self.calculate_overall_score(
-            yield_velocity,
-            net_yield,
+            norm_velocity,
+            norm_net_yield,
             liquidity_score,
             definition_risk_score,
             staleness_penalty,
         );
 
+        // Trade features: whether size is executing near the quoted price
+        let mid_no = quote.mid_no.unwrap_or((no_bid + no_ask) / 2.0);
+        let last_trade_no_price = trades.and_then(|t| t.last_no_price);
+        let last_trade_vs_mid = last_trade_no_price.map(|p| p - mid_no);
+        let notional_1h = trades.map(|t| t.window_notional);
+        let trades_1h = trades.map(|t| t.window_count);
+        let time_since_last_trade_sec = trades.and_then(|t| t.seconds_since_last_trade(now));
+
         // Score breakdown for transparency
         let score_breakdown = json!({
             "yield_velocity": yield_velocity,
             "net_yield": net_yield,
             "liquidity_score": liquidity_score,
             "definition_risk_score": definition_risk_score,
+            "rule_missing": rule.is_none(),
             "staleness_penalty": staleness_penalty,
             "gross_yield": gross_yield,
             "fee_rate": fee_rate,
             "t_days": t_days,
             "entry_price": entry_price,
+            "depth_score": depth_score,
+            "volume_24h": metadata.volume_24h,
+            "displayed_liquidity": metadata.liquidity,
+            "last_trade_no_price": last_trade_no_price,
+            "last_trade_vs_mid": last_trade_vs_mid,
+            "notional_1h": notional_1h,
+            "trades_1h": trades_1h,
+            "time_since_last_trade_sec": time_since_last_trade_sec,
+            "normalization": normalization_breakdown(
+                NormalizationMethod::MinMax,
+                (yield_velocity, norm_velocity),
+                (net_yield, norm_net_yield),
+            ),
         });
 
         Ok(Score {
@@ -172,6 +298,7 @@ impl ScoringEngine {
             risk_score,
             risk_flags,
             notes: Some(notes),
+            retraction: None,
         }
     }
 
@@ -181,32 +308,51 @@ impl ScoringEngine {
         ratio.clamp(0.0, 1.0)
     }
 
+    /// Calculate depth score from displayed liquidity, if configured and
+    /// reported
+    fn calculate_depth_score(&self, liquidity: Option<f64>) -> Option<f64> {
+        let target = self.config.activity.liquidity_target?;
+        let liquidity = liquidity?;
+        if target <= 0.0 {
+            return None;
+        }
+        Some((liquidity / target).clamp(0.0, 1.0))
+    }
+
     /// Calculate liquidity score
-    fn calculate_liquidity_score(&self, no_bid: f64, no_ask: f64, staleness_penalty: f64) -> f64 {
+    fn calculate_liquidity_score(
+        &self,
+        no_bid: f64,
+        no_ask: f64,
+        staleness_penalty: f64,
+        depth_score: Option<f64>,
+    ) -> f64 {
         let spread_no = no_ask - no_bid;
         let spread_ratio = spread_no / self.config.bounds.spread_target;
-        let raw_score = (1.0 - spread_ratio).clamp(0.0, 1.0);
+        let spread_score = (1.0 - spread_ratio).clamp(0.0, 1.0);
+
+        // Blend in depth equally with spread when available
+        let raw_score = match depth_score {
+            Some(depth) => (spread_score + depth) / 2.0,
+            None => spread_score,
+        };
 
         // Apply staleness penalty
         raw_score * (1.0 - staleness_penalty)
     }
 
-    /// Calculate overall score (weighted combination)
+    /// Calculate overall score (weighted combination) from normalized
+    /// yield features
     fn calculate_overall_score(
         &self,
-        yield_velocity: f64,
-        net_yield: f64,
+        norm_velocity: f64,
+        norm_net_yield: f64,
         liquidity_score: f64,
         definition_risk_score: f64,
         staleness_penalty: f64,
     ) -> f64 {
         let w = &self.config.weights;
 
-        // Normalize yield metrics (assuming max ~1.0 for yield velocity, ~0.5 for net
-        // yield)
-        let norm_velocity = (yield_velocity / 1.0).clamp(0.0, 1.0);
-        let norm_net_yield = (net_yield / 0.5).clamp(0.0, 1.0);
-
         // Weighted combination
         let score = w.w1 * norm_velocity + w.w2 * norm_net_yield + w.w3 * liquidity_score
             - w.w4 * definition_risk_score
@@ -231,59 +377,133 @@ impl ScoringEngine {
         position_pct.clamp(0.01, 0.10) // Min 1%, max 10%
     }
 
-    /// Batch score computation for multiple markets
-    pub fn compute_scores_batch(
+    /// Re-normalize yield features across `scores`, the cycle's eligible
+    /// set, and recompute their overall scores
+    ///
+    /// Does nothing for min-max normalization, which `compute_score`
+    /// already applied.
+    fn normalize_universe(&self, scores: &mut [Score]) {
+        let normalization = &self.config.normalization;
+        let method = normalization.method;
+        if !method.is_universe_relative() || scores.is_empty() {
+            return;
+        }
+
+        let velocities: Vec<f64> = scores.iter().map(|s| s.yield_velocity).collect();
+        let net_yields: Vec<f64> = scores.iter().map(|s| s.net_yield).collect();
+        let (norm_velocities, norm_net_yields) = match method {
+            NormalizationMethod::Percentile => (
+                normalize::percentile_ranks(&velocities),
+                normalize::percentile_ranks(&net_yields),
+            ),
+            NormalizationMethod::ZScore => (
+                normalize::z_scores(&velocities, normalization.z_clip),
+                normalize::z_scores(&net_yields, normalization.z_clip),
+            ),
+            NormalizationMethod::MinMax => return,
+        };
+
+        for ((score, norm_velocity), norm_net_yield) in
+            scores.iter_mut().zip(norm_velocities).zip(norm_net_yields)
+        {
+            score.overall_score = self.calculate_overall_score(
+                norm_velocity,
+                norm_net_yield,
+                score.liquidity_score,
+                score.definition_risk_score,
+                score.staleness_penalty,
+            );
+            score.score_breakdown["normalization"] = normalization_breakdown(
+                method,
+                (score.yield_velocity, norm_velocity),
+                (score.net_yield, norm_net_yield),
+            );
+        }
+    }
+}
+
+impl ScoringModel for ScoringEngine {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn describe(&self) -> Value {
+        json!({
+            "id": self.id,
+            "kind": "weighted_sum",
+            "weights": self.config.weights,
+            "normalization": self.config.normalization,
+            "fee_bps": self.config.fee_bps,
+        })
+    }
+
+    /// Universe-relative normalization needs the whole eligible set
+    fn scores_independently(&self) -> bool {
+        !self.config.normalization.method.is_universe_relative()
+    }
+
+    fn check_market(&self, market: &Market, now: DateTime<Utc>) -> Result<()> {
+        self.time_remaining(market, now).map(|_| ())
+    }
+
+    fn compute_score(
         &self,
-        markets: &[Market],
-        quotes: &HashMap<String, Quote>,
-        rules: &HashMap<String, RuleSnapshot>,
+        market: &Market,
+        quote: &Quote,
+        rule: Option<&RuleSnapshot>,
+        trades: Option<&TradeStats>,
         now: DateTime<Utc>,
-    ) -> Vec<Score> {
-        markets
-            .iter()
-            .filter_map(|market| {
-                let quote = quotes.get(&market.market_id)?;
-                let rule = rules.get(&market.market_id);
-
-                match self.compute_score(market, quote, rule, now) {
-                    Ok(score) => Some(score),
-                    Err(e) => {
-                        tracing::debug!(
-                            market_id = %market.market_id,
-                            error = %e,
-                            "Skipping market in scoring"
-                        );
-                        None
-                    }
-                }
-            })
-            .collect()
+    ) -> Result<Score> {
+        ScoringEngine::compute_score(self, market, quote, rule, trades, now)
+    }
+
+    fn recommend(
+        &self,
+        market: &Market,
+        score: &Score,
+        quote: &Quote,
+        rule: Option<&RuleSnapshot>,
+    ) -> Option<Recommendation> {
+        let rec = self.generate_recommendation(market, score, quote, rule);
+        match self.config.sizing.max_risk_score {
+            Some(max) if rec.risk_score > max => None,
+            _ => Some(rec),
+        }
     }
 
-    /// Batch recommendation generation
-    pub fn generate_recommendations_batch(
+    /// With a percentile or z-score normalization method, yield features
+    /// are normalized across the markets that were eligible this batch
+    fn compute_scores_batch(
         &self,
         markets: &[Market],
-        scores: &HashMap<String, Score>,
         quotes: &HashMap<String, Quote>,
         rules: &HashMap<String, RuleSnapshot>,
-    ) -> Vec<Recommendation> {
-        markets
-            .iter()
-            .filter_map(|market| {
-                let score = scores.get(&market.market_id)?;
-                let quote = quotes.get(&market.market_id)?;
-                let rule = rules.get(&market.market_id);
-
-                Some(self.generate_recommendation(market, score, quote, rule))
-            })
-            .collect()
+        trades: &HashMap<String, TradeStats>,
+        now: DateTime<Utc>,
+    ) -> ScoredBatch {
+        let mut batch = model::score_each(self, markets, quotes, rules, trades, now);
+        self.normalize_universe(&mut batch.scores);
+        batch
     }
 }
 
+/// `score_breakdown` entry recording how yield features were normalized
+fn normalization_breakdown(
+    method: NormalizationMethod,
+    (velocity, norm_velocity): (f64, f64),
+    (net_yield, norm_net_yield): (f64, f64),
+) -> Value {
+    json!({
+        "method": method,
+        "yield_velocity": { "raw": velocity, "normalized": norm_velocity },
+        "net_yield": { "raw": net_yield, "normalized": norm_net_yield },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{ScoringWeights, ShadowModelConfig};
 
     #[test]
     fn test_staleness_penalty() {
@@ -309,15 +529,279 @@ mod tests {
         let engine = ScoringEngine::new(config);
 
         // Perfect liquidity (no spread)
-        let score = engine.calculate_liquidity_score(0.95, 0.95, 0.0);
+        let score = engine.calculate_liquidity_score(0.95, 0.95, 0.0, None);
         assert_eq!(score, 1.0);
 
         // Target spread (2%)
-        let score = engine.calculate_liquidity_score(0.94, 0.96, 0.0);
+        let score = engine.calculate_liquidity_score(0.94, 0.96, 0.0, None);
         assert_eq!(score, 0.0);
 
         // With staleness penalty
-        let score = engine.calculate_liquidity_score(0.95, 0.95, 0.5);
+        let score = engine.calculate_liquidity_score(0.95, 0.95, 0.5, None);
         assert_eq!(score, 0.5);
+
+        // Blended with depth
+        let score = engine.calculate_liquidity_score(0.95, 0.95, 0.0, Some(0.5));
+        assert_eq!(score, 0.75);
+    }
+
+    fn endgame_market(now: DateTime<Utc>) -> Market {
+        Market {
+            market_id: "polymarket:0xabc".to_string(),
+            venue: "polymarket".to_string(),
+            venue_market_id: "0xabc".to_string(),
+            title: "Will it happen?".to_string(),
+            slug: None,
+            category: None,
+            status: pm_domain::MarketStatus::Active,
+            open_time: None,
+            close_time: Some(now + Duration::days(3)),
+            resolved_time: None,
+            url: None,
+            metadata: Default::default(),
+            outcomes: Vec::new(),
+        }
+    }
+
+    fn endgame_quote(now: DateTime<Utc>) -> Quote {
+        Quote {
+            market_id: "polymarket:0xabc".to_string(),
+            as_of: now,
+            yes_bid: Some(0.03),
+            yes_ask: Some(0.05),
+            no_bid: Some(0.95),
+            no_ask: Some(0.97),
+            spread_yes: Some(0.02),
+            spread_no: Some(0.02),
+            mid_yes: Some(0.04),
+            mid_no: Some(0.96),
+            yes_source: pm_domain::PriceSource::Observed,
+            no_source: pm_domain::PriceSource::Observed,
+            quote_source: "polymarket".to_string(),
+        }
+    }
+
+    fn endgame_rule(market_id: &str, now: DateTime<Utc>) -> RuleSnapshot {
+        RuleSnapshot {
+            market_id: market_id.to_string(),
+            as_of: now,
+            rule_text: "Resolves YES if it happens.".to_string(),
+            rule_hash: "hash".to_string(),
+            settlement_source: None,
+            settlement_window: None,
+            definition_risk_score: 0.1,
+            risk_flags: Vec::new(),
+        }
+    }
+
+    fn endgame_rules<'a>(
+        ids: impl IntoIterator<Item = &'a str>,
+        now: DateTime<Utc>,
+    ) -> HashMap<String, RuleSnapshot> {
+        ids.into_iter()
+            .map(|id| (id.to_string(), endgame_rule(id, now)))
+            .collect()
+    }
+
+    #[test]
+    fn test_trade_features_in_breakdown() {
+        let engine = ScoringEngine::new(ScoringConfig::default());
+        let now = Utc::now();
+        let market = endgame_market(now);
+        let quote = endgame_quote(now);
+        let rule = endgame_rule(&market.market_id, now);
+
+        let stats = TradeStats {
+            last_no_price: Some(0.95),
+            last_trade_at: Some(now - Duration::minutes(10)),
+            window_notional: 1900.0,
+            window_count: 4,
+        };
+        let score = engine
+            .compute_score(&market, &quote, Some(&rule), Some(&stats), now)
+            .unwrap();
+        let breakdown = &score.score_breakdown;
+        assert!((breakdown["last_trade_vs_mid"].as_f64().unwrap() + 0.01).abs() < 1e-9);
+        assert_eq!(breakdown["notional_1h"].as_f64(), Some(1900.0));
+        assert_eq!(breakdown["trades_1h"].as_u64(), Some(4));
+        assert_eq!(breakdown["time_since_last_trade_sec"].as_i64(), Some(600));
+
+        // Without trades the features are null and the score is unchanged
+        let untraded = engine
+            .compute_score(&market, &quote, Some(&rule), None, now)
+            .unwrap();
+        assert!(untraded.score_breakdown["last_trade_vs_mid"].is_null());
+        assert_eq!(untraded.overall_score, score.overall_score);
+    }
+
+    #[test]
+    fn test_percentile_normalization_across_batch() {
+        let now = Utc::now();
+        let mut markets = Vec::new();
+        let mut quotes = HashMap::new();
+        for (id, no_bid) in [("polymarket:0xa", 0.90), ("polymarket:0xb", 0.95)] {
+            let mut market = endgame_market(now);
+            market.market_id = id.to_string();
+            markets.push(market);
+            let mut quote = endgame_quote(now);
+            quote.market_id = id.to_string();
+            quote.no_bid = Some(no_bid);
+            quotes.insert(id.to_string(), quote);
+        }
+        let rules = endgame_rules(["polymarket:0xa", "polymarket:0xb"], now);
+        let no_trades = HashMap::new();
+
+        let min_max = ScoringEngine::new(ScoringConfig::default())
+            .compute_scores_batch(&markets, &quotes, &rules, &no_trades, now)
+            .scores;
+        assert_eq!(
+            min_max[0].score_breakdown["normalization"]["method"],
+            "min_max"
+        );
+
+        let mut config = ScoringConfig::default();
+        config.normalization.method = NormalizationMethod::Percentile;
+        let scores = ScoringEngine::new(config)
+            .compute_scores_batch(&markets, &quotes, &rules, &no_trades, now)
+            .scores;
+
+        let normalization = &scores[1].score_breakdown["normalization"];
+        assert_eq!(normalization["method"], "percentile");
+        assert_eq!(normalization["net_yield"]["normalized"].as_f64(), Some(1.0));
+        assert_eq!(
+            normalization["net_yield"]["raw"].as_f64(),
+            Some(scores[1].net_yield)
+        );
+        let lowest = &scores[0].score_breakdown["normalization"];
+        assert_eq!(lowest["yield_velocity"]["normalized"].as_f64(), Some(0.0));
+        assert!(scores[1].overall_score > scores[0].overall_score);
+        assert!(scores[1].overall_score > min_max[1].overall_score);
+    }
+
+    #[test]
+    fn test_shadow_variant_overrides_weights() {
+        let now = Utc::now();
+        let market = endgame_market(now);
+        let quote = endgame_quote(now);
+        let base = ScoringConfig::default();
+        let shadow = ShadowModelConfig {
+            id: "velocity_only".to_string(),
+            weights: Some(ScoringWeights {
+                w1: 1.0,
+                w2: 0.0,
+                w3: 0.0,
+                w4: 0.0,
+                w5: 0.0,
+            }),
+            normalization: None,
+        };
+
+        let primary = ScoringEngine::new(base.clone());
+        let variant = ScoringEngine::new(shadow.apply(&base)).with_id(&shadow.id);
+        assert_eq!(primary.id(), DEFAULT_MODEL_ID);
+        assert_eq!(variant.id(), "velocity_only");
+        assert_eq!(variant.describe()["weights"]["w1"].as_f64(), Some(1.0));
+
+        let rule = endgame_rule(&market.market_id, now);
+        let score =
+            ScoringModel::compute_score(&variant, &market, &quote, Some(&rule), None, now).unwrap();
+        let norm_velocity =
+            score.score_breakdown["normalization"]["yield_velocity"]["normalized"].as_f64();
+        assert_eq!(Some(score.overall_score), norm_velocity);
+    }
+
+    #[test]
+    fn test_batch_records_why_markets_were_not_scored() {
+        let now = Utc::now();
+        let mut markets = Vec::new();
+        let mut quotes = HashMap::new();
+        let ids = [
+            "ok", "no_quote", "no_ask", "too_far", "stale", "no_rule", "undated",
+        ];
+        for id in ids {
+            let mut market = endgame_market(now);
+            market.market_id = id.to_string();
+            markets.push(market);
+            let mut quote = endgame_quote(now);
+            quote.market_id = id.to_string();
+            quotes.insert(id.to_string(), quote);
+        }
+        quotes.remove("no_quote");
+        quotes.get_mut("no_ask").unwrap().no_ask = None;
+        markets[3].close_time = Some(now + Duration::days(30));
+        quotes.remove("too_far");
+        // Market gates are reported ahead of the missing quote
+        markets[6].close_time = None;
+        quotes.remove("undated");
+        quotes.get_mut("stale").unwrap().as_of = now - Duration::minutes(5);
+        let mut rules = endgame_rules(ids, now);
+        rules.remove("no_rule");
+
+        let batch = ScoringEngine::new(ScoringConfig::default()).compute_scores_batch(
+            &markets,
+            &quotes,
+            &rules,
+            &HashMap::new(),
+            now,
+        );
+
+        assert_eq!(batch.scores.len(), 1);
+        let reasons: Vec<_> = batch.checks.iter().map(|c| c.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                None,
+                Some(IneligibleReason::MissingQuote),
+                Some(IneligibleReason::MissingNoAsk),
+                Some(IneligibleReason::OutsideWindow),
+                Some(IneligibleReason::StaleQuote),
+                Some(IneligibleReason::MissingRule),
+                Some(IneligibleReason::MissingCloseTime),
+            ]
+        );
+        assert!(batch.checks[3]
+            .detail
+            .as_deref()
+            .is_some_and(|d| d.contains("outside bounds")));
+    }
+
+    #[test]
+    fn test_gates_can_be_relaxed() {
+        let now = Utc::now();
+        let market = endgame_market(now);
+        let mut quote = endgame_quote(now);
+        quote.as_of = now - Duration::minutes(5);
+
+        let mut config = ScoringConfig::default();
+        config.gates.require_fresh_quote = false;
+        config.gates.missing_rules = MissingRulesPolicy::DefaultRisk;
+        let engine = ScoringEngine::new(config);
+
+        // Stale quotes are only penalized, and unknown rules score as risky
+        let score = engine
+            .compute_score(&market, &quote, None, None, now)
+            .unwrap();
+        assert_eq!(score.staleness_penalty, 1.0);
+        assert_eq!(score.definition_risk_score, 0.8);
+        assert_eq!(score.score_breakdown["rule_missing"], true);
+
+        let rule = endgame_rule(&market.market_id, now);
+        let known = engine
+            .compute_score(&market, &quote, Some(&rule), None, now)
+            .unwrap();
+        assert!(known.overall_score > score.overall_score);
+    }
+
+    #[test]
+    fn test_depth_score() {
+        let mut config = ScoringConfig::default();
+        let engine = ScoringEngine::new(config.clone());
+        assert_eq!(engine.calculate_depth_score(Some(5000.0)), None);
+
+        config.activity.liquidity_target = Some(10_000.0);
+        let engine = ScoringEngine::new(config);
+        assert_eq!(engine.calculate_depth_score(Some(5000.0)), Some(0.5));
+        assert_eq!(engine.calculate_depth_score(Some(50_000.0)), Some(1.0));
+        assert_eq!(engine.calculate_depth_score(None), None);
     }
 }
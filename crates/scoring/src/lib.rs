@@ -4,8 +4,13 @@
 
 pub mod config;
 pub mod engine;
+pub mod metrics;
+pub mod model;
+pub mod normalize;
 pub mod orchestrator;
 
 pub use config::ScoringConfig;
 pub use engine::ScoringEngine;
+pub use metrics::ScoringMetrics;
+pub use model::{ScoredBatch, ScoringModel};
 pub use orchestrator::ScoringOrchestrator;
@@ -2,8 +2,13 @@
 //!
 //! Computes opportunity scores and risk decomposition.
 
-use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use std::sync::Arc;
+
+use anyhow::Context;
+use pm_metrics::MetricsServer;
+use pm_scoring::{ScoringConfig, ScoringMetrics, ScoringOrchestrator};
 use sqlx::postgres::PgPoolOptions;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -19,7 +24,13 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("pm-score starting");
 
     // Load configuration
-    let config = ScoringConfig::default();
+    let mut config = ScoringConfig::default();
+    if std::env::var("LEADER_ELECTION").is_ok_and(|v| v == "0" || v == "false") {
+        config.leader.enabled = false;
+    }
+    if let Ok(port) = std::env::var("METRICS_PORT") {
+        config.metrics.port = port.parse().context("invalid METRICS_PORT")?;
+    }
 
     // Connect to database
     let database_url = std::env::var("DATABASE_URL")
@@ -35,7 +46,9 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Connected to database");
 
     // Create orchestrator
-    let orchestrator = ScoringOrchestrator::new(pool, config);
+    let metrics = ScoringMetrics::default();
+    let metrics_config = config.metrics.clone();
+    let orchestrator = ScoringOrchestrator::new(pool, config).with_metrics(metrics.clone());
 
     // Setup signal handler for graceful shutdown
     tokio::spawn({
@@ -47,7 +60,19 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    orchestrator.run().await?;
+    let orchestrator = Arc::new(orchestrator);
+    let metrics_shutdown = CancellationToken::new();
+    MetricsServer::new("pm-score", metrics.registry())
+        .with_leader({
+            let orchestrator = Arc::clone(&orchestrator);
+            move || orchestrator.is_leader()
+        })
+        .spawn(&metrics_config, metrics_shutdown.clone())
+        .await?;
+
+    let result = orchestrator.run().await;
+    metrics_shutdown.cancel();
+    result?;
 
     tracing::info!("pm-score shutdown complete");
     Ok(())
@@ -0,0 +1,78 @@
+//! Prometheus metrics for the scoring service
+
+use std::sync::Arc;
+
+use pm_metrics::LoopMetrics;
+use prometheus::{opts, IntCounterVec, IntGauge, Registry};
+
+/// Metrics collector for the scoring service
+#[derive(Clone)]
+pub struct ScoringMetrics {
+    /// Cycle durations and results, rows upserted and markets skipped
+    pub loops: LoopMetrics,
+
+    /// Markets scored in the last cycle
+    pub markets_scored: IntGauge,
+
+    /// 1 while this replica holds scoring leadership
+    pub leader: IntGauge,
+
+    /// Recommendations retracted by reason
+    pub recs_retracted_total: IntCounterVec,
+
+    registry: Arc<Registry>,
+}
+
+impl ScoringMetrics {
+    /// Create new metrics collector
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let loops = LoopMetrics::new(&registry, "pm_score")?;
+
+        let markets_scored = IntGauge::with_opts(opts!(
+            "pm_score_markets_scored",
+            "Markets scored in the last scoring cycle"
+        ))?;
+        registry.register(Box::new(markets_scored.clone()))?;
+
+        let leader = IntGauge::with_opts(opts!(
+            "pm_score_leader",
+            "Whether this replica leads scoring, or runs it alone without election"
+        ))?;
+        registry.register(Box::new(leader.clone()))?;
+
+        let recs_retracted_total = IntCounterVec::new(
+            opts!(
+                "pm_score_recs_retracted_total",
+                "Recommendations retracted because a cycle did not re-issue them"
+            ),
+            &["reason"],
+        )?;
+        registry.register(Box::new(recs_retracted_total.clone()))?;
+
+        Ok(Self {
+            loops,
+            markets_scored,
+            leader,
+            recs_retracted_total,
+            registry: Arc::new(registry),
+        })
+    }
+
+    /// Registry holding every scoring metric, for the metrics listener
+    pub fn registry(&self) -> Arc<Registry> {
+        Arc::clone(&self.registry)
+    }
+
+    /// Get metrics in Prometheus text format
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        pm_metrics::render(&self.registry)
+    }
+}
+
+impl Default for ScoringMetrics {
+    fn default() -> Self {
+        Self::new().expect("Failed to create metrics")
+    }
+}
@@ -0,0 +1,152 @@
+//! Pluggable scoring models
+//!
+//! A [`ScoringModel`] turns one cycle's market inputs into scores and
+//! recommendations. [`ScoringEngine`](crate::ScoringEngine) is the default
+//! weighted-sum model. The orchestrator can also run shadow models each
+//! cycle; their scores go to `scores_shadow`, tagged by [`ScoringModel::id`],
+//! and never into `scores_latest` or `recs_latest`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use pm_domain::{
+    EligibilityCheck, IneligibleReason, Market, Quote, Recommendation, RuleSnapshot, Score,
+    TradeStats,
+};
+use serde_json::Value;
+
+use crate::engine::{Result, ScoringError};
+
+/// Scores for a batch of markets, with the eligibility check of each market
+#[derive(Debug, Clone, Default)]
+pub struct ScoredBatch {
+    pub scores: Vec<Score>,
+    /// One check per market in the batch, eligible or not
+    pub checks: Vec<EligibilityCheck>,
+}
+
+/// A formula for scoring markets and sizing recommendations
+pub trait ScoringModel: Send + Sync {
+    /// Stable id, tagging the model's rows in `scores_shadow`
+    fn id(&self) -> &str;
+
+    /// Model kind and parameters, for logs and comparing models
+    fn describe(&self) -> Value;
+
+    /// Whether each market's score depends only on its own inputs, so
+    /// changed markets can be re-scored without the rest of the cycle
+    fn scores_independently(&self) -> bool {
+        true
+    }
+
+    /// Fail if the market is ineligible whatever its quote, e.g. because
+    /// of its close time; checked before the quote
+    fn check_market(&self, _market: &Market, _now: DateTime<Utc>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Score one market, or fail if it is not eligible
+    fn compute_score(
+        &self,
+        market: &Market,
+        quote: &Quote,
+        rule: Option<&RuleSnapshot>,
+        trades: Option<&TradeStats>,
+        now: DateTime<Utc>,
+    ) -> Result<Score>;
+
+    /// Recommend a position from a market's score, or `None` to withhold
+    /// one because the position is too risky
+    fn recommend(
+        &self,
+        market: &Market,
+        score: &Score,
+        quote: &Quote,
+        rule: Option<&RuleSnapshot>,
+    ) -> Option<Recommendation>;
+
+    /// Score a cycle's markets, leaving out those without a quote or that
+    /// fail eligibility and recording why
+    fn compute_scores_batch(
+        &self,
+        markets: &[Market],
+        quotes: &HashMap<String, Quote>,
+        rules: &HashMap<String, RuleSnapshot>,
+        trades: &HashMap<String, TradeStats>,
+        now: DateTime<Utc>,
+    ) -> ScoredBatch {
+        score_each(self, markets, quotes, rules, trades, now)
+    }
+
+    /// Recommend positions for the scored markets
+    fn recommend_batch(
+        &self,
+        markets: &[Market],
+        scores: &HashMap<String, Score>,
+        quotes: &HashMap<String, Quote>,
+        rules: &HashMap<String, RuleSnapshot>,
+    ) -> Vec<Recommendation> {
+        markets
+            .iter()
+            .filter_map(|market| {
+                let score = scores.get(&market.market_id)?;
+                let quote = quotes.get(&market.market_id)?;
+                let rule = rules.get(&market.market_id);
+
+                self.recommend(market, score, quote, rule)
+            })
+            .collect()
+    }
+}
+
+/// Score each market on its own with [`ScoringModel::compute_score`]
+pub fn score_each<M: ScoringModel + ?Sized>(
+    model: &M,
+    markets: &[Market],
+    quotes: &HashMap<String, Quote>,
+    rules: &HashMap<String, RuleSnapshot>,
+    trades: &HashMap<String, TradeStats>,
+    now: DateTime<Utc>,
+) -> ScoredBatch {
+    let mut batch = ScoredBatch::default();
+
+    for market in markets {
+        // Market gates come first: markets outside the window are rarely
+        // quoted, and their check should still name the window
+        let scored = model.check_market(market, now).and_then(|()| {
+            let quote = quotes.get(&market.market_id).ok_or_else(|| {
+                ScoringError::ineligible(market, IneligibleReason::MissingQuote, "No latest quote")
+            })?;
+            let rule = rules.get(&market.market_id);
+            let trades = trades.get(&market.market_id);
+
+            model.compute_score(market, quote, rule, trades, now)
+        });
+
+        match scored {
+            Ok(score) => {
+                batch
+                    .checks
+                    .push(EligibilityCheck::eligible(&market.market_id, now));
+                batch.scores.push(score);
+            }
+            Err(ScoringError::Ineligible { reason, detail, .. }) => {
+                tracing::debug!(
+                    model = model.id(),
+                    market_id = %market.market_id,
+                    %reason,
+                    detail,
+                    "Skipping market in scoring"
+                );
+                batch.checks.push(EligibilityCheck::failed(
+                    &market.market_id,
+                    now,
+                    reason,
+                    detail,
+                ));
+            }
+        }
+    }
+
+    batch
+}
@@ -0,0 +1,99 @@
+//! Feature normalization for the overall score
+//!
+//! Yield features are unbounded, so they are scaled into `[0, 1]` before
+//! weighting: either min-max against configured ranges, or relative to the
+//! other markets eligible in the same cycle.
+
+use crate::config::FeatureRange;
+
+/// Min-max normalize `value` into `range`, clamped to `[0, 1]`
+///
+/// An empty or inverted range normalizes everything to 0.
+pub fn min_max(value: f64, range: &FeatureRange) -> f64 {
+    let width = range.max - range.min;
+    if width <= 0.0 {
+        return 0.0;
+    }
+    ((value - range.min) / width).clamp(0.0, 1.0)
+}
+
+/// Percentile rank of each value within `values`
+///
+/// The smallest value ranks 0 and the largest 1; ties share the midpoint
+/// of their ranks. A single value ranks 0.5.
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    if values.len() < 2 {
+        return vec![0.5; values.len()];
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let last = (values.len() - 1) as f64;
+
+    values
+        .iter()
+        .map(|&value| {
+            let below = sorted.partition_point(|&v| v < value);
+            let equal = sorted.partition_point(|&v| v <= value) - below;
+            (below as f64 + (equal as f64 - 1.0) / 2.0) / last
+        })
+        .collect()
+}
+
+/// Z-score of each value within `values`, mapped from `[-clip, clip]`
+/// onto `[0, 1]`
+///
+/// The mean maps to 0.5. Without spread every value maps to 0.5.
+pub fn z_scores(values: &[f64], clip: f64) -> Vec<f64> {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let std_dev = variance.sqrt();
+
+    if !std_dev.is_finite() || std_dev == 0.0 || clip <= 0.0 {
+        return vec![0.5; values.len()];
+    }
+
+    values
+        .iter()
+        .map(|v| {
+            let z = (v - mean) / std_dev;
+            ((z + clip) / (2.0 * clip)).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_max() {
+        let range = FeatureRange { min: 0.0, max: 0.5 };
+        assert_eq!(min_max(0.25, &range), 0.5);
+        assert_eq!(min_max(0.75, &range), 1.0);
+        assert_eq!(min_max(-0.1, &range), 0.0);
+
+        let empty = FeatureRange { min: 1.0, max: 1.0 };
+        assert_eq!(min_max(2.0, &empty), 0.0);
+    }
+
+    #[test]
+    fn test_percentile_ranks() {
+        assert_eq!(percentile_ranks(&[0.3, 0.1, 0.2]), vec![1.0, 0.0, 0.5]);
+        assert_eq!(percentile_ranks(&[0.1, 0.2, 0.2]), vec![0.0, 0.75, 0.75]);
+        assert_eq!(percentile_ranks(&[0.4]), vec![0.5]);
+        assert!(percentile_ranks(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_z_scores() {
+        // Mean 2, population standard deviation 1
+        let values = [1.0, 3.0, 1.0, 3.0];
+        let normalized = z_scores(&values, 2.0);
+        assert_eq!(normalized, vec![0.25, 0.75, 0.25, 0.75]);
+
+        assert_eq!(z_scores(&[0.2, 0.2], 3.0), vec![0.5, 0.5]);
+        assert_eq!(z_scores(&[0.0, 10.0], 0.5), vec![0.0, 1.0]);
+    }
+}
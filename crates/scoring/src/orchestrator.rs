@@ -1,14 +1,36 @@
 //! Scoring orchestrator that periodically computes scores and recommendations
 
-use std::{collections::HashMap, time::Duration};
-
-use pm_domain::{Quote, RuleSnapshot, Score};
-use pm_storage::{markets, quotes, recs, rules, scores};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use pm_domain::{
+    EligibilityCheck, IneligibleReason, Market, MarketStatus, Quote, RetractionReason,
+    RuleSnapshot, Score, SharedClock, SystemClock, Trade, TradeStats,
+};
+use pm_storage::{
+    changes::MarketChangeListener,
+    eligibility,
+    leader::{self, LeaderElection, LeaderRole, Leadership},
+    markets::{self, MarketError},
+    quotes, recs, rules, scores, trades,
+};
 use sqlx::PgPool;
-use tokio::time::interval;
+use tokio::{
+    sync::mpsc,
+    time::{interval, sleep_until, Instant},
+};
 use tokio_util::sync::CancellationToken;
 
-use crate::{config::ScoringConfig, engine::ScoringEngine};
+use crate::{
+    config::ScoringConfig,
+    engine::{ScoringEngine, TRADE_WINDOW},
+    metrics::ScoringMetrics,
+    model::{ScoredBatch, ScoringModel},
+};
 
 /// Error type for orchestrator operations
 #[derive(Debug, thiserror::Error)]
@@ -18,50 +40,181 @@ pub enum OrchestratorError {
 
     #[error("Scoring error: {0}")]
     Scoring(#[from] crate::engine::ScoringError),
+
+    #[error("Lost scoring leadership")]
+    LeadershipLost,
 }
 
 pub type Result<T> = std::result::Result<T, OrchestratorError>;
 
+/// Wait before reconnecting a failed market change listener
+const CHANGE_LISTENER_RETRY: Duration = Duration::from_secs(5);
+
 /// Scoring orchestrator coordinates periodic scoring runs
 pub struct ScoringOrchestrator {
-    engine: ScoringEngine,
+    model: Arc<dyn ScoringModel>,
+    shadows: Vec<Arc<dyn ScoringModel>>,
     pool: PgPool,
     config: ScoringConfig,
     cancellation: CancellationToken,
+    leader: Option<LeaderElection>,
+    clock: SharedClock,
+    metrics: ScoringMetrics,
 }
 
 impl ScoringOrchestrator {
     /// Create a new orchestrator
+    ///
+    /// Scores with [`ScoringEngine`], plus a shadow engine for each of
+    /// `config.shadow_models`. With `config.leader.enabled`, `run` first
+    /// waits to be elected scoring leader.
     pub fn new(pool: PgPool, config: ScoringConfig) -> Self {
-        let engine = ScoringEngine::new(config.clone());
+        let model: Arc<dyn ScoringModel> = Arc::new(ScoringEngine::new(config.clone()));
+        let shadows = config
+            .shadow_models
+            .iter()
+            .map(|shadow| {
+                Arc::new(ScoringEngine::new(shadow.apply(&config)).with_id(&shadow.id))
+                    as Arc<dyn ScoringModel>
+            })
+            .collect();
+        let leader = config.leader.enabled.then(|| {
+            LeaderElection::new(
+                pool.clone(),
+                LeaderRole::Scoring,
+                &leader::instance_name(LeaderRole::Scoring),
+                config.leader.clone(),
+            )
+        });
 
         Self {
-            engine,
+            model,
+            shadows,
             pool,
             config,
             cancellation: CancellationToken::new(),
+            leader,
+            clock: SystemClock::shared(),
+            metrics: ScoringMetrics::default(),
         }
     }
 
+    /// Score against `clock` instead of the wall clock, for eligibility
+    /// windows and quote staleness
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Score with `model` instead of the default [`ScoringEngine`]
+    pub fn with_model(mut self, model: impl ScoringModel + 'static) -> Self {
+        self.model = Arc::new(model);
+        self
+    }
+
+    /// Also run `model` each cycle, writing its scores to `scores_shadow`
+    pub fn with_shadow_model(mut self, model: impl ScoringModel + 'static) -> Self {
+        self.shadows.push(Arc::new(model));
+        self
+    }
+
+    /// Record cycle and row metrics into `metrics`
+    pub fn with_metrics(mut self, metrics: ScoringMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Metrics recorded by this orchestrator
+    pub fn metrics(&self) -> &ScoringMetrics {
+        &self.metrics
+    }
+
+    /// Whether this replica may score: it holds the leader lock, or leader
+    /// election is disabled
+    pub fn is_leader(&self) -> bool {
+        self.leader.as_ref().is_none_or(LeaderElection::is_leader)
+    }
+
     /// Get cancellation token for external shutdown
     pub fn cancellation_token(&self) -> CancellationToken {
         self.cancellation.clone()
     }
 
     /// Start scoring loop
+    ///
+    /// Runs a full sweep every `cadence_sec`. With `incremental.enabled`,
+    /// markets that ingest reports as changed are also collected for
+    /// `incremental.debounce_ms` and re-scored on their own in between.
+    ///
+    /// With leader election, a standby waits here until it holds the lock,
+    /// and [`OrchestratorError::LeadershipLost`] is returned if the leader's
+    /// lock session drops.
     pub async fn run(&self) -> Result<()> {
-        tracing::info!("Starting scoring orchestrator");
+        let mut leadership = match &self.leader {
+            Some(election) => match election.acquire(&self.cancellation).await {
+                Some(leadership) => Some(leadership),
+                None => {
+                    tracing::info!("Scoring orchestrator cancelled while standing by");
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+
+        self.metrics.leader.set(1);
+        tracing::info!(
+            model = %self.model.describe(),
+            shadows = ?self.shadows.iter().map(|m| m.id()).collect::<Vec<_>>(),
+            "Starting scoring orchestrator"
+        );
 
         let mut ticker = interval(Duration::from_secs(self.config.cadence_sec));
 
+        // Change notifications stop with this run, e.g. on lost leadership
+        let listening = self.cancellation.child_token();
+        let _stop_listening = listening.clone().drop_guard();
+        let mut changes = self
+            .config
+            .incremental
+            .enabled
+            .then(|| self.subscribe_changes(listening));
+        let debounce = Duration::from_millis(self.config.incremental.debounce_ms);
+        let mut changed: HashSet<String> = HashSet::new();
+        let mut rescore_at: Option<Instant> = None;
+
         loop {
             tokio::select! {
                 _ = ticker.tick() => {
+                    // A full sweep covers every pending change
+                    changed.clear();
+                    rescore_at = None;
                     if let Err(e) = self.run_scoring_cycle().await {
                         tracing::error!(error = %e, "Scoring cycle failed");
                     }
                 }
+                ids = Self::next_changes(&mut changes) => match ids {
+                    Some(ids) => {
+                        changed.extend(ids);
+                        rescore_at.get_or_insert_with(|| Instant::now() + debounce);
+                    }
+                    None => changes = None,
+                },
+                _ = Self::debounce_elapsed(rescore_at) => {
+                    rescore_at = None;
+                    let ids: Vec<String> = changed.drain().collect();
+                    if let Err(e) = self.run_incremental_cycle(&ids).await {
+                        tracing::error!(error = %e, "Incremental scoring cycle failed");
+                    }
+                }
+                _ = Self::leadership_lost(&mut leadership) => {
+                    self.metrics.leader.set(0);
+                    return Err(OrchestratorError::LeadershipLost);
+                }
                 _ = self.cancellation.cancelled() => {
+                    if let Some(leadership) = leadership {
+                        leadership.release().await;
+                    }
+                    self.metrics.leader.set(0);
                     tracing::info!("Scoring orchestrator cancelled");
                     return Ok(());
                 }
@@ -69,14 +222,130 @@ impl ScoringOrchestrator {
         }
     }
 
-    /// Run a single scoring cycle
-    async fn run_scoring_cycle(&self) -> Result<()> {
-        let now = chrono::Utc::now();
+    /// Resolves once held leadership is lost; never without election
+    async fn leadership_lost(leadership: &mut Option<Leadership>) {
+        match leadership {
+            Some(leadership) => leadership.lost().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Listen for market changes on a background task until `stop`
+    ///
+    /// The listener reconnects after errors; full sweeps cover any
+    /// changes missed meanwhile.
+    fn subscribe_changes(&self, stop: CancellationToken) -> mpsc::Receiver<Vec<String>> {
+        let (tx, rx) = mpsc::channel(64);
+        let pool = self.pool.clone();
+
+        tokio::spawn(async move {
+            let mut listener: Option<MarketChangeListener> = None;
+            loop {
+                let received = async {
+                    let listener = match &mut listener {
+                        Some(listener) => listener,
+                        None => listener.insert(MarketChangeListener::connect(&pool).await?),
+                    };
+                    listener.recv().await
+                };
+
+                tokio::select! {
+                    received = received => match received {
+                        Ok(ids) if ids.is_empty() => {}
+                        Ok(ids) => {
+                            if tx.send(ids).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Market change listener failed");
+                            listener = None;
+                            tokio::select! {
+                                _ = tokio::time::sleep(CHANGE_LISTENER_RETRY) => {}
+                                _ = stop.cancelled() => return,
+                            }
+                        }
+                    },
+                    _ = stop.cancelled() => return,
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// Next batch of changed market ids; never without a subscription
+    async fn next_changes(
+        changes: &mut Option<mpsc::Receiver<Vec<String>>>,
+    ) -> Option<Vec<String>> {
+        match changes {
+            Some(changes) => changes.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Resolves at `deadline`; never without one
+    async fn debounce_elapsed(deadline: Option<Instant>) {
+        match deadline {
+            Some(deadline) => sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Run a single scoring cycle over every eligible market
+    pub async fn run_scoring_cycle(&self) -> Result<()> {
+        let mut cycle = self.metrics.loops.cycle("scoring");
+        let result = self.score_all().await;
+        if result.is_err() {
+            cycle.fail();
+        }
+        result
+    }
+
+    /// Re-score only `market_ids`, e.g. after ingest reported them changed
+    ///
+    /// Runs a full cycle instead if the scoring model needs the whole
+    /// eligible set. Shadow models that do are left to the full sweeps.
+    pub async fn run_incremental_cycle(&self, market_ids: &[String]) -> Result<()> {
+        if !self.model.scores_independently() {
+            return self.run_scoring_cycle().await;
+        }
+
+        let mut cycle = self.metrics.loops.cycle("incremental_scoring");
+        let result = self.score_changed(market_ids).await;
+        if result.is_err() {
+            cycle.fail();
+        }
+        result
+    }
+
+    async fn score_changed(&self, market_ids: &[String]) -> Result<()> {
+        let now = self.clock.now();
+        tracing::debug!(count = market_ids.len(), "Re-scoring changed markets");
+
+        let markets = markets::list_active_markets_by_ids(&self.pool, market_ids)
+            .await
+            .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+
+        if markets.is_empty() {
+            tracing::debug!("No changed markets are active");
+            return self
+                .retract_stale(Some(market_ids), &[], &HashMap::new(), &HashSet::new(), now)
+                .await;
+        }
+
+        self.score_markets(markets, now, Some(market_ids)).await
+    }
+
+    async fn score_all(&self) -> Result<()> {
+        let now = self.clock.now();
         tracing::info!("Running scoring cycle");
 
-        // Fetch active markets
-        let markets = markets::list_active_markets(
+        // Fetch active markets; the engine records why each one is or is
+        // not eligible
+        let markets = markets::list_scoring_candidates(
             &self.pool,
+            now,
             self.config.bounds.min_t_remaining_sec,
             self.config.bounds.max_t_remaining_sec,
             1000, // Process up to 1000 markets per cycle
@@ -86,11 +355,33 @@ impl ScoringOrchestrator {
 
         if markets.is_empty() {
             tracing::debug!("No active markets to score");
-            return Ok(());
+            self.metrics.markets_scored.set(0);
+            self.save_checks(&[], now, true).await;
+            return self
+                .retract_stale(None, &[], &HashMap::new(), &HashSet::new(), now)
+                .await;
         }
 
         tracing::info!(count = markets.len(), "Fetched active markets");
 
+        self.score_markets(markets, now, None).await
+    }
+
+    /// Score `markets` with the scoring model, save the results and retract
+    /// recommendations that were not re-issued
+    ///
+    /// `changed` holds the market ids an incremental cycle was asked to
+    /// re-score; `None` marks a full sweep over every eligible market.
+    /// Incremental cycles only run shadow models that score markets
+    /// independently.
+    async fn score_markets(
+        &self,
+        markets: Vec<Market>,
+        now: DateTime<Utc>,
+        changed: Option<&[String]>,
+    ) -> Result<()> {
+        let full_sweep = changed.is_none();
+
         // Fetch quotes for these markets
         let market_ids: Vec<String> = markets.iter().map(|m| m.market_id.clone()).collect();
 
@@ -117,22 +408,83 @@ impl ScoringOrchestrator {
 
         tracing::info!(count = rules.len(), "Fetched rules");
 
+        // Fetch recent trades and summarize them per market
+        let trades_list = trades::get_trades_batch(&self.pool, &market_ids)
+            .await
+            .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+
+        let mut by_market: HashMap<String, Vec<Trade>> = HashMap::new();
+        for trade in trades_list {
+            by_market
+                .entry(trade.market_id.clone())
+                .or_default()
+                .push(trade);
+        }
+        let trade_stats: HashMap<String, TradeStats> = by_market
+            .into_iter()
+            .map(|(id, t)| (id, TradeStats::from_trades(&t, now, TRADE_WINDOW)))
+            .collect();
+
+        tracing::info!(count = trade_stats.len(), "Fetched trade stats");
+
         // Compute scores
-        let computed_scores = self
-            .engine
-            .compute_scores_batch(&markets, &quotes, &rules, now);
+        let ScoredBatch {
+            scores: computed_scores,
+            checks,
+        } = self
+            .model
+            .compute_scores_batch(&markets, &quotes, &rules, &trade_stats, now);
+
+        let mut skipped: HashMap<IneligibleReason, usize> = HashMap::new();
+        for reason in checks.iter().filter_map(|c| c.reason) {
+            *skipped.entry(reason).or_default() += 1;
+        }
+        for (reason, count) in skipped {
+            self.metrics.loops.record_skipped(reason.as_str(), count);
+        }
+        if full_sweep {
+            self.metrics
+                .markets_scored
+                .set(computed_scores.len() as i64);
+        }
+        self.save_checks(&checks, now, full_sweep).await;
 
-        if computed_scores.is_empty() {
+        let recommended = if computed_scores.is_empty() {
             tracing::debug!("No scores computed");
-            return Ok(());
-        }
+            HashSet::new()
+        } else {
+            self.save_scores(computed_scores, &markets, &quotes, &rules)
+                .await?
+        };
+
+        self.retract_stale(changed, &checks, &quotes, &recommended, now)
+            .await?;
+
+        self.run_shadow_models(&markets, &quotes, &rules, &trade_stats, now, full_sweep)
+            .await;
 
+        tracing::info!("Scoring cycle complete");
+        Ok(())
+    }
+
+    /// Save the scoring model's scores and the recommendations made from
+    /// them, returning the ids of the recommended markets
+    async fn save_scores(
+        &self,
+        computed_scores: Vec<Score>,
+        markets: &[Market],
+        quotes: &HashMap<String, Quote>,
+        rules: &HashMap<String, RuleSnapshot>,
+    ) -> Result<HashSet<String>> {
         tracing::info!(count = computed_scores.len(), "Computed scores");
 
         // Save scores to database
         scores::upsert_scores_batch(&self.pool, &computed_scores)
             .await
             .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+        self.metrics
+            .loops
+            .record_rows("scores_latest", computed_scores.len());
 
         // Build scores map for recommendation generation
         let scores_map: HashMap<String, Score> = computed_scores
@@ -141,9 +493,9 @@ impl ScoringOrchestrator {
             .collect();
 
         // Generate recommendations
-        let recommendations =
-            self.engine
-                .generate_recommendations_batch(&markets, &scores_map, &quotes, &rules);
+        let recommendations = self
+            .model
+            .recommend_batch(markets, &scores_map, quotes, rules);
 
         tracing::info!(count = recommendations.len(), "Generated recommendations");
 
@@ -152,9 +504,184 @@ impl ScoringOrchestrator {
             recs::upsert_recs_batch(&self.pool, &recommendations)
                 .await
                 .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+            self.metrics
+                .loops
+                .record_rows("recs_latest", recommendations.len());
         }
 
-        tracing::info!("Scoring cycle complete");
+        Ok(recommendations
+            .into_iter()
+            .map(|rec| rec.market_id)
+            .collect())
+    }
+
+    /// Save the cycle's eligibility checks for diagnostics
+    ///
+    /// A full sweep also drops checks of markets it no longer considered.
+    /// Failures are logged and never fail the cycle.
+    async fn save_checks(&self, checks: &[EligibilityCheck], now: DateTime<Utc>, full_sweep: bool) {
+        if let Err(e) = eligibility::upsert_checks(&self.pool, checks).await {
+            tracing::warn!(error = %e, "Failed to save eligibility checks");
+            return;
+        }
+        self.metrics
+            .loops
+            .record_rows("market_eligibility", checks.len());
+
+        if full_sweep {
+            if let Err(e) = eligibility::prune_checks(&self.pool, now).await {
+                tracing::warn!(error = %e, "Failed to prune eligibility checks");
+            }
+        }
+    }
+
+    /// Retract active recommendations that this cycle did not re-issue
+    ///
+    /// Considers every active recommendation on a full sweep (`scope` is
+    /// `None`), otherwise only those for the markets in `scope`. `checks`
+    /// cover the markets the cycle scored, with their `quotes`;
+    /// `recommended` holds the ids the model recommended.
+    async fn retract_stale(
+        &self,
+        scope: Option<&[String]>,
+        checks: &[EligibilityCheck],
+        quotes: &HashMap<String, Quote>,
+        recommended: &HashSet<String>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let active = recs::list_active_rec_ids(&self.pool, scope)
+            .await
+            .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+
+        let checks: HashMap<&str, &EligibilityCheck> =
+            checks.iter().map(|c| (c.market_id.as_str(), c)).collect();
+        let mut retractions = Vec::new();
+        for market_id in active {
+            if recommended.contains(&market_id) {
+                continue;
+            }
+
+            let reason = match checks.get(market_id.as_str()) {
+                Some(check) => Some(match check.reason {
+                    Some(reason) => RetractionReason::from(reason),
+                    None => self.not_recommended_reason(quotes.get(&market_id), now),
+                }),
+                None => self.dropped_out_reason(&market_id, now).await?,
+            };
+            if let Some(reason) = reason {
+                retractions.push((market_id, reason));
+            }
+        }
+
+        if retractions.is_empty() {
+            return Ok(());
+        }
+
+        let retracted = recs::retract_recs(&self.pool, &retractions, now)
+            .await
+            .map_err(|e| OrchestratorError::Storage(e.to_string()))?;
+        for (_, reason) in &retractions {
+            self.metrics
+                .recs_retracted_total
+                .with_label_values(&[reason.as_str()])
+                .inc();
+        }
+
+        tracing::info!(count = retracted, "Retracted stale recommendations");
         Ok(())
     }
+
+    /// Why a scored market's recommendation was withheld
+    fn not_recommended_reason(
+        &self,
+        quote: Option<&Quote>,
+        now: DateTime<Utc>,
+    ) -> RetractionReason {
+        let stale = quote
+            .is_none_or(|q| (now - q.as_of).num_seconds() > self.config.bounds.quote_stale_max_sec);
+
+        if stale {
+            RetractionReason::StaleQuote
+        } else {
+            RetractionReason::RiskIncrease
+        }
+    }
+
+    /// Why a market left the eligible set, or `None` if it is still
+    /// eligible and was only left out by the cycle's market limit
+    async fn dropped_out_reason(
+        &self,
+        market_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RetractionReason>> {
+        let market = match markets::get_market(&self.pool, market_id).await {
+            Ok(market) => market,
+            Err(MarketError::NotFound(_)) => return Ok(Some(RetractionReason::Closed)),
+            Err(e) => return Err(OrchestratorError::Storage(e.to_string())),
+        };
+
+        let Some(close_time) = market.close_time else {
+            return Ok(Some(RetractionReason::Ineligible));
+        };
+        if market.status != MarketStatus::Active || close_time <= now {
+            return Ok(Some(RetractionReason::Closed));
+        }
+
+        let t_remaining_sec = (close_time - now).num_seconds();
+        let bounds = &self.config.bounds;
+        if t_remaining_sec < bounds.min_t_remaining_sec {
+            Ok(Some(RetractionReason::Expired))
+        } else if t_remaining_sec > bounds.max_t_remaining_sec {
+            Ok(Some(RetractionReason::Ineligible))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Score the cycle's inputs with each shadow model into
+    /// `scores_shadow`
+    ///
+    /// A shadow model's failure is logged and never fails the cycle.
+    async fn run_shadow_models(
+        &self,
+        markets: &[Market],
+        quotes: &HashMap<String, Quote>,
+        rules: &HashMap<String, RuleSnapshot>,
+        trade_stats: &HashMap<String, TradeStats>,
+        now: DateTime<Utc>,
+        full_sweep: bool,
+    ) {
+        for shadow in &self.shadows {
+            if !full_sweep && !shadow.scores_independently() {
+                continue;
+            }
+
+            let mut cycle = self.metrics.loops.cycle("shadow_scoring");
+            let shadow_scores = shadow
+                .compute_scores_batch(markets, quotes, rules, trade_stats, now)
+                .scores;
+
+            match scores::upsert_shadow_scores_batch(&self.pool, shadow.id(), &shadow_scores).await
+            {
+                Ok(()) => {
+                    tracing::debug!(
+                        model = shadow.id(),
+                        count = shadow_scores.len(),
+                        "Saved shadow scores"
+                    );
+                    self.metrics
+                        .loops
+                        .record_rows("scores_shadow", shadow_scores.len());
+                }
+                Err(e) => {
+                    cycle.fail();
+                    tracing::warn!(
+                        model = shadow.id(),
+                        error = %e,
+                        "Failed to save shadow scores"
+                    );
+                }
+            }
+        }
+    }
 }
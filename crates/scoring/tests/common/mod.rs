@@ -0,0 +1,105 @@
+//! Helpers shared by scoring integration tests
+
+#![allow(dead_code)]
+
+use chrono::{DateTime, Duration, Utc};
+use pm_domain::{Market, MarketMetadata, MarketStatus, PriceSource, Quote, RiskFlag, RuleSnapshot};
+use pm_storage::{markets, quotes, rules};
+use sqlx::PgPool;
+
+/// Active market closing at `close_time`, without venue metadata
+pub fn market(id: &str, close_time: Option<DateTime<Utc>>) -> Market {
+    Market {
+        market_id: format!("polymarket:{id}"),
+        venue: "polymarket".to_string(),
+        venue_market_id: id.to_string(),
+        title: format!("Market {id}"),
+        slug: None,
+        category: None,
+        status: MarketStatus::Active,
+        open_time: None,
+        close_time,
+        resolved_time: None,
+        url: None,
+        metadata: MarketMetadata::default(),
+        outcomes: Vec::new(),
+    }
+}
+
+/// Observed quote for `market_id` taken at `as_of`
+fn quote(market_id: &str, as_of: DateTime<Utc>, no_bid: f64, no_ask: f64) -> Quote {
+    let (yes_bid, yes_ask) = (1.0 - no_ask, 1.0 - no_bid);
+    Quote {
+        market_id: market_id.to_string(),
+        as_of,
+        yes_bid: Some(yes_bid),
+        yes_ask: Some(yes_ask),
+        no_bid: Some(no_bid),
+        no_ask: Some(no_ask),
+        spread_yes: Some(yes_ask - yes_bid),
+        spread_no: Some(no_ask - no_bid),
+        mid_yes: Some((yes_bid + yes_ask) / 2.0),
+        mid_no: Some((no_bid + no_ask) / 2.0),
+        yes_source: PriceSource::Observed,
+        no_source: PriceSource::Observed,
+        quote_source: "polymarket".to_string(),
+    }
+}
+
+/// Rule snapshot for `market_id` taken at `as_of`
+fn rule(market_id: &str, as_of: DateTime<Utc>, flags: &[&str]) -> RuleSnapshot {
+    RuleSnapshot {
+        market_id: market_id.to_string(),
+        as_of,
+        rule_text: format!("{market_id} resolves per the official announcement."),
+        rule_hash: format!("{market_id}-rule"),
+        settlement_source: None,
+        settlement_window: None,
+        definition_risk_score: 0.3 * flags.len() as f64,
+        risk_flags: flags
+            .iter()
+            .map(|code| RiskFlag {
+                code: code.to_string(),
+                severity: "high".to_string(),
+                evidence_spans: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Store two scoreable markets with quotes and rules as of `now`, and
+/// return their ids
+///
+/// `polymarket:0xa1` closes three days after `now` with $95k of liquidity;
+/// `polymarket:0xb2` closes two days later with $8.2k and a subjective
+/// resolution flag.
+pub async fn seed_two_markets(pool: &PgPool, now: DateTime<Utc>) -> Vec<String> {
+    let mut a1 = market("0xa1", Some(now + Duration::days(3)));
+    a1.metadata.liquidity = Some(95_000.0);
+    let mut b2 = market("0xb2", Some(now + Duration::days(5)));
+    b2.metadata.liquidity = Some(8_200.0);
+    markets::upsert_markets_batch(pool, &[a1, b2], now)
+        .await
+        .unwrap();
+
+    quotes::upsert_quotes_latest_batch(
+        pool,
+        &[
+            quote("polymarket:0xa1", now, 0.955, 0.962),
+            quote("polymarket:0xb2", now, 0.91, 0.93),
+        ],
+    )
+    .await
+    .unwrap();
+    rules::upsert_rule(pool, &rule("polymarket:0xa1", now, &[]))
+        .await
+        .unwrap();
+    rules::upsert_rule(
+        pool,
+        &rule("polymarket:0xb2", now, &["SUBJECTIVE_RESOLUTION"]),
+    )
+    .await
+    .unwrap();
+
+    vec!["polymarket:0xa1".to_string(), "polymarket:0xb2".to_string()]
+}
@@ -0,0 +1,111 @@
+//! Scoring records why each candidate market was or was not scored
+
+mod common;
+
+use chrono::Utc;
+use pm_domain::IneligibleReason;
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::{
+    eligibility::{self, ReasonCount},
+    markets,
+};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn each_candidate_gets_an_eligibility_check(pool: PgPool) {
+    common::seed_two_markets(&pool, Utc::now()).await;
+
+    // Active markets the scoring window leaves out are still checked
+    let undated = common::market("0xc3", None);
+    let distant = common::market("0xd4", Some(Utc::now() + chrono::Duration::days(60)));
+    markets::upsert_markets_batch(&pool, &[undated, distant], Utc::now())
+        .await
+        .unwrap();
+
+    let mut config = ScoringConfig::default();
+    ScoringOrchestrator::new(pool.clone(), config.clone())
+        .run_scoring_cycle()
+        .await
+        .unwrap();
+
+    let check = eligibility::get_check(&pool, "polymarket:0xb2")
+        .await
+        .unwrap();
+    assert!(check.is_eligible());
+    let undated = eligibility::get_check(&pool, "polymarket:0xc3")
+        .await
+        .unwrap();
+    assert_eq!(undated.reason, Some(IneligibleReason::MissingCloseTime));
+    let distant = eligibility::get_check(&pool, "polymarket:0xd4")
+        .await
+        .unwrap();
+    assert_eq!(distant.reason, Some(IneligibleReason::OutsideWindow));
+
+    let mut counts = eligibility::count_by_reason(&pool).await.unwrap();
+    counts.sort_by_key(|c| c.reason.map(|r| r.as_str()));
+    assert_eq!(
+        counts,
+        vec![
+            ReasonCount {
+                reason: None,
+                markets: 2
+            },
+            ReasonCount {
+                reason: Some(IneligibleReason::MissingCloseTime),
+                markets: 1
+            },
+            ReasonCount {
+                reason: Some(IneligibleReason::OutsideWindow),
+                markets: 1
+            },
+        ]
+    );
+
+    // 0xb2 shows $8.2k of liquidity, under the new floor
+    config.activity.min_liquidity = Some(10_000.0);
+    let scoring = ScoringOrchestrator::new(pool.clone(), config);
+    scoring.run_scoring_cycle().await.unwrap();
+
+    let check = eligibility::get_check(&pool, "polymarket:0xb2")
+        .await
+        .unwrap();
+    assert_eq!(check.reason, Some(IneligibleReason::LowLiquidity));
+    assert!(check.detail.unwrap().contains("below minimum"));
+    assert!(eligibility::get_check(&pool, "polymarket:0xa1")
+        .await
+        .unwrap()
+        .is_eligible());
+
+    let mut counts = eligibility::count_by_reason(&pool).await.unwrap();
+    counts.sort_by_key(|c| c.reason.map(|r| r.as_str()));
+    assert_eq!(
+        counts,
+        vec![
+            ReasonCount {
+                reason: None,
+                markets: 1
+            },
+            ReasonCount {
+                reason: Some(IneligibleReason::LowLiquidity),
+                markets: 1
+            },
+            ReasonCount {
+                reason: Some(IneligibleReason::MissingCloseTime),
+                markets: 1
+            },
+            ReasonCount {
+                reason: Some(IneligibleReason::OutsideWindow),
+                markets: 1
+            },
+        ]
+    );
+    assert_eq!(
+        scoring
+            .metrics()
+            .loops
+            .markets_skipped_total
+            .with_label_values(&["low_liquidity"])
+            .get(),
+        1
+    );
+}
@@ -0,0 +1,68 @@
+//! Recommendations that a scoring cycle does not re-issue are retracted
+
+mod common;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use pm_domain::{Clock, RetractionReason, SharedClock, SimulatedClock};
+use pm_scoring::{ScoringConfig, ScoringOrchestrator};
+use pm_storage::recs::{self, RecFilter};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn recs_are_retracted_when_markets_drop_out(pool: PgPool) {
+    // 0xa1 closes three days after the inputs were stored and 0xb2 five
+    let start = Utc::now();
+    let clock = Arc::new(SimulatedClock::new(start));
+    let shared: SharedClock = clock.clone();
+    common::seed_two_markets(&pool, start).await;
+
+    let config = ScoringConfig::default();
+    let scoring = ScoringOrchestrator::new(pool.clone(), config).with_clock(Arc::clone(&shared));
+
+    clock.advance(chrono::Duration::minutes(1));
+    scoring.run_scoring_cycle().await.unwrap();
+    let issued = recs::list_recs(&pool, &RecFilter::default(), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(issued.len(), 2);
+    assert!(issued.iter().all(|r| r.retraction.is_none()));
+
+    // Within 0xa1's final hour it leaves the scoring window, while 0xb2 is
+    // still in the window but its quote has long gone stale
+    clock.set(start + chrono::Duration::days(3) - chrono::Duration::minutes(30));
+    scoring.run_scoring_cycle().await.unwrap();
+
+    let expired = recs::get_rec(&pool, "polymarket:0xa1").await.unwrap();
+    let retraction = expired.retraction.expect("0xa1 was not retracted");
+    assert_eq!(retraction.reason, RetractionReason::Expired);
+    assert_eq!(retraction.at.timestamp(), clock.now().timestamp());
+
+    let stale = recs::get_rec(&pool, "polymarket:0xb2").await.unwrap();
+    assert_eq!(
+        stale.retraction.map(|r| r.reason),
+        Some(RetractionReason::StaleQuote)
+    );
+
+    // Retracted recommendations are hidden unless asked for
+    assert!(recs::list_recs(&pool, &RecFilter::default(), 10, 0)
+        .await
+        .unwrap()
+        .is_empty());
+    let all = RecFilter {
+        include_retracted: true,
+        ..RecFilter::default()
+    };
+    assert_eq!(recs::list_recs(&pool, &all, 10, 0).await.unwrap().len(), 2);
+
+    // Later cycles keep the original retraction
+    clock.advance(chrono::Duration::days(1));
+    scoring.run_scoring_cycle().await.unwrap();
+    let closed = recs::get_rec(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(closed.retraction.map(|r| r.at), Some(retraction.at));
+
+    let retracted = &scoring.metrics().recs_retracted_total;
+    assert_eq!(retracted.with_label_values(&["expired"]).get(), 1);
+    assert_eq!(retracted.with_label_values(&["stale_quote"]).get(), 1);
+}
@@ -0,0 +1,68 @@
+//! Shadow scoring models run alongside the primary model
+
+mod common;
+
+use chrono::Utc;
+use pm_scoring::{
+    config::{ScoringWeights, ShadowModelConfig},
+    engine::DEFAULT_MODEL_ID,
+    ScoringConfig, ScoringEngine, ScoringOrchestrator,
+};
+use pm_storage::scores;
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn shadow_models_score_into_their_own_table(pool: PgPool) {
+    common::seed_two_markets(&pool, Utc::now()).await;
+
+    // One variant from configuration, one added by the caller
+    let mut config = ScoringConfig::default();
+    config.shadow_models.push(ShadowModelConfig {
+        id: "velocity_only".to_string(),
+        weights: Some(ScoringWeights {
+            w1: 1.0,
+            w2: 0.0,
+            w3: 0.0,
+            w4: 0.0,
+            w5: 0.0,
+        }),
+        normalization: None,
+    });
+    let fee_heavy = ScoringConfig {
+        fee_bps: 500.0,
+        ..config.clone()
+    };
+    let scoring = ScoringOrchestrator::new(pool.clone(), config)
+        .with_shadow_model(ScoringEngine::new(fee_heavy).with_id("fee_heavy"));
+    scoring.run_scoring_cycle().await.unwrap();
+
+    let primary = scores::get_score(&pool, "polymarket:0xa1").await.unwrap();
+    assert_eq!(primary.fee_bps, 120.0);
+
+    let velocity_only = scores::list_shadow_scores(&pool, "velocity_only", 10)
+        .await
+        .unwrap();
+    assert_eq!(velocity_only.len(), 2);
+    let shadow = velocity_only
+        .iter()
+        .find(|s| s.market_id == "polymarket:0xa1")
+        .unwrap();
+    assert_ne!(shadow.overall_score, primary.overall_score);
+    assert_eq!(shadow.net_yield, primary.net_yield);
+
+    let fee_heavy = scores::list_shadow_scores(&pool, "fee_heavy", 10)
+        .await
+        .unwrap();
+    assert_eq!(fee_heavy.len(), 2);
+    assert!(fee_heavy.iter().all(|s| s.fee_bps == 500.0));
+
+    // Shadow scores never reach the primary tables
+    assert!(scores::list_shadow_scores(&pool, DEFAULT_MODEL_ID, 10)
+        .await
+        .unwrap()
+        .is_empty());
+    let top = scores::list_top_scores(&pool, None, None, 10, 0)
+        .await
+        .unwrap();
+    assert!(top.iter().all(|s| s.fee_bps == 120.0));
+}
@@ -0,0 +1,115 @@
+//! Market change notifications from ingest to scoring
+//!
+//! Ingest calls [`notify_markets_changed`] after upserting quotes or rules,
+//! which sends the market ids over Postgres `NOTIFY` on
+//! [`MARKET_CHANGES_CHANNEL`]. Scoring listens with a
+//! [`MarketChangeListener`] and re-scores just those markets. Notifications
+//! are best effort: any sent while no listener is connected are lost.
+
+use sqlx::{postgres::PgListener, PgPool};
+
+/// Error type for change notification operations
+#[derive(Debug, thiserror::Error)]
+pub enum ChangeError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ChangeError>;
+
+/// Channel carrying newline-separated ids of markets whose inputs changed
+pub const MARKET_CHANGES_CHANNEL: &str = "pm_market_changes";
+
+/// Payload budget per notification; Postgres rejects payloads of 8000
+/// bytes or more
+const MAX_PAYLOAD_BYTES: usize = 7000;
+
+/// Split `market_ids` into newline-separated payloads under the size limit
+fn payloads(market_ids: &[String]) -> Vec<String> {
+    let mut payloads = Vec::new();
+    let mut current = String::new();
+
+    for id in market_ids {
+        if !current.is_empty() && current.len() + 1 + id.len() > MAX_PAYLOAD_BYTES {
+            payloads.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(id);
+    }
+    if !current.is_empty() {
+        payloads.push(current);
+    }
+
+    payloads
+}
+
+/// Notify listeners that quotes or rules changed for `market_ids`
+pub async fn notify_markets_changed(pool: &PgPool, market_ids: &[String]) -> Result<()> {
+    let payloads = payloads(market_ids);
+    if payloads.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "sent!"
+        FROM (SELECT pg_notify($1, payload) FROM UNNEST($2::text[]) AS payload) n
+        "#,
+        MARKET_CHANGES_CHANNEL,
+        &payloads
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Receives market change notifications on a dedicated connection
+pub struct MarketChangeListener {
+    listener: PgListener,
+}
+
+impl MarketChangeListener {
+    /// Connect and `LISTEN` on [`MARKET_CHANGES_CHANNEL`]
+    pub async fn connect(pool: &PgPool) -> Result<Self> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(MARKET_CHANGES_CHANNEL).await?;
+        Ok(Self { listener })
+    }
+
+    /// Wait for the next notification and return its market ids
+    ///
+    /// A lost connection is re-established on the next call; changes
+    /// notified in between are missed.
+    pub async fn recv(&mut self) -> Result<Vec<String>> {
+        let notification = self.listener.recv().await?;
+        Ok(notification
+            .payload()
+            .lines()
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payloads_stay_under_the_limit() {
+        assert!(payloads(&[]).is_empty());
+
+        let ids: Vec<String> = (0..500)
+            .map(|i| format!("polymarket:0x{:064x}", i))
+            .collect();
+        let payloads = payloads(&ids);
+        assert!(payloads.len() > 1);
+        assert!(payloads.iter().all(|p| p.len() <= MAX_PAYLOAD_BYTES));
+
+        let rejoined: Vec<&str> = payloads.iter().flat_map(|p| p.lines()).collect();
+        assert_eq!(rejoined, ids);
+    }
+}
@@ -0,0 +1,121 @@
+//! Database operations for scoring eligibility diagnostics
+
+use chrono::{DateTime, Utc};
+use pm_domain::{EligibilityCheck, IneligibleReason};
+use sqlx::PgPool;
+
+/// Error type for eligibility operations
+#[derive(Debug, thiserror::Error)]
+pub enum EligibilityError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("No eligibility check for market: {0}")]
+    NotFound(String),
+    #[error("Invalid ineligibility reason: {0}")]
+    InvalidReason(#[from] pm_domain::ParseIneligibleReasonError),
+}
+
+pub type Result<T> = std::result::Result<T, EligibilityError>;
+
+/// Markets whose latest check ended with `reason` (`None` for eligible)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReasonCount {
+    pub reason: Option<IneligibleReason>,
+    pub markets: i64,
+}
+
+/// Batch upsert each market's latest eligibility check
+pub async fn upsert_checks(pool: &PgPool, checks: &[EligibilityCheck]) -> Result<()> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    let market_ids: Vec<String> = checks.iter().map(|c| c.market_id.clone()).collect();
+    let checked_at: Vec<DateTime<Utc>> = checks.iter().map(|c| c.checked_at).collect();
+    let reasons: Vec<Option<String>> = checks
+        .iter()
+        .map(|c| c.reason.map(|r| r.as_str().to_string()))
+        .collect();
+    let details: Vec<Option<String>> = checks.iter().map(|c| c.detail.clone()).collect();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO market_eligibility (market_id, checked_at, reason, detail)
+        SELECT * FROM UNNEST($1::text[], $2::timestamptz[], $3::text[], $4::text[])
+        ON CONFLICT (market_id)
+        DO UPDATE SET
+            checked_at = EXCLUDED.checked_at,
+            reason = EXCLUDED.reason,
+            detail = EXCLUDED.detail
+        "#,
+        &market_ids,
+        &checked_at,
+        &reasons as &[Option<String>],
+        &details as &[Option<String>]
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Delete checks older than `before`, e.g. for markets a full sweep no
+/// longer considered; returns how many were deleted
+pub async fn prune_checks(pool: &PgPool, before: DateTime<Utc>) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM market_eligibility
+        WHERE checked_at < $1
+        "#,
+        before
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Get a market's latest eligibility check
+pub async fn get_check(pool: &PgPool, market_id: &str) -> Result<EligibilityCheck> {
+    let row = sqlx::query!(
+        r#"
+        SELECT market_id, checked_at, reason, detail
+        FROM market_eligibility
+        WHERE market_id = $1
+        "#,
+        market_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| EligibilityError::NotFound(market_id.to_string()))?;
+
+    Ok(EligibilityCheck {
+        market_id: row.market_id,
+        checked_at: row.checked_at,
+        reason: row.reason.as_deref().map(str::parse).transpose()?,
+        detail: row.detail,
+    })
+}
+
+/// Count markets by the outcome of their latest check, largest first
+pub async fn count_by_reason(pool: &PgPool) -> Result<Vec<ReasonCount>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT reason, COUNT(*) AS "markets!"
+        FROM market_eligibility
+        GROUP BY reason
+        ORDER BY COUNT(*) DESC, reason
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ReasonCount {
+                reason: row.reason.as_deref().map(str::parse).transpose()?,
+                markets: row.markets,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,249 @@
+//! Database operations for ingest checkpoint state
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// Error type for ingest state operations
+#[derive(Debug, thiserror::Error)]
+pub enum IngestStateError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, IngestStateError>;
+
+/// Checkpoint of one ingest task
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskState {
+    pub task: String,
+    /// When the task last completed a full cycle
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Where an interrupted pass resumes
+    pub cursor: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Per-market venue fetch tracked in `ingest_market_state`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketFetch {
+    Quote,
+    Rule,
+    Outcome,
+    Trade,
+}
+
+impl MarketFetch {
+    /// Name used in queries
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketFetch::Quote => "quote",
+            MarketFetch::Rule => "rule",
+            MarketFetch::Outcome => "outcome",
+            MarketFetch::Trade => "trade",
+        }
+    }
+}
+
+impl fmt::Display for MarketFetch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Last venue fetch times for one market
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketFetchState {
+    pub market_id: String,
+    pub last_quote_at: Option<DateTime<Utc>>,
+    pub last_rule_at: Option<DateTime<Utc>>,
+    pub last_outcome_at: Option<DateTime<Utc>>,
+    pub last_trade_at: Option<DateTime<Utc>>,
+}
+
+/// Get a task's checkpoint, if it has ever recorded one
+pub async fn get_task_state(pool: &PgPool, task: &str) -> Result<Option<TaskState>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT task, last_success_at, cursor, updated_at
+        FROM ingest_state
+        WHERE task = $1
+        "#,
+        task
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| TaskState {
+        task: r.task,
+        last_success_at: r.last_success_at,
+        cursor: r.cursor,
+        updated_at: r.updated_at,
+    }))
+}
+
+/// Record a completed cycle; a completed pass needs no cursor, so any is
+/// cleared
+pub async fn record_task_success(pool: &PgPool, task: &str, at: DateTime<Utc>) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO ingest_state (task, last_success_at, cursor, updated_at)
+        VALUES ($1, $2, NULL, $2)
+        ON CONFLICT (task) DO UPDATE SET
+            last_success_at = EXCLUDED.last_success_at,
+            cursor = NULL,
+            updated_at = EXCLUDED.updated_at
+        "#,
+        task,
+        at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record where an in-progress pass should resume
+pub async fn set_task_cursor(
+    pool: &PgPool,
+    task: &str,
+    cursor: Option<&str>,
+    at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO ingest_state (task, cursor, updated_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (task) DO UPDATE SET
+            cursor = EXCLUDED.cursor,
+            updated_at = EXCLUDED.updated_at
+        "#,
+        task,
+        cursor,
+        at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a successful fetch for markets; ids not in `markets` are skipped
+pub async fn record_market_fetches(
+    pool: &PgPool,
+    fetch: MarketFetch,
+    market_ids: &[String],
+    at: DateTime<Utc>,
+) -> Result<()> {
+    if market_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO ingest_market_state (
+            market_id, last_quote_at, last_rule_at, last_outcome_at, last_trade_at,
+            updated_at
+        )
+        SELECT
+            m.market_id,
+            CASE WHEN $2 = 'quote' THEN $3::timestamptz END,
+            CASE WHEN $2 = 'rule' THEN $3::timestamptz END,
+            CASE WHEN $2 = 'outcome' THEN $3::timestamptz END,
+            CASE WHEN $2 = 'trade' THEN $3::timestamptz END,
+            $3
+        FROM markets m
+        WHERE m.market_id = ANY($1)
+        ON CONFLICT (market_id) DO UPDATE SET
+            last_quote_at = COALESCE(EXCLUDED.last_quote_at, ingest_market_state.last_quote_at),
+            last_rule_at = COALESCE(EXCLUDED.last_rule_at, ingest_market_state.last_rule_at),
+            last_outcome_at =
+                COALESCE(EXCLUDED.last_outcome_at, ingest_market_state.last_outcome_at),
+            last_trade_at = COALESCE(EXCLUDED.last_trade_at, ingest_market_state.last_trade_at),
+            updated_at = EXCLUDED.updated_at
+        "#,
+        market_ids,
+        fetch.as_str(),
+        at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Get fetch times for multiple markets; markets never fetched are omitted
+pub async fn get_market_states(
+    pool: &PgPool,
+    market_ids: &[String],
+) -> Result<Vec<MarketFetchState>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id, last_quote_at, last_rule_at, last_outcome_at, last_trade_at
+        FROM ingest_market_state
+        WHERE market_id = ANY($1)
+        ORDER BY market_id
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| MarketFetchState {
+            market_id: r.market_id,
+            last_quote_at: r.last_quote_at,
+            last_rule_at: r.last_rule_at,
+            last_outcome_at: r.last_outcome_at,
+            last_trade_at: r.last_trade_at,
+        })
+        .collect())
+}
+
+/// List active markets closing between `min_time_remaining` and
+/// `max_time_remaining` seconds after `now`, least recently fetched first
+///
+/// Markets never fetched come first, then by close time, so a bounded
+/// cycle works through every market instead of the same leading ones.
+pub async fn list_due_markets(
+    pool: &PgPool,
+    fetch: MarketFetch,
+    now: DateTime<Utc>,
+    min_time_remaining: i64,
+    max_time_remaining: i64,
+    limit: i64,
+) -> Result<Vec<String>> {
+    let min_close = now + chrono::Duration::seconds(min_time_remaining);
+    let max_close = now + chrono::Duration::seconds(max_time_remaining);
+
+    let rows = sqlx::query_scalar!(
+        r#"
+        SELECT m.market_id
+        FROM markets m
+        LEFT JOIN ingest_market_state s ON s.market_id = m.market_id
+        WHERE m.status = 'active'
+          AND m.close_time IS NOT NULL
+          AND m.close_time >= $2
+          AND m.close_time <= $3
+        ORDER BY
+            CASE $1
+                WHEN 'quote' THEN s.last_quote_at
+                WHEN 'rule' THEN s.last_rule_at
+                WHEN 'trade' THEN s.last_trade_at
+                ELSE s.last_outcome_at
+            END ASC NULLS FIRST,
+            m.close_time ASC
+        LIMIT $4
+        "#,
+        fetch.as_str(),
+        min_close,
+        max_close,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
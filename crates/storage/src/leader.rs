@@ -0,0 +1,326 @@
+//! Leader election over Postgres session advisory locks
+//!
+//! Each service role maps to one advisory lock key. An instance becomes
+//! leader by taking the lock on a dedicated connection and stays leader for
+//! as long as that session lives; if the session drops, Postgres releases
+//! the lock and a standby polling `pg_try_advisory_lock` takes over.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, PgConnection, PgPool};
+use tokio_util::sync::CancellationToken;
+
+/// Error type for leader election operations
+#[derive(Debug, thiserror::Error)]
+pub enum LeaderError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, LeaderError>;
+
+/// Service whose replicas elect a single leader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LeaderRole {
+    Ingest,
+    Scoring,
+}
+
+/// Error returned when parsing an unknown leader role string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown leader role: {0}")]
+pub struct ParseLeaderRoleError(pub String);
+
+impl LeaderRole {
+    /// Every role, in lock key order
+    pub const ALL: [LeaderRole; 2] = [LeaderRole::Ingest, LeaderRole::Scoring];
+
+    /// Canonical lowercase name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderRole::Ingest => "ingest",
+            LeaderRole::Scoring => "scoring",
+        }
+    }
+
+    /// Advisory lock key; kept below 2^32 so it shows up in `pg_locks` as
+    /// `classid = 0, objid = key`
+    pub fn lock_key(&self) -> i64 {
+        match self {
+            LeaderRole::Ingest => 0x706d_0001,
+            LeaderRole::Scoring => 0x706d_0002,
+        }
+    }
+
+    fn from_lock_key(key: i64) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.lock_key() == key)
+    }
+}
+
+impl fmt::Display for LeaderRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LeaderRole {
+    type Err = ParseLeaderRoleError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ingest" => Ok(LeaderRole::Ingest),
+            "scoring" => Ok(LeaderRole::Scoring),
+            _ => Err(ParseLeaderRoleError(s.to_string())),
+        }
+    }
+}
+
+/// Leader election settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderConfig {
+    /// Elect a leader before doing any work; disable for single-replica
+    /// deployments that should not hold an extra connection
+    pub enabled: bool,
+
+    /// How often a standby retries the lock (milliseconds)
+    pub retry_interval_ms: u64,
+
+    /// How often the leader checks its lock session is alive (milliseconds)
+    pub check_interval_ms: u64,
+}
+
+impl Default for LeaderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retry_interval_ms: 2000,
+            check_interval_ms: 1000,
+        }
+    }
+}
+
+/// Name identifying this replica: the role, host and process id
+pub fn instance_name(role: LeaderRole) -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+    format!("pm-{}@{}:{}", role, host, std::process::id())
+}
+
+/// Contends for leadership of one role
+pub struct LeaderElection {
+    pool: PgPool,
+    role: LeaderRole,
+    instance: String,
+    config: LeaderConfig,
+    leader: Arc<AtomicBool>,
+}
+
+impl LeaderElection {
+    /// Create an election for `role`; `instance` names this replica in
+    /// `pg_stat_activity.application_name` while it leads
+    pub fn new(pool: PgPool, role: LeaderRole, instance: &str, config: LeaderConfig) -> Self {
+        Self {
+            pool,
+            role,
+            instance: instance.to_string(),
+            config,
+            leader: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Role contended for
+    pub fn role(&self) -> LeaderRole {
+        self.role
+    }
+
+    /// Whether this replica currently holds the lock
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::SeqCst)
+    }
+
+    /// Wait until this replica holds the lock
+    ///
+    /// Returns `None` if `cancellation` fires first. Database errors are
+    /// logged and retried.
+    pub async fn acquire(&self, cancellation: &CancellationToken) -> Option<Leadership> {
+        let retry = Duration::from_millis(self.config.retry_interval_ms);
+        let mut waiting = false;
+
+        loop {
+            match self.try_acquire().await {
+                Ok(Some(conn)) => {
+                    self.leader.store(true, Ordering::SeqCst);
+                    tracing::info!(
+                        role = %self.role,
+                        instance = %self.instance,
+                        "Acquired leadership"
+                    );
+                    return Some(Leadership {
+                        conn,
+                        role: self.role,
+                        check_interval: Duration::from_millis(self.config.check_interval_ms),
+                        flag: LeaderFlag(Arc::clone(&self.leader)),
+                    });
+                }
+                Ok(None) if !waiting => {
+                    waiting = true;
+                    tracing::info!(
+                        role = %self.role,
+                        instance = %self.instance,
+                        "Another replica leads, standing by"
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(role = %self.role, error = %e, "Leader election failed");
+                }
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(retry) => {}
+                _ = cancellation.cancelled() => return None,
+            }
+        }
+    }
+
+    /// Try the lock once; a session that gets it leaves the pool so the
+    /// lock is never handed to unrelated queries
+    async fn try_acquire(&self) -> Result<Option<PgConnection>> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query!(
+            "SELECT set_config('application_name', $1, false)",
+            self.instance
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let locked = sqlx::query_scalar!(
+            r#"SELECT pg_try_advisory_lock($1) AS "locked!""#,
+            self.role.lock_key()
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if locked {
+            Ok(Some(conn.detach()))
+        } else {
+            // Back to the pool without this replica's name
+            sqlx::query!("RESET application_name")
+                .execute(&mut *conn)
+                .await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Held leadership; the lock lives as long as the session behind it
+pub struct Leadership {
+    conn: PgConnection,
+    role: LeaderRole,
+    check_interval: Duration,
+    flag: LeaderFlag,
+}
+
+/// Clears the election's leader flag when leadership ends
+struct LeaderFlag(Arc<AtomicBool>);
+
+impl Drop for LeaderFlag {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Leadership {
+    /// Resolves once the lock session is gone, at which point a standby
+    /// may already lead
+    ///
+    /// A check that gets no answer within the check interval counts as lost:
+    /// on a half-open connection the server may already have dropped the
+    /// session and released the lock.
+    pub async fn lost(&mut self) {
+        loop {
+            tokio::time::sleep(self.check_interval).await;
+
+            let probe = sqlx::query!("SELECT 1 AS alive").fetch_one(&mut self.conn);
+            let error = match tokio::time::timeout(self.check_interval, probe).await {
+                Ok(Ok(_)) => continue,
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => "leader session did not answer in time".to_string(),
+            };
+
+            self.flag.0.store(false, Ordering::SeqCst);
+            tracing::error!(role = %self.role, error = %error, "Lost leadership");
+            return;
+        }
+    }
+
+    /// Give up leadership by closing the lock session
+    pub async fn release(self) {
+        if let Err(e) = self.conn.close().await {
+            tracing::warn!(role = %self.role, error = %e, "Failed to close leader session");
+        }
+        tracing::info!(role = %self.role, "Released leadership");
+    }
+}
+
+/// Current holder of a role's lock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderInfo {
+    pub role: LeaderRole,
+    /// Backend pid of the lock session
+    pub pid: i32,
+    /// Replica name set by the leader
+    pub instance: Option<String>,
+    /// When the lock session connected
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// List the replicas holding each role's lock in this database
+pub async fn list_leaders(pool: &PgPool) -> Result<Vec<LeaderInfo>> {
+    let keys: Vec<i64> = LeaderRole::ALL.iter().map(|r| r.lock_key()).collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            l.objid::bigint AS "key!",
+            l.pid AS "pid!",
+            a.application_name,
+            a.backend_start
+        FROM pg_locks l
+        LEFT JOIN pg_stat_activity a ON a.pid = l.pid
+        WHERE l.locktype = 'advisory'
+          AND l.granted
+          AND l.classid = 0
+          AND l.objsubid = 1
+          AND l.database = (SELECT oid FROM pg_database WHERE datname = current_database())
+          AND l.objid::bigint = ANY($1)
+        ORDER BY l.objid
+        "#,
+        &keys
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(LeaderInfo {
+                role: LeaderRole::from_lock_key(r.key)?,
+                pid: r.pid,
+                instance: r.application_name.filter(|n| !n.is_empty()),
+                since: r.backend_start,
+            })
+        })
+        .collect())
+}
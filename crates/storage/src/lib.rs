@@ -2,10 +2,17 @@
 //!
 //! This crate provides the PostgreSQL storage layer using SQLx.
 
+pub mod changes;
+pub mod eligibility;
+pub mod ingest_state;
+pub mod leader;
 pub mod markets;
+pub mod payloads;
 pub mod quotes;
 pub mod recs;
 pub mod rules;
+pub mod schema_drift;
 pub mod scores;
+pub mod trades;
 
 pub use sqlx::PgPool;
@@ -1,8 +1,13 @@
 //! Database operations for markets
 
-use chrono::Utc;
-use pm_domain::{Market, MarketStatus, Outcome};
-use sqlx::PgPool;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use pm_domain::{
+    Market, MarketMetadata, MarketStatus, MarketStatusChange, Outcome, TransitionCheck,
+};
+use sqlx::{PgPool, Postgres, Transaction};
 
 /// Error type for market operations
 #[derive(Debug, thiserror::Error)]
@@ -11,19 +16,158 @@ pub enum MarketError {
     Database(#[from] sqlx::Error),
     #[error("Market not found: {0}")]
     NotFound(String),
+    #[error("Market id is ambiguous across venues: {0}")]
+    Ambiguous(String),
+    #[error("Status transition {from} -> {to} not applied for market {market_id}: {check:?}")]
+    IllegalTransition {
+        market_id: String,
+        from: MarketStatus,
+        to: MarketStatus,
+        check: TransitionCheck,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, MarketError>;
 
-/// Insert or update a market
-pub async fn upsert_market(pool: &PgPool, market: &Market) -> Result<()> {
+/// Convert Option<f64> to Option<BigDecimal>
+fn opt_f64_to_bigdecimal(val: Option<f64>) -> Option<BigDecimal> {
+    val.map(|v| BigDecimal::from_str(&v.to_string()).unwrap_or_else(|_| BigDecimal::from(0)))
+}
+
+/// Convert Option<BigDecimal> to Option<f64>
+fn opt_bigdecimal_to_f64(val: Option<BigDecimal>) -> Option<f64> {
+    val.map(|v| v.to_string().parse().unwrap_or(0.0))
+}
+
+/// Source recorded for status changes observed by discovery upserts
+const DISCOVERY_SOURCE: &str = "discovery";
+
+/// Columns selected for a [`Market`]
+struct MarketRow {
+    market_id: String,
+    venue: String,
+    venue_market_id: String,
+    title: String,
+    slug: Option<String>,
+    category: Option<String>,
+    status: String,
+    open_time: Option<DateTime<Utc>>,
+    close_time: Option<DateTime<Utc>>,
+    resolved_time: Option<DateTime<Utc>>,
+    url: Option<String>,
+    event_id: Option<String>,
+    event_title: Option<String>,
+    tags: Vec<String>,
+    volume_24h: Option<BigDecimal>,
+    volume_total: Option<BigDecimal>,
+    liquidity: Option<BigDecimal>,
+    neg_risk: bool,
+    image_url: Option<String>,
+    icon_url: Option<String>,
+}
+
+impl From<MarketRow> for Market {
+    fn from(row: MarketRow) -> Self {
+        Market {
+            market_id: row.market_id,
+            venue: row.venue,
+            venue_market_id: row.venue_market_id,
+            title: row.title,
+            slug: row.slug,
+            category: row.category,
+            status: parse_market_status(&row.status),
+            open_time: row.open_time,
+            close_time: row.close_time,
+            resolved_time: row.resolved_time,
+            url: row.url,
+            metadata: MarketMetadata {
+                event_id: row.event_id,
+                event_title: row.event_title,
+                tags: row.tags,
+                volume_24h: opt_bigdecimal_to_f64(row.volume_24h),
+                volume_total: opt_bigdecimal_to_f64(row.volume_total),
+                liquidity: opt_bigdecimal_to_f64(row.liquidity),
+                neg_risk: row.neg_risk,
+                image_url: row.image_url,
+                icon_url: row.icon_url,
+            },
+            outcomes: Vec::new(),
+        }
+    }
+}
+
+/// Insert or update a market, marking it as seen at `now`
+pub async fn upsert_market(pool: &PgPool, market: &Market, now: DateTime<Utc>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    upsert_market_in_tx(&mut tx, market, now).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Batch upsert markets, marking them as seen at `now`
+pub async fn upsert_markets_batch(
+    pool: &PgPool,
+    markets: &[Market],
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if markets.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    for market in markets {
+        upsert_market_in_tx(&mut tx, market, now).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Upsert a single market and any outcomes listed with it, marking it as
+/// seen at `now`
+///
+/// Status changes go through the domain state machine: accepted ones are
+/// recorded in the history table, others keep the stored status and are
+/// recorded for review while the rest of the row is still refreshed.
+async fn upsert_market_in_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    market: &Market,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let previous = sqlx::query!(
+        r#"
+        SELECT status
+        FROM markets
+        WHERE market_id = $1
+        FOR UPDATE
+        "#,
+        market.market_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .map(|row| parse_market_status(&row.status));
+
+    let check = previous.map(|from| from.check_transition(market.status));
+    let status = match (previous, check) {
+        (Some(from), Some(TransitionCheck::NeedsReview | TransitionCheck::Rejected)) => from,
+        _ => market.status,
+    };
+
     sqlx::query!(
         r#"
         INSERT INTO markets (
-            market_id, venue, title, slug, category, status,
-            open_time, close_time, resolved_time, url
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url, last_seen_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+            $12, $13, $14, $15, $16, $17, $18, $19, $20, $21
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         ON CONFLICT (market_id)
         DO UPDATE SET
             title = EXCLUDED.title,
@@ -32,82 +176,394 @@ pub async fn upsert_market(pool: &PgPool, market: &Market) -> Result<()> {
             status = EXCLUDED.status,
             open_time = EXCLUDED.open_time,
             close_time = EXCLUDED.close_time,
-            resolved_time = EXCLUDED.resolved_time,
+            resolved_time = COALESCE(EXCLUDED.resolved_time, markets.resolved_time),
             url = EXCLUDED.url,
-            updated_at = NOW()
+            event_id = EXCLUDED.event_id,
+            event_title = EXCLUDED.event_title,
+            tags = EXCLUDED.tags,
+            volume_24h = EXCLUDED.volume_24h,
+            volume_total = EXCLUDED.volume_total,
+            liquidity = EXCLUDED.liquidity,
+            neg_risk = EXCLUDED.neg_risk,
+            image_url = EXCLUDED.image_url,
+            icon_url = EXCLUDED.icon_url,
+            last_seen_at = EXCLUDED.last_seen_at,
+            updated_at = EXCLUDED.last_seen_at
         "#,
         market.market_id,
         market.venue,
+        market.venue_market_id,
         market.title,
         market.slug,
         market.category,
-        format!("{:?}", market.status).to_lowercase(),
+        status.as_str(),
         market.open_time,
         market.close_time,
         market.resolved_time,
-        market.url
+        market.url,
+        market.metadata.event_id,
+        market.metadata.event_title,
+        &market.metadata.tags,
+        opt_f64_to_bigdecimal(market.metadata.volume_24h),
+        opt_f64_to_bigdecimal(market.metadata.volume_total),
+        opt_f64_to_bigdecimal(market.metadata.liquidity),
+        market.metadata.neg_risk,
+        market.metadata.image_url,
+        market.metadata.icon_url,
+        now
     )
-    .execute(pool)
+    .execute(&mut **tx)
     .await?;
 
+    if let (Some(from), Some(check)) = (previous, check) {
+        record_transition(
+            tx,
+            &market.market_id,
+            from,
+            market.status,
+            check,
+            DISCOVERY_SOURCE,
+            None,
+            now,
+        )
+        .await?;
+    }
+
+    // Discovery pages may omit token ids that a detail fetch already stored
+    for outcome in &market.outcomes {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_outcomes (market_id, outcome, token_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (market_id, outcome)
+            DO UPDATE SET token_id = COALESCE(EXCLUDED.token_id, market_outcomes.token_id)
+            "#,
+            outcome.market_id,
+            outcome.outcome,
+            outcome.token_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
     Ok(())
 }
 
-/// Batch upsert markets
-pub async fn upsert_markets_batch(pool: &PgPool, markets: &[Market]) -> Result<()> {
-    if markets.is_empty() {
+/// Mark markets as seen at `at`, e.g. after the venue confirmed their status
+pub async fn mark_markets_seen(
+    pool: &PgPool,
+    market_ids: &[String],
+    at: DateTime<Utc>,
+) -> Result<()> {
+    if market_ids.is_empty() {
         return Ok(());
     }
 
+    sqlx::query!(
+        r#"
+        UPDATE markets
+        SET last_seen_at = $2
+        WHERE market_id = ANY($1)
+        "#,
+        market_ids,
+        at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Update a market's status through the state machine
+///
+/// Returns the previous status if the transition was applied, or `None` if
+/// the market already had the requested status. Transitions that are not
+/// allowed are recorded for review and returned as
+/// `MarketError::IllegalTransition`. A first move to `Resolved` stamps the
+/// resolution time with `now`.
+pub async fn update_market_status(
+    pool: &PgPool,
+    market_id: &str,
+    status: MarketStatus,
+    source: &str,
+    reason: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<MarketStatus>> {
     let mut tx = pool.begin().await?;
 
-    for market in markets {
+    let previous = sqlx::query!(
+        r#"
+        SELECT status
+        FROM markets
+        WHERE market_id = $1
+        FOR UPDATE
+        "#,
+        market_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .map(|row| parse_market_status(&row.status))
+    .ok_or_else(|| MarketError::NotFound(market_id.to_string()))?;
+
+    let check = previous.check_transition(status);
+
+    if check == TransitionCheck::Allowed {
+        let resolved_time = (status == MarketStatus::Resolved).then_some(now);
+
         sqlx::query!(
             r#"
-            INSERT INTO markets (
-                market_id, venue, title, slug, category, status,
-                open_time, close_time, resolved_time, url
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            ON CONFLICT (market_id)
-            DO UPDATE SET
-                title = EXCLUDED.title,
-                slug = EXCLUDED.slug,
-                category = EXCLUDED.category,
-                status = EXCLUDED.status,
-                open_time = EXCLUDED.open_time,
-                close_time = EXCLUDED.close_time,
-                resolved_time = EXCLUDED.resolved_time,
-                url = EXCLUDED.url,
-                updated_at = NOW()
+            UPDATE markets
+            SET status = $2,
+                resolved_time = COALESCE(resolved_time, $3),
+                updated_at = $4
+            WHERE market_id = $1
             "#,
-            market.market_id,
-            market.venue,
-            market.title,
-            market.slug,
-            market.category,
-            format!("{:?}", market.status).to_lowercase(),
-            market.open_time,
-            market.close_time,
-            market.resolved_time,
-            market.url
+            market_id,
+            status.as_str(),
+            resolved_time,
+            now
         )
         .execute(&mut *tx)
         .await?;
     }
 
+    record_transition(
+        &mut tx, market_id, previous, status, check, source, reason, now,
+    )
+    .await?;
+
     tx.commit().await?;
 
+    match check {
+        TransitionCheck::Unchanged => Ok(None),
+        TransitionCheck::Allowed => Ok(Some(previous)),
+        TransitionCheck::NeedsReview | TransitionCheck::Rejected => {
+            Err(MarketError::IllegalTransition {
+                market_id: market_id.to_string(),
+                from: previous,
+                to: status,
+                check,
+            })
+        }
+    }
+}
+
+/// Record the result of a transition check made at `at`: accepted changes
+/// go to the history table, others to the review table
+///
+/// A change that is proposed again while its review is still open is not
+/// recorded twice; applying any change resolves the market's open reviews.
+#[allow(clippy::too_many_arguments)]
+async fn record_transition(
+    tx: &mut Transaction<'_, Postgres>,
+    market_id: &str,
+    from: MarketStatus,
+    to: MarketStatus,
+    check: TransitionCheck,
+    source: &str,
+    reason: Option<&str>,
+    at: DateTime<Utc>,
+) -> Result<()> {
+    match check {
+        TransitionCheck::Unchanged => {}
+        TransitionCheck::Allowed => {
+            insert_status_change(tx, market_id, from, to, source, reason, at).await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE market_status_reviews
+                SET resolved_at = $2
+                WHERE market_id = $1
+                  AND resolved_at IS NULL
+                "#,
+                market_id,
+                at
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+        TransitionCheck::NeedsReview | TransitionCheck::Rejected => {
+            let verdict = match check {
+                TransitionCheck::NeedsReview => "needs_review",
+                _ => "rejected",
+            };
+
+            let inserted = sqlx::query!(
+                r#"
+                INSERT INTO market_status_reviews (
+                    market_id, current_status, proposed_status, verdict, source, reason,
+                    created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (market_id, current_status, proposed_status)
+                    WHERE resolved_at IS NULL
+                DO NOTHING
+                "#,
+                market_id,
+                from.as_str(),
+                to.as_str(),
+                verdict,
+                source,
+                reason,
+                at
+            )
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+
+            if inserted > 0 {
+                tracing::warn!(
+                    market_id,
+                    from = %from,
+                    to = %to,
+                    verdict,
+                    source,
+                    "Market status transition not applied"
+                );
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Append a row to the status history, dated `changed_at`
+async fn insert_status_change(
+    tx: &mut Transaction<'_, Postgres>,
+    market_id: &str,
+    from: MarketStatus,
+    to: MarketStatus,
+    source: &str,
+    reason: Option<&str>,
+    changed_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO market_status_history (
+            market_id, from_status, to_status, source, reason, changed_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        market_id,
+        from.as_str(),
+        to.as_str(),
+        source,
+        reason,
+        changed_at
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    tracing::info!(
+        market_id,
+        from = %from,
+        to = %to,
+        source,
+        "Market status changed"
+    );
+
+    Ok(())
+}
+
+/// Get the status history for a market, most recent first
+pub async fn get_status_history(
+    pool: &PgPool,
+    market_id: &str,
+    limit: i64,
+) -> Result<Vec<MarketStatusChange>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id, from_status, to_status, source, reason, changed_at
+        FROM market_status_history
+        WHERE market_id = $1
+        ORDER BY changed_at DESC, id DESC
+        LIMIT $2
+        "#,
+        market_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| MarketStatusChange {
+            market_id: row.market_id,
+            from_status: row.from_status.as_deref().map(parse_market_status),
+            to_status: parse_market_status(&row.to_status),
+            source: row.source,
+            reason: row.reason,
+            changed_at: row.changed_at,
+        })
+        .collect())
+}
+
+/// List active or halted markets not seen since `seen_before`, least
+/// recently seen first
+pub async fn list_unseen_markets(
+    pool: &PgPool,
+    seen_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Market>> {
+    let rows = sqlx::query_as!(
+        MarketRow,
+        r#"
+        SELECT
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url
+        FROM markets
+        WHERE status IN ('active', 'halted')
+          AND last_seen_at < $1
+        ORDER BY last_seen_at ASC
+        LIMIT $2
+        "#,
+        seen_before,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(Market::from).collect())
+}
+
+/// Resolve a market reference to its namespaced key
+///
+/// Accepts a namespaced key (`polymarket:0xabc`) or, for backward
+/// compatibility, a bare venue id (`0xabc`) as long as only one venue uses it.
+pub async fn resolve_market_id(pool: &PgPool, reference: &str) -> Result<String> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id
+        FROM markets
+        WHERE market_id = $1 OR venue_market_id = $1
+        ORDER BY (market_id = $1) DESC
+        LIMIT 2
+        "#,
+        reference
+    )
+    .fetch_all(pool)
+    .await?;
+
+    match rows.as_slice() {
+        [] => Err(MarketError::NotFound(reference.to_string())),
+        [first, ..] if first.market_id == reference => Ok(first.market_id.clone()),
+        [only] => Ok(only.market_id.clone()),
+        _ => Err(MarketError::Ambiguous(reference.to_string())),
+    }
+}
+
 /// Get a market by ID
 pub async fn get_market(pool: &PgPool, market_id: &str) -> Result<Market> {
-    let row = sqlx::query!(
+    let row = sqlx::query_as!(
+        MarketRow,
         r#"
         SELECT
-            market_id, venue, title, slug, category, status,
-            open_time, close_time, resolved_time, url
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url
         FROM markets
         WHERE market_id = $1
         "#,
@@ -117,18 +573,7 @@ pub async fn get_market(pool: &PgPool, market_id: &str) -> Result<Market> {
     .await?
     .ok_or_else(|| MarketError::NotFound(market_id.to_string()))?;
 
-    Ok(Market {
-        market_id: row.market_id,
-        venue: row.venue,
-        title: row.title,
-        slug: row.slug,
-        category: row.category,
-        status: parse_market_status(&row.status),
-        open_time: row.open_time,
-        close_time: row.close_time,
-        resolved_time: row.resolved_time,
-        url: row.url,
-    })
+    Ok(row.into())
 }
 
 /// List markets with optional filters
@@ -139,13 +584,16 @@ pub async fn list_markets(
     limit: i64,
     offset: i64,
 ) -> Result<Vec<Market>> {
-    let status_str = status.map(|s| format!("{:?}", s).to_lowercase());
+    let status_str = status.map(|s| s.as_str());
 
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        MarketRow,
         r#"
         SELECT
-            market_id, venue, title, slug, category, status,
-            open_time, close_time, resolved_time, url
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url
         FROM markets
         WHERE ($1::text IS NULL OR venue = $1)
           AND ($2::text IS NULL OR status = $2)
@@ -154,52 +602,44 @@ pub async fn list_markets(
         OFFSET $4
         "#,
         venue,
-        status_str.as_deref(),
+        status_str,
         limit,
         offset
     )
     .fetch_all(pool)
     .await?;
 
-    Ok(rows
-        .into_iter()
-        .map(|row| Market {
-            market_id: row.market_id,
-            venue: row.venue,
-            title: row.title,
-            slug: row.slug,
-            category: row.category,
-            status: parse_market_status(&row.status),
-            open_time: row.open_time,
-            close_time: row.close_time,
-            resolved_time: row.resolved_time,
-            url: row.url,
-        })
-        .collect())
+    Ok(rows.into_iter().map(Market::from).collect())
 }
 
-/// List active markets eligible for scoring
-pub async fn list_active_markets(
+/// List up to `limit` active markets for scoring, whatever their close
+/// time, leaving the eligibility gates to the scoring engine
+///
+/// Markets closing between `min_time_remaining` and `max_time_remaining`
+/// seconds after `now` come first, so the limit never crowds them out.
+pub async fn list_scoring_candidates(
     pool: &PgPool,
+    now: DateTime<Utc>,
     min_time_remaining: i64,
     max_time_remaining: i64,
     limit: i64,
 ) -> Result<Vec<Market>> {
-    let now = Utc::now();
     let min_close = now + chrono::Duration::seconds(min_time_remaining);
     let max_close = now + chrono::Duration::seconds(max_time_remaining);
 
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        MarketRow,
         r#"
         SELECT
-            market_id, venue, title, slug, category, status,
-            open_time, close_time, resolved_time, url
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url
         FROM markets
         WHERE status = 'active'
-          AND close_time IS NOT NULL
-          AND close_time >= $1
-          AND close_time <= $2
-        ORDER BY close_time ASC
+        ORDER BY
+            COALESCE(close_time >= $1 AND close_time <= $2, FALSE) DESC,
+            close_time ASC NULLS LAST
         LIMIT $3
         "#,
         min_close,
@@ -209,21 +649,37 @@ pub async fn list_active_markets(
     .fetch_all(pool)
     .await?;
 
-    Ok(rows
-        .into_iter()
-        .map(|row| Market {
-            market_id: row.market_id,
-            venue: row.venue,
-            title: row.title,
-            slug: row.slug,
-            category: row.category,
-            status: parse_market_status(&row.status),
-            open_time: row.open_time,
-            close_time: row.close_time,
-            resolved_time: row.resolved_time,
-            url: row.url,
-        })
-        .collect())
+    Ok(rows.into_iter().map(Market::from).collect())
+}
+
+/// List those of `market_ids` that are active
+pub async fn list_active_markets_by_ids(
+    pool: &PgPool,
+    market_ids: &[String],
+) -> Result<Vec<Market>> {
+    if market_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query_as!(
+        MarketRow,
+        r#"
+        SELECT
+            market_id, venue, venue_market_id, title, slug, category, status,
+            open_time, close_time, resolved_time, url,
+            event_id, event_title, tags, volume_24h, volume_total,
+            liquidity, neg_risk, image_url, icon_url
+        FROM markets
+        WHERE market_id = ANY($1)
+          AND status = 'active'
+        ORDER BY close_time ASC NULLS LAST
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(Market::from).collect())
 }
 
 /// Upsert market outcomes
@@ -279,15 +735,61 @@ pub async fn get_outcomes(pool: &PgPool, market_id: &str) -> Result<Vec<Outcome>
         .collect())
 }
 
+/// Get outcomes for multiple markets
+pub async fn get_outcomes_batch(pool: &PgPool, market_ids: &[String]) -> Result<Vec<Outcome>> {
+    if market_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id, outcome, token_id
+        FROM market_outcomes
+        WHERE market_id = ANY($1)
+        ORDER BY market_id, outcome
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Outcome {
+            market_id: row.market_id,
+            outcome: row.outcome,
+            token_id: row.token_id,
+        })
+        .collect())
+}
+
+/// Of the given market ids, those with a stored market row
+pub async fn existing_market_ids(pool: &PgPool, market_ids: &[String]) -> Result<Vec<String>> {
+    if market_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids = sqlx::query_scalar!(
+        r#"
+        SELECT market_id
+        FROM markets
+        WHERE market_id = ANY($1)
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
 /// Parse market status from string
+///
+/// The `markets_status_check` constraint keeps stored values canonical; an
+/// unknown value can only come from a newer schema and is treated as halted
+/// so it is excluded from scoring.
 fn parse_market_status(s: &str) -> MarketStatus {
-    match s.to_lowercase().as_str() {
-        "active" => MarketStatus::Active,
-        "closed" => MarketStatus::Closed,
-        "resolved" => MarketStatus::Resolved,
-        "halted" => MarketStatus::Halted,
-        _ => MarketStatus::Active, // Default fallback
-    }
+    s.parse().unwrap_or(MarketStatus::Halted)
 }
 
 #[cfg(test)]
@@ -0,0 +1,229 @@
+//! Database operations for the raw venue payload archive
+//!
+//! Bodies are stored gzip-compressed; the archive is bounded by age and by
+//! total stored size, oldest payloads going first.
+
+use std::io::{Read, Write};
+
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use pm_domain::RawPayload;
+use sqlx::PgPool;
+
+/// Error type for payload archive operations
+#[derive(Debug, thiserror::Error)]
+pub enum PayloadError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Compression error: {0}")]
+    Compression(#[from] std::io::Error),
+    #[error("Unsupported content encoding: {0}")]
+    Encoding(String),
+}
+
+pub type Result<T> = std::result::Result<T, PayloadError>;
+
+/// Content encoding recorded for stored bodies
+const GZIP: &str = "gzip";
+
+/// Archived payload with its row id
+#[derive(Debug, Clone)]
+pub struct StoredPayload {
+    pub id: i64,
+    pub payload: RawPayload,
+    pub stored_size: i32,
+}
+
+/// Filter for listing archived payloads
+#[derive(Debug, Clone, Default)]
+pub struct PayloadFilter {
+    pub market_id: Option<String>,
+    pub endpoint: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Gzip a payload body
+pub fn compress(body: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    Ok(encoder.finish()?)
+}
+
+/// Inflate a gzip payload body
+pub fn decompress(stored: &[u8]) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    GzDecoder::new(stored).read_to_end(&mut body)?;
+    Ok(body)
+}
+
+/// Batch insert payloads, compressing their bodies
+///
+/// Returns the total stored (compressed) size.
+pub async fn insert_payloads_batch(pool: &PgPool, payloads: &[RawPayload]) -> Result<i64> {
+    if payloads.is_empty() {
+        return Ok(0);
+    }
+
+    let mut tx = pool.begin().await?;
+    let mut stored_total = 0i64;
+
+    for payload in payloads {
+        let stored = compress(&payload.body)?;
+        stored_total += stored.len() as i64;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO raw_payloads (
+                venue, market_id, endpoint, request_url, outcome, fetched_at,
+                http_status, content_encoding, body, body_size, stored_size
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            payload.venue,
+            payload.market_id,
+            payload.endpoint,
+            payload.request_url,
+            payload.outcome,
+            payload.fetched_at,
+            payload.http_status as i32,
+            GZIP,
+            stored,
+            payload.body.len() as i32,
+            stored.len() as i32
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(stored_total)
+}
+
+/// List archived payloads in fetch order, decompressed
+///
+/// Pages by row id: pass the last id seen as `after_id`.
+pub async fn list_payloads(
+    pool: &PgPool,
+    filter: &PayloadFilter,
+    after_id: i64,
+    limit: i64,
+) -> Result<Vec<StoredPayload>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            id, venue, market_id, endpoint, request_url, outcome, fetched_at,
+            http_status, content_encoding, body, stored_size
+        FROM raw_payloads
+        WHERE id > $1
+          AND ($2::TEXT IS NULL OR market_id = $2)
+          AND ($3::TEXT IS NULL OR endpoint = $3)
+          AND ($4::TIMESTAMPTZ IS NULL OR fetched_at >= $4)
+        ORDER BY id
+        LIMIT $5
+        "#,
+        after_id,
+        filter.market_id,
+        filter.endpoint,
+        filter.since,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            if row.content_encoding != GZIP {
+                return Err(PayloadError::Encoding(row.content_encoding));
+            }
+
+            Ok(StoredPayload {
+                id: row.id,
+                payload: RawPayload {
+                    venue: row.venue,
+                    market_id: row.market_id,
+                    endpoint: row.endpoint,
+                    request_url: row.request_url,
+                    outcome: row.outcome,
+                    fetched_at: row.fetched_at,
+                    http_status: row.http_status as u16,
+                    body: decompress(&row.body)?,
+                },
+                stored_size: row.stored_size,
+            })
+        })
+        .collect()
+}
+
+/// Delete payloads fetched before `cutoff`, then the oldest payloads until
+/// the archive fits in `max_total_bytes` of stored size
+///
+/// Returns the number of payloads deleted.
+pub async fn prune_payloads(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    max_total_bytes: i64,
+) -> Result<u64> {
+    let expired = sqlx::query!(
+        r#"
+        DELETE FROM raw_payloads
+        WHERE fetched_at < $1
+        "#,
+        cutoff
+    )
+    .execute(pool)
+    .await?;
+
+    let oversized = sqlx::query!(
+        r#"
+        DELETE FROM raw_payloads
+        WHERE id IN (
+            SELECT id
+            FROM (
+                SELECT
+                    id,
+                    SUM(stored_size) OVER (ORDER BY fetched_at DESC, id DESC) AS retained
+                FROM raw_payloads
+            ) sized
+            WHERE retained > $1
+        )
+        "#,
+        max_total_bytes
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(expired.rows_affected() + oversized.rows_affected())
+}
+
+/// Total stored size of the archive (bytes)
+pub async fn total_stored_size(pool: &PgPool) -> Result<i64> {
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(SUM(stored_size), 0)::BIGINT AS "total!"
+        FROM raw_payloads
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_round_trip() {
+        let body = br#"[{"conditionId":"0xabc","question":"Will it rain?"}]"#.repeat(20);
+        let stored = compress(&body).unwrap();
+        assert!(stored.len() < body.len());
+        assert_eq!(decompress(&stored).unwrap(), body);
+    }
+
+    #[test]
+    fn test_decompress_rejects_garbage() {
+        assert!(decompress(b"not gzip").is_err());
+    }
+}
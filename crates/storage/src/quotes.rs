@@ -4,7 +4,7 @@ use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Timelike, Utc};
-use pm_domain::Quote;
+use pm_domain::{PriceSource, QuarantinedQuote, Quote, QuoteValidity};
 use sqlx::PgPool;
 
 /// Error type for quote operations
@@ -14,6 +14,8 @@ pub enum QuoteError {
     Database(#[from] sqlx::Error),
     #[error("Quote not found for market: {0}")]
     NotFound(String),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
 }
 
 pub type Result<T> = std::result::Result<T, QuoteError>;
@@ -44,9 +46,10 @@ pub async fn upsert_quote_latest(pool: &PgPool, quote: &Quote) -> Result<()> {
         r#"
         INSERT INTO quotes_latest (
             market_id, as_of, yes_bid, yes_ask, no_bid, no_ask,
-            spread_yes, spread_no, mid_yes, mid_no, quote_source
+            spread_yes, spread_no, mid_yes, mid_no, yes_source, no_source,
+            quote_source
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         ON CONFLICT (market_id)
         DO UPDATE SET
             as_of = EXCLUDED.as_of,
@@ -58,6 +61,8 @@ pub async fn upsert_quote_latest(pool: &PgPool, quote: &Quote) -> Result<()> {
             spread_no = EXCLUDED.spread_no,
             mid_yes = EXCLUDED.mid_yes,
             mid_no = EXCLUDED.mid_no,
+            yes_source = EXCLUDED.yes_source,
+            no_source = EXCLUDED.no_source,
             quote_source = EXCLUDED.quote_source,
             updated_at = NOW()
         "#,
@@ -71,6 +76,8 @@ pub async fn upsert_quote_latest(pool: &PgPool, quote: &Quote) -> Result<()> {
         opt_f64_to_bigdecimal(quote.spread_no),
         opt_f64_to_bigdecimal(quote.mid_yes),
         opt_f64_to_bigdecimal(quote.mid_no),
+        format_price_source(quote.yes_source),
+        format_price_source(quote.no_source),
         quote.quote_source
     )
     .execute(pool)
@@ -92,9 +99,10 @@ pub async fn upsert_quotes_latest_batch(pool: &PgPool, quotes: &[Quote]) -> Resu
             r#"
             INSERT INTO quotes_latest (
                 market_id, as_of, yes_bid, yes_ask, no_bid, no_ask,
-                spread_yes, spread_no, mid_yes, mid_no, quote_source
+                spread_yes, spread_no, mid_yes, mid_no, yes_source, no_source,
+                quote_source
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             ON CONFLICT (market_id)
             DO UPDATE SET
                 as_of = EXCLUDED.as_of,
@@ -106,6 +114,8 @@ pub async fn upsert_quotes_latest_batch(pool: &PgPool, quotes: &[Quote]) -> Resu
                 spread_no = EXCLUDED.spread_no,
                 mid_yes = EXCLUDED.mid_yes,
                 mid_no = EXCLUDED.mid_no,
+                yes_source = EXCLUDED.yes_source,
+                no_source = EXCLUDED.no_source,
                 quote_source = EXCLUDED.quote_source,
                 updated_at = NOW()
             "#,
@@ -119,6 +129,8 @@ pub async fn upsert_quotes_latest_batch(pool: &PgPool, quotes: &[Quote]) -> Resu
             opt_f64_to_bigdecimal(quote.spread_no),
             opt_f64_to_bigdecimal(quote.mid_yes),
             opt_f64_to_bigdecimal(quote.mid_no),
+            format_price_source(quote.yes_source),
+            format_price_source(quote.no_source),
             quote.quote_source
         )
         .execute(&mut *tx)
@@ -136,7 +148,8 @@ pub async fn get_quote_latest(pool: &PgPool, market_id: &str) -> Result<Quote> {
         r#"
         SELECT
             market_id, as_of, yes_bid, yes_ask, no_bid, no_ask,
-            spread_yes, spread_no, mid_yes, mid_no, quote_source
+            spread_yes, spread_no, mid_yes, mid_no, yes_source, no_source,
+            quote_source
         FROM quotes_latest
         WHERE market_id = $1
         "#,
@@ -157,6 +170,8 @@ pub async fn get_quote_latest(pool: &PgPool, market_id: &str) -> Result<Quote> {
         spread_no: opt_bigdecimal_to_f64(row.spread_no),
         mid_yes: opt_bigdecimal_to_f64(row.mid_yes),
         mid_no: opt_bigdecimal_to_f64(row.mid_no),
+        yes_source: parse_price_source(&row.yes_source),
+        no_source: parse_price_source(&row.no_source),
         quote_source: row.quote_source,
     })
 }
@@ -171,7 +186,8 @@ pub async fn get_quotes_latest_batch(pool: &PgPool, market_ids: &[String]) -> Re
         r#"
         SELECT
             market_id, as_of, yes_bid, yes_ask, no_bid, no_ask,
-            spread_yes, spread_no, mid_yes, mid_no, quote_source
+            spread_yes, spread_no, mid_yes, mid_no, yes_source, no_source,
+            quote_source
         FROM quotes_latest
         WHERE market_id = ANY($1)
         "#,
@@ -193,6 +209,8 @@ pub async fn get_quotes_latest_batch(pool: &PgPool, market_ids: &[String]) -> Re
             spread_no: opt_bigdecimal_to_f64(row.spread_no),
             mid_yes: opt_bigdecimal_to_f64(row.mid_yes),
             mid_no: opt_bigdecimal_to_f64(row.mid_no),
+            yes_source: parse_price_source(&row.yes_source),
+            no_source: parse_price_source(&row.no_source),
             quote_source: row.quote_source,
         })
         .collect())
@@ -206,9 +224,10 @@ pub async fn insert_quote_5m(pool: &PgPool, quote: &Quote) -> Result<()> {
     sqlx::query!(
         r#"
         INSERT INTO quotes_5m (
-            market_id, bucket_start, as_of, yes_bid, yes_ask, no_bid, no_ask
+            market_id, bucket_start, as_of, yes_bid, yes_ask, no_bid, no_ask,
+            yes_source, no_source
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         ON CONFLICT (market_id, bucket_start) DO NOTHING
         "#,
         quote.market_id,
@@ -217,7 +236,9 @@ pub async fn insert_quote_5m(pool: &PgPool, quote: &Quote) -> Result<()> {
         opt_f64_to_bigdecimal(quote.yes_bid),
         opt_f64_to_bigdecimal(quote.yes_ask),
         opt_f64_to_bigdecimal(quote.no_bid),
-        opt_f64_to_bigdecimal(quote.no_ask)
+        opt_f64_to_bigdecimal(quote.no_ask),
+        format_price_source(quote.yes_source),
+        format_price_source(quote.no_source)
     )
     .execute(pool)
     .await?;
@@ -225,9 +246,14 @@ pub async fn insert_quote_5m(pool: &PgPool, quote: &Quote) -> Result<()> {
     Ok(())
 }
 
-/// Delete old 5-minute samples (retention policy)
-pub async fn delete_old_quotes_5m(pool: &PgPool, retention_days: i64) -> Result<u64> {
-    let cutoff = Utc::now() - chrono::Duration::days(retention_days);
+/// Delete 5-minute samples older than `retention_days` before `now`
+/// (retention policy)
+pub async fn delete_old_quotes_5m(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+    retention_days: i64,
+) -> Result<u64> {
+    let cutoff = now - chrono::Duration::days(retention_days);
 
     let result = sqlx::query!(
         r#"
@@ -253,7 +279,7 @@ pub async fn get_quotes_5m(
         r#"
         SELECT
             market_id, as_of, yes_bid, yes_ask, no_bid, no_ask,
-            bucket_start
+            yes_source, no_source, bucket_start
         FROM quotes_5m
         WHERE market_id = $1
           AND bucket_start >= $2
@@ -280,11 +306,120 @@ pub async fn get_quotes_5m(
             spread_no: None,
             mid_yes: None,
             mid_no: None,
+            yes_source: parse_price_source(&row.yes_source),
+            no_source: parse_price_source(&row.no_source),
             quote_source: "polymarket".to_string(),
         })
         .collect())
 }
 
+/// Record quotes that failed validation
+pub async fn insert_quarantined_batch(
+    pool: &PgPool,
+    quarantined: &[QuarantinedQuote],
+) -> Result<()> {
+    if quarantined.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    for entry in quarantined {
+        let issues_json = serde_json::to_value(&entry.issues)?;
+        let quote_json = serde_json::to_value(&entry.quote)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO quotes_quarantine (market_id, as_of, validity, issues, quote)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            entry.quote.market_id,
+            entry.quote.as_of,
+            entry.validity.as_str(),
+            issues_json,
+            quote_json
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// List quarantined quotes, newest first, optionally for one market
+pub async fn list_quarantined(
+    pool: &PgPool,
+    market_id: Option<&str>,
+    limit: i64,
+) -> Result<Vec<QuarantinedQuote>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT validity, issues, quote
+        FROM quotes_quarantine
+        WHERE ($1::TEXT IS NULL OR market_id = $1)
+        ORDER BY as_of DESC, id DESC
+        LIMIT $2
+        "#,
+        market_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(QuarantinedQuote {
+                quote: serde_json::from_value(row.quote)?,
+                validity: parse_validity(&row.validity),
+                issues: serde_json::from_value(row.issues)?,
+            })
+        })
+        .collect()
+}
+
+/// Delete quarantined quotes older than `retention_days` before `now`
+pub async fn delete_old_quarantined(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+    retention_days: i64,
+) -> Result<u64> {
+    let cutoff = now - chrono::Duration::days(retention_days);
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM quotes_quarantine
+        WHERE as_of < $1
+        "#,
+        cutoff
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Parse stored quarantine validity
+fn parse_validity(s: &str) -> QuoteValidity {
+    match s {
+        "suspicious" => QuoteValidity::Suspicious,
+        _ => QuoteValidity::Invalid,
+    }
+}
+
+/// Format price source for storage
+fn format_price_source(source: PriceSource) -> String {
+    format!("{:?}", source).to_lowercase()
+}
+
+/// Parse price source from string
+fn parse_price_source(s: &str) -> PriceSource {
+    match s.to_lowercase().as_str() {
+        "observed" => PriceSource::Observed,
+        _ => PriceSource::Derived, // Unknown provenance is not trusted as observed
+    }
+}
+
 /// Round timestamp down to nearest 5-minute bucket
 fn bucket_to_5m(dt: DateTime<Utc>) -> DateTime<Utc> {
     let minutes = dt.minute();
@@ -312,4 +447,12 @@ mod tests {
         assert_eq!(bucketed.second(), 0);
         assert_eq!(bucketed.nanosecond(), 0);
     }
+
+    #[test]
+    fn test_price_source_round_trip() {
+        for source in [PriceSource::Observed, PriceSource::Derived] {
+            assert_eq!(parse_price_source(&format_price_source(source)), source);
+        }
+        assert_eq!(parse_price_source("bogus"), PriceSource::Derived);
+    }
 }
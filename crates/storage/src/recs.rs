@@ -3,7 +3,8 @@
 use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
-use pm_domain::Recommendation;
+use chrono::{DateTime, Utc};
+use pm_domain::{Recommendation, Retraction, RetractionReason};
 use sqlx::PgPool;
 
 /// Error type for recommendation operations
@@ -15,6 +16,8 @@ pub enum RecError {
     NotFound(String),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("Invalid retraction reason: {0}")]
+    InvalidRetractionReason(#[from] pm_domain::ParseRetractionReasonError),
 }
 
 pub type Result<T> = std::result::Result<T, RecError>;
@@ -29,7 +32,18 @@ fn opt_f64_to_bigdecimal(val: Option<f64>) -> Option<BigDecimal> {
     val.map(f64_to_bigdecimal)
 }
 
-/// Upsert recommendation for a market
+/// Retraction stored alongside a recommendation, if any
+fn retraction(at: Option<DateTime<Utc>>, reason: Option<String>) -> Result<Option<Retraction>> {
+    match (at, reason) {
+        (Some(at), Some(reason)) => Ok(Some(Retraction {
+            at,
+            reason: reason.parse()?,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Upsert recommendation for a market, clearing any retraction
 pub async fn upsert_rec(pool: &PgPool, rec: &Recommendation) -> Result<()> {
     let risk_flags_json = serde_json::to_value(&rec.risk_flags)?;
 
@@ -51,6 +65,8 @@ pub async fn upsert_rec(pool: &PgPool, rec: &Recommendation) -> Result<()> {
             risk_score = EXCLUDED.risk_score,
             risk_flags = EXCLUDED.risk_flags,
             notes = EXCLUDED.notes,
+            retracted_at = NULL,
+            retraction_reason = NULL,
             updated_at = NOW()
         "#,
         rec.market_id,
@@ -69,7 +85,7 @@ pub async fn upsert_rec(pool: &PgPool, rec: &Recommendation) -> Result<()> {
     Ok(())
 }
 
-/// Batch upsert recommendations
+/// Batch upsert recommendations, clearing any retractions
 pub async fn upsert_recs_batch(pool: &PgPool, recs: &[Recommendation]) -> Result<()> {
     if recs.is_empty() {
         return Ok(());
@@ -98,6 +114,8 @@ pub async fn upsert_recs_batch(pool: &PgPool, recs: &[Recommendation]) -> Result
                 risk_score = EXCLUDED.risk_score,
                 risk_flags = EXCLUDED.risk_flags,
                 notes = EXCLUDED.notes,
+                retracted_at = NULL,
+                retraction_reason = NULL,
                 updated_at = NOW()
             "#,
             rec.market_id,
@@ -126,7 +144,7 @@ pub async fn get_rec(pool: &PgPool, market_id: &str) -> Result<Recommendation> {
         SELECT
             market_id, as_of, recommended_side, entry_price,
             expected_payout, max_position_pct, risk_score,
-            risk_flags, notes
+            risk_flags, notes, retracted_at, retraction_reason
         FROM recs_latest
         WHERE market_id = $1
         "#,
@@ -148,16 +166,43 @@ pub async fn get_rec(pool: &PgPool, market_id: &str) -> Result<Recommendation> {
         risk_score: row.risk_score.to_string().parse().unwrap_or(0.0),
         risk_flags,
         notes: row.notes,
+        retraction: retraction(row.retracted_at, row.retraction_reason)?,
     })
 }
 
+/// Filters for listing recommendations
+///
+/// Every field is optional; unset fields do not constrain the result.
+#[derive(Debug, Clone, Default)]
+pub struct RecFilter {
+    /// Minimum overall score
+    pub min_score: Option<f64>,
+    /// Maximum time remaining in seconds
+    pub max_t_remaining_sec: Option<i64>,
+    /// Maximum aggregate risk score
+    pub max_risk_score: Option<f64>,
+    /// Only markets with (true) or without (false) risk flags
+    pub has_flags: Option<bool>,
+    /// Market category (case-insensitive)
+    pub category: Option<String>,
+    /// Market tag (case-insensitive)
+    pub tag: Option<String>,
+    /// Parent event id
+    pub event_id: Option<String>,
+    /// Minimum 24h traded volume (USD)
+    pub min_volume_24h: Option<f64>,
+    /// Minimum displayed liquidity (USD)
+    pub min_liquidity: Option<f64>,
+    /// Only neg-risk (true) or standard (false) markets
+    pub neg_risk: Option<bool>,
+    /// Also list retracted recommendations
+    pub include_retracted: bool,
+}
+
 /// List recommendations with filters
 pub async fn list_recs(
     pool: &PgPool,
-    min_score: Option<f64>,
-    max_t_remaining_sec: Option<i64>,
-    max_risk_score: Option<f64>,
-    has_flags: Option<bool>,
+    filter: &RecFilter,
     limit: usize,
     offset: usize,
 ) -> Result<Vec<Recommendation>> {
@@ -166,23 +211,39 @@ pub async fn list_recs(
         SELECT
             r.market_id, r.as_of, r.recommended_side, r.entry_price,
             r.expected_payout, r.max_position_pct, r.risk_score,
-            r.risk_flags, r.notes
+            r.risk_flags, r.notes, r.retracted_at, r.retraction_reason
         FROM recs_latest r
         LEFT JOIN scores_latest s ON r.market_id = s.market_id
+        JOIN markets m ON r.market_id = m.market_id
         WHERE ($1::numeric IS NULL OR s.overall_score >= $1)
           AND ($2::bigint IS NULL OR s.t_remaining_sec <= $2)
           AND ($3::numeric IS NULL OR r.risk_score <= $3)
           AND ($4::boolean IS NULL OR
                ($4 = true AND jsonb_array_length(r.risk_flags) > 0) OR
                ($4 = false AND jsonb_array_length(r.risk_flags) = 0))
+          AND ($5::text IS NULL OR lower(m.category) = lower($5))
+          AND ($6::text IS NULL OR EXISTS (
+               SELECT 1 FROM unnest(m.tags) t WHERE lower(t) = lower($6)))
+          AND ($7::text IS NULL OR m.event_id = $7)
+          AND ($8::numeric IS NULL OR m.volume_24h >= $8)
+          AND ($9::numeric IS NULL OR m.liquidity >= $9)
+          AND ($10::boolean IS NULL OR m.neg_risk = $10)
+          AND ($11 OR r.retracted_at IS NULL)
         ORDER BY s.overall_score DESC NULLS LAST
-        LIMIT $5
-        OFFSET $6
+        LIMIT $12
+        OFFSET $13
         "#,
-        opt_f64_to_bigdecimal(min_score),
-        max_t_remaining_sec,
-        opt_f64_to_bigdecimal(max_risk_score),
-        has_flags,
+        opt_f64_to_bigdecimal(filter.min_score),
+        filter.max_t_remaining_sec,
+        opt_f64_to_bigdecimal(filter.max_risk_score),
+        filter.has_flags,
+        filter.category.as_deref(),
+        filter.tag.as_deref(),
+        filter.event_id.as_deref(),
+        opt_f64_to_bigdecimal(filter.min_volume_24h),
+        opt_f64_to_bigdecimal(filter.min_liquidity),
+        filter.neg_risk,
+        filter.include_retracted,
         limit as i64,
         offset as i64
     )
@@ -203,6 +264,7 @@ pub async fn list_recs(
             risk_score: row.risk_score.to_string().parse().unwrap_or(0.0),
             risk_flags,
             notes: row.notes,
+            retraction: retraction(row.retracted_at, row.retraction_reason)?,
         });
     }
 
@@ -210,29 +272,39 @@ pub async fn list_recs(
 }
 
 /// Count recommendations with filters
-pub async fn count_recs(
-    pool: &PgPool,
-    min_score: Option<f64>,
-    max_t_remaining_sec: Option<i64>,
-    max_risk_score: Option<f64>,
-    has_flags: Option<bool>,
-) -> Result<usize> {
+pub async fn count_recs(pool: &PgPool, filter: &RecFilter) -> Result<usize> {
     let row = sqlx::query!(
         r#"
         SELECT COUNT(*) as count
         FROM recs_latest r
         LEFT JOIN scores_latest s ON r.market_id = s.market_id
+        JOIN markets m ON r.market_id = m.market_id
         WHERE ($1::numeric IS NULL OR s.overall_score >= $1)
           AND ($2::bigint IS NULL OR s.t_remaining_sec <= $2)
           AND ($3::numeric IS NULL OR r.risk_score <= $3)
           AND ($4::boolean IS NULL OR
                ($4 = true AND jsonb_array_length(r.risk_flags) > 0) OR
                ($4 = false AND jsonb_array_length(r.risk_flags) = 0))
+          AND ($5::text IS NULL OR lower(m.category) = lower($5))
+          AND ($6::text IS NULL OR EXISTS (
+               SELECT 1 FROM unnest(m.tags) t WHERE lower(t) = lower($6)))
+          AND ($7::text IS NULL OR m.event_id = $7)
+          AND ($8::numeric IS NULL OR m.volume_24h >= $8)
+          AND ($9::numeric IS NULL OR m.liquidity >= $9)
+          AND ($10::boolean IS NULL OR m.neg_risk = $10)
+          AND ($11 OR r.retracted_at IS NULL)
         "#,
-        opt_f64_to_bigdecimal(min_score),
-        max_t_remaining_sec,
-        opt_f64_to_bigdecimal(max_risk_score),
-        has_flags
+        opt_f64_to_bigdecimal(filter.min_score),
+        filter.max_t_remaining_sec,
+        opt_f64_to_bigdecimal(filter.max_risk_score),
+        filter.has_flags,
+        filter.category.as_deref(),
+        filter.tag.as_deref(),
+        filter.event_id.as_deref(),
+        opt_f64_to_bigdecimal(filter.min_volume_24h),
+        opt_f64_to_bigdecimal(filter.min_liquidity),
+        filter.neg_risk,
+        filter.include_retracted
     )
     .fetch_one(pool)
     .await?;
@@ -240,7 +312,7 @@ pub async fn count_recs(
     Ok(row.count.unwrap_or(0) as usize)
 }
 
-/// List top recommendations
+/// List top active recommendations
 pub async fn list_top_recs(
     pool: &PgPool,
     max_risk_score: Option<f64>,
@@ -253,9 +325,10 @@ pub async fn list_top_recs(
         SELECT
             market_id, as_of, recommended_side, entry_price,
             expected_payout, max_position_pct, risk_score,
-            risk_flags, notes
+            risk_flags, notes, retracted_at, retraction_reason
         FROM recs_latest
-        WHERE ($1::numeric IS NULL OR risk_score <= $1)
+        WHERE retracted_at IS NULL
+          AND ($1::numeric IS NULL OR risk_score <= $1)
           AND ($2::boolean IS NULL OR
                ($2 = true AND jsonb_array_length(risk_flags) > 0) OR
                ($2 = false AND jsonb_array_length(risk_flags) = 0))
@@ -285,8 +358,68 @@ pub async fn list_top_recs(
             risk_score: row.risk_score.to_string().parse().unwrap_or(0.0),
             risk_flags,
             notes: row.notes,
+            retraction: retraction(row.retracted_at, row.retraction_reason)?,
         });
     }
 
     Ok(results)
 }
+
+/// List markets with an active (not retracted) recommendation, limited to
+/// `market_ids` if given
+pub async fn list_active_rec_ids(
+    pool: &PgPool,
+    market_ids: Option<&[String]>,
+) -> Result<Vec<String>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id
+        FROM recs_latest
+        WHERE retracted_at IS NULL
+          AND ($1::text[] IS NULL OR market_id = ANY($1))
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.market_id).collect())
+}
+
+/// Retract active recommendations at `at`, each with its reason
+///
+/// Already retracted recommendations keep their original retraction.
+/// Returns how many were retracted.
+pub async fn retract_recs(
+    pool: &PgPool,
+    retractions: &[(String, RetractionReason)],
+    at: DateTime<Utc>,
+) -> Result<u64> {
+    if retractions.is_empty() {
+        return Ok(0);
+    }
+
+    let (market_ids, reasons): (Vec<String>, Vec<String>) = retractions
+        .iter()
+        .map(|(id, reason)| (id.clone(), reason.as_str().to_string()))
+        .unzip();
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE recs_latest r
+        SET retracted_at = $1,
+            retraction_reason = x.reason,
+            updated_at = NOW()
+        FROM UNNEST($2::text[], $3::text[]) AS x(market_id, reason)
+        WHERE r.market_id = x.market_id
+          AND r.retracted_at IS NULL
+        "#,
+        at,
+        &market_ids,
+        &reasons
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
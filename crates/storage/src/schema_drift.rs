@@ -0,0 +1,97 @@
+//! Database operations for venue response schema drift
+
+use pm_domain::{DriftKind, SchemaDrift};
+use sqlx::PgPool;
+
+/// Error type for schema drift operations
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaDriftError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid drift kind: {0}")]
+    InvalidKind(#[from] pm_domain::ParseDriftKindError),
+}
+
+pub type Result<T> = std::result::Result<T, SchemaDriftError>;
+
+/// Batch upsert drift, adding `occurrences` to the stored counts
+pub async fn upsert_drift_batch(pool: &PgPool, drift: &[SchemaDrift]) -> Result<()> {
+    if drift.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    for d in drift {
+        sqlx::query!(
+            r#"
+            INSERT INTO schema_drift (
+                venue, endpoint, field, kind, expected, observed,
+                critical, active, occurrences, first_seen_at, last_seen_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (venue, endpoint, field, kind)
+            DO UPDATE SET
+                expected = EXCLUDED.expected,
+                observed = COALESCE(EXCLUDED.observed, schema_drift.observed),
+                critical = EXCLUDED.critical,
+                active = EXCLUDED.active,
+                occurrences = schema_drift.occurrences + EXCLUDED.occurrences,
+                first_seen_at = LEAST(schema_drift.first_seen_at, EXCLUDED.first_seen_at),
+                last_seen_at = GREATEST(schema_drift.last_seen_at, EXCLUDED.last_seen_at)
+            "#,
+            d.venue,
+            d.endpoint,
+            d.field,
+            d.kind.as_str(),
+            d.expected,
+            d.observed,
+            d.critical,
+            d.active,
+            d.occurrences,
+            d.first_seen_at,
+            d.last_seen_at
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// List tracked drift, most recently seen first
+pub async fn list_drift(pool: &PgPool, active_only: bool) -> Result<Vec<SchemaDrift>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            venue, endpoint, field, kind, expected, observed,
+            critical, active, occurrences, first_seen_at, last_seen_at
+        FROM schema_drift
+        WHERE active OR NOT $1
+        ORDER BY last_seen_at DESC, endpoint, field
+        "#,
+        active_only
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(SchemaDrift {
+                venue: row.venue,
+                endpoint: row.endpoint,
+                field: row.field,
+                kind: row.kind.parse::<DriftKind>()?,
+                expected: row.expected,
+                observed: row.observed,
+                critical: row.critical,
+                active: row.active,
+                occurrences: row.occurrences,
+                first_seen_at: row.first_seen_at,
+                last_seen_at: row.last_seen_at,
+            })
+        })
+        .collect()
+}
@@ -227,3 +227,107 @@ pub async fn list_top_scores(
         })
         .collect())
 }
+
+/// Batch upsert the latest scores from shadow model `model_id`
+pub async fn upsert_shadow_scores_batch(
+    pool: &PgPool,
+    model_id: &str,
+    scores: &[Score],
+) -> Result<()> {
+    if scores.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    for score in scores {
+        let score_breakdown_json = serde_json::to_value(&score.score_breakdown)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO scores_shadow (
+                model_id, market_id, as_of, t_remaining_sec, gross_yield, fee_bps,
+                net_yield, yield_velocity, liquidity_score,
+                staleness_sec, staleness_penalty, definition_risk_score,
+                overall_score, score_breakdown
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (model_id, market_id)
+            DO UPDATE SET
+                as_of = EXCLUDED.as_of,
+                t_remaining_sec = EXCLUDED.t_remaining_sec,
+                gross_yield = EXCLUDED.gross_yield,
+                fee_bps = EXCLUDED.fee_bps,
+                net_yield = EXCLUDED.net_yield,
+                yield_velocity = EXCLUDED.yield_velocity,
+                liquidity_score = EXCLUDED.liquidity_score,
+                staleness_sec = EXCLUDED.staleness_sec,
+                staleness_penalty = EXCLUDED.staleness_penalty,
+                definition_risk_score = EXCLUDED.definition_risk_score,
+                overall_score = EXCLUDED.overall_score,
+                score_breakdown = EXCLUDED.score_breakdown,
+                updated_at = NOW()
+            "#,
+            model_id,
+            score.market_id,
+            score.as_of,
+            score.t_remaining_sec,
+            f64_to_bigdecimal(score.gross_yield),
+            f64_to_bigdecimal(score.fee_bps),
+            f64_to_bigdecimal(score.net_yield),
+            f64_to_bigdecimal(score.yield_velocity),
+            f64_to_bigdecimal(score.liquidity_score),
+            score.staleness_sec,
+            f64_to_bigdecimal(score.staleness_penalty),
+            f64_to_bigdecimal(score.definition_risk_score),
+            f64_to_bigdecimal(score.overall_score),
+            score_breakdown_json
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// List the top scores from shadow model `model_id`
+pub async fn list_shadow_scores(pool: &PgPool, model_id: &str, limit: i64) -> Result<Vec<Score>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            market_id, as_of, t_remaining_sec, gross_yield, fee_bps,
+            net_yield, yield_velocity, liquidity_score,
+            staleness_sec, staleness_penalty, definition_risk_score,
+            overall_score, score_breakdown
+        FROM scores_shadow
+        WHERE model_id = $1
+        ORDER BY overall_score DESC
+        LIMIT $2
+        "#,
+        model_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Score {
+            market_id: row.market_id,
+            as_of: row.as_of,
+            t_remaining_sec: row.t_remaining_sec,
+            gross_yield: row.gross_yield.to_string().parse().unwrap_or(0.0),
+            fee_bps: row.fee_bps.to_string().parse().unwrap_or(0.0),
+            net_yield: row.net_yield.to_string().parse().unwrap_or(0.0),
+            yield_velocity: row.yield_velocity.to_string().parse().unwrap_or(0.0),
+            liquidity_score: row.liquidity_score.to_string().parse().unwrap_or(0.0),
+            staleness_sec: row.staleness_sec,
+            staleness_penalty: row.staleness_penalty.to_string().parse().unwrap_or(0.0),
+            definition_risk_score: row.definition_risk_score.to_string().parse().unwrap_or(0.0),
+            overall_score: row.overall_score.to_string().parse().unwrap_or(0.0),
+            score_breakdown: row.score_breakdown,
+        })
+        .collect())
+}
@@ -0,0 +1,132 @@
+//! Database operations for trade prints
+
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use pm_domain::{Trade, TradeSide};
+use sqlx::PgPool;
+
+/// Error type for trade operations
+#[derive(Debug, thiserror::Error)]
+pub enum TradeError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid trade side: {0}")]
+    InvalidSide(#[from] pm_domain::ParseTradeSideError),
+}
+
+pub type Result<T> = std::result::Result<T, TradeError>;
+
+/// Convert f64 to BigDecimal
+fn f64_to_bigdecimal(val: f64) -> BigDecimal {
+    BigDecimal::from_str(&val.to_string()).unwrap_or_else(|_| BigDecimal::from(0))
+}
+
+/// Convert BigDecimal to f64
+fn bigdecimal_to_f64(val: BigDecimal) -> f64 {
+    val.to_string().parse().unwrap_or(0.0)
+}
+
+/// Batch insert trades, skipping fills already stored
+///
+/// Returns the number of new trades.
+pub async fn insert_trades_batch(pool: &PgPool, trades: &[Trade]) -> Result<u64> {
+    if trades.is_empty() {
+        return Ok(0);
+    }
+
+    let mut tx = pool.begin().await?;
+    let mut inserted = 0;
+
+    for trade in trades {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO trades (market_id, trade_id, outcome, side, price, size, traded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (market_id, trade_id) DO NOTHING
+            "#,
+            trade.market_id,
+            trade.trade_id,
+            trade.outcome,
+            trade.side.as_str(),
+            f64_to_bigdecimal(trade.price),
+            f64_to_bigdecimal(trade.size),
+            trade.traded_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        inserted += result.rows_affected();
+    }
+
+    tx.commit().await?;
+    Ok(inserted)
+}
+
+/// Get stored trades for multiple markets, newest first
+pub async fn get_trades_batch(pool: &PgPool, market_ids: &[String]) -> Result<Vec<Trade>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT market_id, trade_id, outcome, side, price, size, traded_at
+        FROM trades
+        WHERE market_id = ANY($1)
+        ORDER BY market_id, traded_at DESC
+        "#,
+        market_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(Trade {
+                market_id: row.market_id,
+                trade_id: row.trade_id,
+                outcome: row.outcome,
+                side: row.side.parse::<TradeSide>()?,
+                price: bigdecimal_to_f64(row.price),
+                size: bigdecimal_to_f64(row.size),
+                traded_at: row.traded_at,
+            })
+        })
+        .collect()
+}
+
+/// Delete trades older than `cutoff`, and beyond the newest
+/// `max_per_market` fills of each market
+///
+/// Returns the number of deleted trades.
+pub async fn prune_trades(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+    max_per_market: i64,
+) -> Result<u64> {
+    let aged = sqlx::query!(
+        r#"
+        DELETE FROM trades
+        WHERE traded_at < $1
+        "#,
+        cutoff
+    )
+    .execute(pool)
+    .await?;
+
+    let excess = sqlx::query!(
+        r#"
+        DELETE FROM trades t
+        USING (
+            SELECT market_id, trade_id,
+                   ROW_NUMBER() OVER (PARTITION BY market_id ORDER BY traded_at DESC, trade_id) AS rank
+            FROM trades
+        ) ranked
+        WHERE t.market_id = ranked.market_id
+          AND t.trade_id = ranked.trade_id
+          AND ranked.rank > $1
+        "#,
+        max_per_market
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(aged.rows_affected() + excess.rows_affected())
+}
@@ -0,0 +1,18 @@
+-- PM Endgame Sweep - Quote side provenance
+-- Migration: 20260102000001_quote_side_sources
+
+-- Record whether each side of a quote was read from its own CLOB order book
+-- ('observed') or synthesized as the complement of the other side ('derived').
+-- Existing rows were built from the YES book only, so NO is marked derived.
+ALTER TABLE quotes_latest
+  ADD COLUMN IF NOT EXISTS yes_source TEXT NOT NULL DEFAULT 'observed',
+  ADD COLUMN IF NOT EXISTS no_source TEXT NOT NULL DEFAULT 'derived';
+
+ALTER TABLE quotes_5m
+  ADD COLUMN IF NOT EXISTS yes_source TEXT NOT NULL DEFAULT 'observed',
+  ADD COLUMN IF NOT EXISTS no_source TEXT NOT NULL DEFAULT 'derived';
+
+-- Token ids are looked up per market when polling order books
+CREATE INDEX IF NOT EXISTS market_outcomes_token_id_idx
+  ON market_outcomes (token_id)
+  WHERE token_id IS NOT NULL;
@@ -0,0 +1,24 @@
+-- PM Endgame Sweep - Market lifecycle tracking
+-- Migration: 20260102000002_market_status_history
+
+-- When discovery last returned the market; markets not seen recently are
+-- re-checked individually by the reconciliation pass
+ALTER TABLE markets
+  ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS markets_status_last_seen_idx
+  ON markets (status, last_seen_at);
+
+-- Every status transition observed for a market
+CREATE TABLE IF NOT EXISTS market_status_history (
+  id BIGSERIAL PRIMARY KEY,
+  market_id TEXT NOT NULL REFERENCES markets(market_id) ON DELETE CASCADE,
+  from_status TEXT NULL,
+  to_status TEXT NOT NULL,
+  source TEXT NOT NULL,
+  reason TEXT NULL,
+  changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS market_status_history_market_idx
+  ON market_status_history (market_id, changed_at DESC);
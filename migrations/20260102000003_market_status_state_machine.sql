@@ -0,0 +1,26 @@
+-- PM Endgame Sweep - Market status state machine
+-- Migration: 20260102000003_market_status_state_machine
+
+-- Only canonical status names may be stored
+ALTER TABLE markets
+  DROP CONSTRAINT IF EXISTS markets_status_check;
+
+ALTER TABLE markets
+  ADD CONSTRAINT markets_status_check
+  CHECK (status IN ('active', 'closed', 'resolved', 'halted'));
+
+-- Status changes that were not applied: either illegal ('rejected') or
+-- unusual enough to need an operator decision ('needs_review')
+CREATE TABLE IF NOT EXISTS market_status_reviews (
+  id BIGSERIAL PRIMARY KEY,
+  market_id TEXT NOT NULL REFERENCES markets(market_id) ON DELETE CASCADE,
+  current_status TEXT NOT NULL,
+  proposed_status TEXT NOT NULL,
+  verdict TEXT NOT NULL,
+  source TEXT NOT NULL,
+  reason TEXT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS market_status_reviews_market_idx
+  ON market_status_reviews (market_id, created_at DESC);
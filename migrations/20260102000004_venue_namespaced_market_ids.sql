@@ -0,0 +1,78 @@
+-- PM Endgame Sweep - Venue-namespaced market identity
+-- Migration: 20260102000004_venue_namespaced_market_ids
+
+-- markets.market_id becomes the namespaced key '<venue>:<venue_market_id>'
+-- so ids from different venues cannot collide. Child tables keep referencing
+-- markets(market_id); their foreign keys cascade the rename below.
+ALTER TABLE markets
+  ADD COLUMN IF NOT EXISTS venue_market_id TEXT NULL;
+
+ALTER TABLE market_outcomes
+  DROP CONSTRAINT IF EXISTS market_outcomes_market_id_fkey,
+  ADD CONSTRAINT market_outcomes_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE quotes_latest
+  DROP CONSTRAINT IF EXISTS quotes_latest_market_id_fkey,
+  ADD CONSTRAINT quotes_latest_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE quotes_5m
+  DROP CONSTRAINT IF EXISTS quotes_5m_market_id_fkey,
+  ADD CONSTRAINT quotes_5m_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE rules_latest
+  DROP CONSTRAINT IF EXISTS rules_latest_market_id_fkey,
+  ADD CONSTRAINT rules_latest_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE scores_latest
+  DROP CONSTRAINT IF EXISTS scores_latest_market_id_fkey,
+  ADD CONSTRAINT scores_latest_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE recs_latest
+  DROP CONSTRAINT IF EXISTS recs_latest_market_id_fkey,
+  ADD CONSTRAINT recs_latest_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE market_status_history
+  DROP CONSTRAINT IF EXISTS market_status_history_market_id_fkey,
+  ADD CONSTRAINT market_status_history_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE market_status_reviews
+  DROP CONSTRAINT IF EXISTS market_status_reviews_market_id_fkey,
+  ADD CONSTRAINT market_status_reviews_market_id_fkey
+    FOREIGN KEY (market_id) REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE;
+
+UPDATE markets
+SET venue_market_id = market_id,
+    market_id = venue || ':' || market_id
+WHERE venue_market_id IS NULL;
+
+ALTER TABLE markets
+  ALTER COLUMN venue_market_id SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS markets_venue_market_id_idx
+  ON markets (venue, venue_market_id);
+
+-- Bare venue ids are still accepted by lookups for backward compatibility
+CREATE INDEX IF NOT EXISTS markets_venue_market_id_lookup_idx
+  ON markets (venue_market_id);
+
+ALTER TABLE markets
+  DROP CONSTRAINT IF EXISTS markets_market_id_namespaced;
+
+ALTER TABLE markets
+  ADD CONSTRAINT markets_market_id_namespaced
+  CHECK (market_id = venue || ':' || venue_market_id);
@@ -0,0 +1,21 @@
+-- PM Endgame Sweep - Richer market metadata
+-- Migration: 20260102000005_market_metadata
+
+-- Venue-provided event grouping, tags, activity and display metadata
+ALTER TABLE markets
+  ADD COLUMN IF NOT EXISTS event_id TEXT NULL,
+  ADD COLUMN IF NOT EXISTS event_title TEXT NULL,
+  ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}',
+  ADD COLUMN IF NOT EXISTS volume_24h NUMERIC(20,2) NULL,
+  ADD COLUMN IF NOT EXISTS volume_total NUMERIC(20,2) NULL,
+  ADD COLUMN IF NOT EXISTS liquidity NUMERIC(20,2) NULL,
+  ADD COLUMN IF NOT EXISTS neg_risk BOOLEAN NOT NULL DEFAULT FALSE,
+  ADD COLUMN IF NOT EXISTS image_url TEXT NULL,
+  ADD COLUMN IF NOT EXISTS icon_url TEXT NULL;
+
+CREATE INDEX IF NOT EXISTS markets_event_id_idx
+  ON markets (event_id)
+  WHERE event_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS markets_tags_idx
+  ON markets USING GIN (tags);
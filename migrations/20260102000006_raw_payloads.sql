@@ -0,0 +1,24 @@
+-- PM Endgame Sweep - Raw venue payload archive
+-- Migration: 20260102000006_raw_payloads
+
+-- Compressed response bodies, kept for re-parsing after parser fixes
+CREATE TABLE IF NOT EXISTS raw_payloads (
+  id BIGSERIAL PRIMARY KEY,
+  venue TEXT NOT NULL,
+  market_id TEXT NULL,
+  endpoint TEXT NOT NULL,
+  request_url TEXT NOT NULL,
+  outcome TEXT NULL,
+  fetched_at TIMESTAMPTZ NOT NULL,
+  http_status INT NOT NULL,
+  content_encoding TEXT NOT NULL DEFAULT 'gzip',
+  body BYTEA NOT NULL,
+  body_size INT NOT NULL,
+  stored_size INT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS raw_payloads_market_endpoint_idx
+  ON raw_payloads (market_id, endpoint, fetched_at DESC);
+
+CREATE INDEX IF NOT EXISTS raw_payloads_fetched_at_idx
+  ON raw_payloads (fetched_at);
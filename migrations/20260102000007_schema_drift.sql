@@ -0,0 +1,22 @@
+-- PM Endgame Sweep - Venue response schema drift
+-- Migration: 20260102000007_schema_drift
+
+-- Fields of venue responses that differ from the recorded baseline schema
+CREATE TABLE IF NOT EXISTS schema_drift (
+  venue TEXT NOT NULL,
+  endpoint TEXT NOT NULL,
+  field TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN ('unexpected', 'missing', 'type_changed')),
+  expected TEXT NULL,
+  observed TEXT NULL,
+  critical BOOLEAN NOT NULL DEFAULT FALSE,
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  occurrences BIGINT NOT NULL DEFAULT 0,
+  first_seen_at TIMESTAMPTZ NOT NULL,
+  last_seen_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (venue, endpoint, field, kind)
+);
+
+CREATE INDEX IF NOT EXISTS schema_drift_active_idx
+  ON schema_drift (endpoint)
+  WHERE active;
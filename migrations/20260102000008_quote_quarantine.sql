@@ -0,0 +1,19 @@
+-- PM Endgame Sweep - Quote quarantine
+-- Migration: 20260102000008_quote_quarantine
+
+-- Quotes that failed sanity validation. Invalid quotes never reach
+-- quotes_latest; suspicious quotes are persisted and also recorded here.
+CREATE TABLE IF NOT EXISTS quotes_quarantine (
+  id BIGSERIAL PRIMARY KEY,
+  market_id TEXT NOT NULL REFERENCES markets(market_id) ON DELETE CASCADE,
+  as_of TIMESTAMPTZ NOT NULL,
+  validity TEXT NOT NULL CHECK (validity IN ('suspicious', 'invalid')),
+  issues JSONB NOT NULL DEFAULT '[]'::jsonb,
+  quote JSONB NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS quotes_quarantine_market_idx
+  ON quotes_quarantine (market_id, as_of DESC);
+CREATE INDEX IF NOT EXISTS quotes_quarantine_as_of_idx
+  ON quotes_quarantine (as_of);
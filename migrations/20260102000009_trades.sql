@@ -0,0 +1,20 @@
+-- PM Endgame Sweep - Trade prints
+-- Migration: 20260102000009_trades
+
+-- Recent fills per market, pruned to a bounded window by ingest
+CREATE TABLE IF NOT EXISTS trades (
+  market_id TEXT NOT NULL REFERENCES markets(market_id) ON DELETE CASCADE,
+  trade_id TEXT NOT NULL,
+  outcome TEXT NOT NULL,
+  side TEXT NOT NULL CHECK (side IN ('buy', 'sell')),
+  price NUMERIC(10,6) NOT NULL,
+  size NUMERIC(20,6) NOT NULL,
+  traded_at TIMESTAMPTZ NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (market_id, trade_id)
+);
+
+CREATE INDEX IF NOT EXISTS trades_market_traded_at_idx
+  ON trades (market_id, traded_at DESC);
+CREATE INDEX IF NOT EXISTS trades_traded_at_idx
+  ON trades (traded_at);
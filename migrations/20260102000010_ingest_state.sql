@@ -0,0 +1,21 @@
+-- PM Endgame Sweep - Ingest checkpoint state
+-- Migration: 20260102000010_ingest_state
+
+-- Per-task checkpoints, read on startup to resume schedules
+CREATE TABLE IF NOT EXISTS ingest_state (
+  task TEXT PRIMARY KEY,
+  last_success_at TIMESTAMPTZ,
+  -- Where an interrupted pass resumes (discovery page offset)
+  cursor TEXT,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Per-market fetch times, so each cycle serves the least recently fetched
+-- markets first
+CREATE TABLE IF NOT EXISTS ingest_market_state (
+  market_id TEXT PRIMARY KEY REFERENCES markets(market_id) ON DELETE CASCADE,
+  last_quote_at TIMESTAMPTZ,
+  last_rule_at TIMESTAMPTZ,
+  last_outcome_at TIMESTAMPTZ,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
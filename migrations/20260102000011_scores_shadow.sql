@@ -0,0 +1,27 @@
+-- PM Endgame Sweep - Shadow model scores
+-- Migration: 20260102000011_scores_shadow
+
+-- Latest score per market from each shadow scoring model, for comparing
+-- alternative formulas against scores_latest on live data
+CREATE TABLE IF NOT EXISTS scores_shadow (
+  model_id TEXT NOT NULL,
+  market_id TEXT NOT NULL REFERENCES markets(market_id)
+    ON DELETE CASCADE ON UPDATE CASCADE,
+  as_of TIMESTAMPTZ NOT NULL,
+  t_remaining_sec BIGINT NOT NULL,
+  gross_yield NUMERIC(10,6) NOT NULL,
+  fee_bps NUMERIC(10,6) NOT NULL,
+  net_yield NUMERIC(10,6) NOT NULL,
+  yield_velocity NUMERIC(10,6) NOT NULL,
+  liquidity_score NUMERIC(10,6) NOT NULL,
+  staleness_sec BIGINT NOT NULL,
+  staleness_penalty NUMERIC(10,6) NOT NULL,
+  definition_risk_score NUMERIC(10,6) NOT NULL,
+  overall_score NUMERIC(10,6) NOT NULL,
+  score_breakdown JSONB NOT NULL,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (model_id, market_id)
+);
+
+CREATE INDEX IF NOT EXISTS scores_shadow_overall_idx
+  ON scores_shadow (model_id, overall_score DESC);
@@ -0,0 +1,19 @@
+-- PM Endgame Sweep - Recommendation retractions
+-- Migration: 20260102000012_rec_retractions
+
+-- Scoring retracts a recommendation it no longer re-issues instead of
+-- leaving the last one in place; re-issuing clears the retraction
+ALTER TABLE recs_latest
+  ADD COLUMN IF NOT EXISTS retracted_at TIMESTAMPTZ,
+  ADD COLUMN IF NOT EXISTS retraction_reason TEXT;
+
+ALTER TABLE recs_latest
+  DROP CONSTRAINT IF EXISTS recs_latest_retraction_check;
+
+ALTER TABLE recs_latest
+  ADD CONSTRAINT recs_latest_retraction_check
+  CHECK (
+    (retracted_at IS NULL) = (retraction_reason IS NULL)
+    AND (retraction_reason IS NULL OR retraction_reason IN
+      ('expired', 'stale_quote', 'closed', 'risk_increase', 'ineligible'))
+  );
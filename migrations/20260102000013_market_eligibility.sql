@@ -0,0 +1,19 @@
+-- PM Endgame Sweep - Scoring eligibility diagnostics
+-- Migration: 20260102000013_market_eligibility
+
+-- Latest eligibility check per scoring candidate: the first gate the market
+-- failed, or no reason if it was scored. Full sweeps drop rows for markets
+-- that are no longer candidates, so the table tracks the current universe.
+CREATE TABLE IF NOT EXISTS market_eligibility (
+  market_id TEXT PRIMARY KEY REFERENCES markets(market_id) ON DELETE CASCADE,
+  checked_at TIMESTAMPTZ NOT NULL,
+  reason TEXT NULL CHECK (reason IN (
+    'missing_close_time', 'outside_window', 'low_volume', 'low_liquidity',
+    'missing_quote', 'missing_no_bid', 'missing_no_ask', 'stale_quote',
+    'missing_rule'
+  )),
+  detail TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS market_eligibility_reason_idx
+  ON market_eligibility (reason);
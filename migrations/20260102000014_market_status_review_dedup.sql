@@ -0,0 +1,21 @@
+-- PM Endgame Sweep - One open review per unapplied status change
+-- Migration: 20260102000014_market_status_review_dedup
+
+-- Discovery re-proposes the same unapplied change on every pass. A review
+-- stays open until the market's status next changes, and only one open
+-- review is kept per market, current status and proposed status.
+ALTER TABLE market_status_reviews
+  ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMPTZ NULL;
+
+DELETE FROM market_status_reviews r
+USING market_status_reviews earlier
+WHERE r.market_id = earlier.market_id
+  AND r.current_status = earlier.current_status
+  AND r.proposed_status = earlier.proposed_status
+  AND r.resolved_at IS NULL
+  AND earlier.resolved_at IS NULL
+  AND r.id > earlier.id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS market_status_reviews_open_idx
+  ON market_status_reviews (market_id, current_status, proposed_status)
+  WHERE resolved_at IS NULL;
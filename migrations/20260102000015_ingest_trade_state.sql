@@ -0,0 +1,6 @@
+-- PM Endgame Sweep - Per-market trade fetch times
+-- Migration: 20260102000015_ingest_trade_state
+
+-- Trade polling serves the least recently polled markets first, like quotes
+ALTER TABLE ingest_market_state
+  ADD COLUMN IF NOT EXISTS last_trade_at TIMESTAMPTZ;